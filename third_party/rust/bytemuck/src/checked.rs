@@ -0,0 +1,125 @@
+#![allow(clippy::module_name_repetitions)]
+
+//! Runtime-checked counterparts to the `must_cast*` family in [`must`].
+//!
+//! Where `must_cast` fails to *compile* when a cast is unsound, `try_cast`
+//! fails at *runtime* with a [`PodCastError`], which is useful when the
+//! source and target types aren't known until runtime (e.g. they come from
+//! a generic parameter that isn't bounded tightly enough for `must_cast` to
+//! type-check).
+
+use crate::{AnyBitPattern, NoUninit};
+use core::mem::{align_of, size_of};
+
+/// The error type for runtime-checked casts.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PodCastError {
+  /// The source and target types don't have the same size (for non-slice
+  /// casts), or the target type doesn't evenly divide the source (for
+  /// slice casts).
+  SizeMismatch,
+  /// The output type has a stricter alignment requirement than the input
+  /// type (for `try_cast_ref`/`try_cast_mut`), or than the input slice's
+  /// address actually satisfies (for the slice casts).
+  AlignmentMismatch,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PodCastError {}
+
+impl core::fmt::Display for PodCastError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "{:?}", self)
+  }
+}
+
+/// Convert `A` into `B` if infallible, or return an error.
+///
+/// As [`crate::must_cast`], but the size check happens at runtime instead
+/// of compile time.
+#[inline]
+pub fn try_cast<A: NoUninit, B: AnyBitPattern>(
+  a: A,
+) -> Result<B, PodCastError> {
+  if size_of::<A>() == size_of::<B>() {
+    let a = core::mem::ManuallyDrop::new(a);
+    // `&a` isn't guaranteed to be aligned for `B`, only for `A`.
+    Ok(unsafe { core::ptr::read_unaligned(&a as *const _ as *const B) })
+  } else {
+    Err(PodCastError::SizeMismatch)
+  }
+}
+
+/// Convert `&A` into `&B` if infallible, or return an error.
+#[inline]
+pub fn try_cast_ref<A: NoUninit, B: AnyBitPattern>(
+  a: &A,
+) -> Result<&B, PodCastError> {
+  if size_of::<A>() != size_of::<B>() {
+    Err(PodCastError::SizeMismatch)
+  } else if align_of::<B>() > align_of::<A>() {
+    Err(PodCastError::AlignmentMismatch)
+  } else {
+    Ok(unsafe { &*(a as *const A as *const B) })
+  }
+}
+
+/// Convert `&mut A` into `&mut B` if infallible, or return an error.
+#[inline]
+pub fn try_cast_mut<
+  A: NoUninit + AnyBitPattern,
+  B: NoUninit + AnyBitPattern,
+>(
+  a: &mut A,
+) -> Result<&mut B, PodCastError> {
+  if size_of::<A>() != size_of::<B>() {
+    Err(PodCastError::SizeMismatch)
+  } else if align_of::<B>() > align_of::<A>() {
+    Err(PodCastError::AlignmentMismatch)
+  } else {
+    Ok(unsafe { &mut *(a as *mut A as *mut B) })
+  }
+}
+
+/// Convert `&[A]` into `&[B]` (possibly with a change in length) if
+/// infallible, or return an error.
+#[inline]
+pub fn try_cast_slice<A: NoUninit, B: AnyBitPattern>(
+  a: &[A],
+) -> Result<&[B], PodCastError> {
+  if align_of::<B>() > align_of::<A>()
+    && (a.as_ptr() as usize) % align_of::<B>() != 0
+  {
+    return Err(PodCastError::AlignmentMismatch);
+  }
+  let byte_size = core::mem::size_of_val(a);
+  if size_of::<B>() == 0 || byte_size % size_of::<B>() != 0 {
+    return Err(PodCastError::SizeMismatch);
+  }
+  let new_len = byte_size / size_of::<B>();
+  Ok(unsafe { core::slice::from_raw_parts(a.as_ptr() as *const B, new_len) })
+}
+
+/// Convert `&mut [A]` into `&mut [B]` (possibly with a change in length) if
+/// infallible, or return an error.
+#[inline]
+pub fn try_cast_slice_mut<
+  A: NoUninit + AnyBitPattern,
+  B: NoUninit + AnyBitPattern,
+>(
+  a: &mut [A],
+) -> Result<&mut [B], PodCastError> {
+  if align_of::<B>() > align_of::<A>()
+    && (a.as_ptr() as usize) % align_of::<B>() != 0
+  {
+    return Err(PodCastError::AlignmentMismatch);
+  }
+  let byte_size = core::mem::size_of_val(a);
+  if size_of::<B>() == 0 || byte_size % size_of::<B>() != 0 {
+    return Err(PodCastError::SizeMismatch);
+  }
+  let new_len = byte_size / size_of::<B>();
+  Ok(unsafe {
+    core::slice::from_raw_parts_mut(a.as_mut_ptr() as *mut B, new_len)
+  })
+}
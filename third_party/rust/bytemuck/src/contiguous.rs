@@ -0,0 +1,102 @@
+use core::fmt;
+
+/// A trait indicating that:
+///
+/// 1. A type has an equivalent representation to some known integer type.
+/// 2. That all instances of this type fall in a fixed range of values.
+///
+/// This is intended to be implemented by enums of integers that don't have
+/// "gaps", and generally converting an integer into an enum using
+/// [`from_integer`] should be infallible within the declared range.
+///
+/// # Derive
+///
+/// A `#[derive(Contiguous)]` macro is provided by `bytemuck_derive`'s
+/// `derive` feature to implement this trait for a field-less enum type,
+/// computing `MIN_VALUE`/`MAX_VALUE` from the discriminants automatically —
+/// this only works when the discriminants are contiguous.
+///
+/// # Safety
+///
+/// * `Self` has the same layout as the specified `Int` type, meaning a
+///   `transmute` between them is sound.
+/// * For every value `v` that is a valid instance of `Self`,
+///   `MIN_VALUE <= (v as Int) <= MAX_VALUE`.
+pub unsafe trait Contiguous: Copy + 'static {
+  /// The primitive integer type with an identical representation to this
+  /// type.
+  ///
+  /// Most contiguous enums just use `u8`, `u16`, `u32`, `i32` and so on.
+  type Int: Copy + core::cmp::PartialOrd + Sized;
+
+  /// The smallest valid discriminant of `Self`.
+  const MIN_VALUE: Self::Int;
+
+  /// The largest valid discriminant of `Self`.
+  const MAX_VALUE: Self::Int;
+
+  /// If `value` is within the range for valid instances of this type,
+  /// converts `value` into type `Self`. Otherwise, returns `None`.
+  ///
+  /// This is almost certainly equivalent to just (safely) transmuting, and
+  /// should compile down to nothing.
+  #[inline]
+  fn from_integer(value: Self::Int) -> Option<Self> {
+    if Self::MIN_VALUE <= value && value <= Self::MAX_VALUE {
+      Some(unsafe { transmute_int(value) })
+    } else {
+      None
+    }
+  }
+
+  /// Perform the conversion from `Self` into the integer.
+  #[inline]
+  fn into_integer(self) -> Self::Int {
+    // Safety: `Self` is guaranteed by the `Contiguous` impl to have the
+    // same layout as `Self::Int`, so this is a value-preserving
+    // reinterpretation, not just a bit-pattern copy.
+    unsafe { transmute_int(self) }
+  }
+}
+
+/// Helper used by [`Contiguous::from_integer`]/[`Contiguous::into_integer`]
+/// to avoid requiring `core::mem::transmute`'s compile-time size check to
+/// see through the generic parameter (the sizes are guaranteed equal by
+/// the unsafe impl contract, not visible to the compiler).
+#[inline]
+unsafe fn transmute_int<A, B>(a: A) -> B {
+  debug_assert_eq!(
+    core::mem::size_of::<A>(),
+    core::mem::size_of::<B>(),
+    "Contiguous impl has mismatched integer size"
+  );
+  let a = core::mem::ManuallyDrop::new(a);
+  // `&a` isn't guaranteed to be aligned for `B`, only for `A`.
+  core::ptr::read_unaligned(&a as *const _ as *const B)
+}
+
+/// The error returned by [`must_cast_contiguous`] when the integer is out of
+/// the target enum's declared range.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ContiguousRangeError;
+
+impl fmt::Display for ContiguousRangeError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "integer value outside the range of the target Contiguous type")
+  }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ContiguousRangeError {}
+
+/// Convert an integer into a [`Contiguous`] enum, or return an error if it's
+/// out of range.
+///
+/// This is a thin, fallible wrapper over [`Contiguous::from_integer`] for
+/// callers who want a `Result` rather than an `Option`.
+#[inline]
+pub fn must_cast_contiguous<C: Contiguous>(
+  value: C::Int,
+) -> Result<C, ContiguousRangeError> {
+  C::from_integer(value).ok_or(ContiguousRangeError)
+}
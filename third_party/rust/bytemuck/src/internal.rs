@@ -0,0 +1,54 @@
+//! Marker traits that let `Option<T>` itself participate in the
+//! `must_cast`/`try_cast` family, for the common case of a niche-optimized
+//! `T` (e.g. `NonZeroU32`, `core::num::NonZero*`, or a `#[repr(transparent)]`
+//! wrapper around one) whose `None` representation is all-zero bits.
+//!
+//! A type opting into [`ZeroableInOption`] asserts that `Option<Self>` has
+//! the same size as `Self` and represents `None` as all-zero bytes; that's
+//! enough for `Option<Self>: Zeroable`. [`PodInOption`] further asserts that
+//! every bit pattern of that same size is a valid `Option<Self>`, which is
+//! enough for `Option<Self>: AnyBitPattern`, and therefore valid as a
+//! `must_cast`/`try_cast` target without an extra wrapper type.
+
+use crate::{AnyBitPattern, Zeroable};
+
+/// Implemented for niche-optimized `T` where `Option<T>`'s `None` value is
+/// represented as all-zero bytes, so `Option<T>: Zeroable` is sound.
+///
+/// # Safety
+///
+/// * `Option<Self>` must have the same size as `Self`.
+/// * The all-zero bit pattern of that size must represent `Option::None`.
+pub unsafe trait ZeroableInOption: Sized {}
+
+/// Implemented for niche-optimized `T` where every bit pattern of
+/// `Option<T>`'s size is a valid `Option<T>`, so `Option<T>: AnyBitPattern`
+/// is sound.
+///
+/// # Safety
+///
+/// * `Self: ZeroableInOption`.
+/// * Every bit pattern of `size_of::<Option<Self>>()` bytes must be a valid
+///   `Option<Self>` (either `None`, or `Some` of a valid `Self`).
+pub unsafe trait PodInOption: ZeroableInOption {}
+
+unsafe impl<T: ZeroableInOption> Zeroable for Option<T> {}
+unsafe impl<T: PodInOption> AnyBitPattern for Option<T> {}
+
+macro_rules! impl_zeroable_pod_in_option {
+  ($($nonzero:ty),* $(,)?) => {
+    $(
+      unsafe impl ZeroableInOption for $nonzero {}
+      unsafe impl PodInOption for $nonzero {}
+    )*
+  };
+}
+
+impl_zeroable_pod_in_option! {
+  core::num::NonZeroU8, core::num::NonZeroI8,
+  core::num::NonZeroU16, core::num::NonZeroI16,
+  core::num::NonZeroU32, core::num::NonZeroI32,
+  core::num::NonZeroU64, core::num::NonZeroI64,
+  core::num::NonZeroU128, core::num::NonZeroI128,
+  core::num::NonZeroUsize, core::num::NonZeroIsize,
+}
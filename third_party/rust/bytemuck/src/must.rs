@@ -6,6 +6,9 @@
 use crate::{AnyBitPattern, NoUninit};
 use core::mem::{align_of, size_of};
 
+#[cfg(feature = "extern_crate_alloc")]
+use alloc::{boxed::Box, vec::Vec};
+
 struct Cast<A, B>((A, B));
 impl<A, B> Cast<A, B> {
   const ASSERT_ALIGN_GREATER_THAN_EQUAL: () =
@@ -201,3 +204,77 @@ pub fn must_cast_slice_mut<
   };
   unsafe { core::slice::from_raw_parts_mut(a.as_mut_ptr() as *mut B, new_len) }
 }
+
+/// Convert a `Box<A>` into a `Box<B>` if infalliable, or fail to compile.
+///
+/// Unlike [`must_cast`], this reuses the existing heap allocation rather
+/// than copying the value, so it's zero-copy as long as `A` and `B` have
+/// the same alignment; if the alignments differ the value is copied into a
+/// newly allocated box.
+///
+/// ## Failure
+///
+/// * If the types don't have the same size this fails to compile.
+#[cfg(feature = "extern_crate_alloc")]
+#[inline]
+pub fn must_cast_box<A: NoUninit, B: AnyBitPattern>(
+  a: Box<A>,
+) -> Box<B> {
+  let _ = Cast::<A, B>::ASSERT_SIZE_EQUAL;
+  if align_of::<B>() == align_of::<A>() {
+    let raw: *mut A = Box::into_raw(a);
+    unsafe { Box::from_raw(raw as *mut B) }
+  } else {
+    let b: B = must_cast(*a);
+    Box::new(b)
+  }
+}
+
+/// Convert a `Vec<A>` into a `Vec<B>` (possibly with a change in length and
+/// capacity) if infalliable, or fail to compile.
+///
+/// As with [`must_cast_slice`], `A`'s size must be an even multiple of `B`'s.
+/// When `B`'s alignment equals `A`'s, the existing allocation is reused in
+/// place (`O(1)`, no copy); otherwise the elements are copied into a freshly
+/// allocated `Vec`.
+///
+/// ## Failure
+///
+/// * If the target element type doesn't evenly fit into the current element
+///   type this fails to compile. A difference in alignment is not a failure;
+///   it only selects the copying path.
+#[cfg(feature = "extern_crate_alloc")]
+#[inline]
+pub fn must_cast_vec<A: NoUninit, B: AnyBitPattern>(
+  mut a: Vec<A>,
+) -> Vec<B> {
+  let _ = Cast::<A, B>::ASSERT_SIZE_MULTIPLE_OF;
+  if align_of::<B>() == align_of::<A>() {
+    let a_len = a.len();
+    let a_cap = a.capacity();
+    let ptr: *mut A = a.as_mut_ptr();
+    core::mem::forget(a);
+    let ratio = size_of::<A>() / size_of::<B>().max(1);
+    let (new_len, new_cap) = if size_of::<A>() == size_of::<B>() {
+      (a_len, a_cap)
+    } else {
+      (a_len * ratio, a_cap * ratio)
+    };
+    unsafe { Vec::from_raw_parts(ptr as *mut B, new_len, new_cap) }
+  } else {
+    // Can't go through `must_cast_slice` here: its
+    // `ASSERT_ALIGN_GREATER_THAN_EQUAL` is a `const` item, so it gets
+    // evaluated at monomorphization time for *every* instantiation of this
+    // function, including this one, where `B`'s alignment may be stricter
+    // than `A`'s -- it would then fail to compile. Copy the
+    // bytes out manually instead, one (possibly misaligned) `B` at a time.
+    let byte_ptr = a.as_ptr() as *const u8;
+    let out_len = (a.len() * size_of::<A>()) / size_of::<B>().max(1);
+    let mut out = Vec::with_capacity(out_len);
+    for i in 0..out_len {
+      let src = unsafe { byte_ptr.add(i * size_of::<B>()) as *const B };
+      out.push(unsafe { core::ptr::read_unaligned(src) });
+    }
+    out
+  }
+}
@@ -0,0 +1,39 @@
+/// Computes the byte offset of `$field` within `#[repr(C)]` struct `$Type`.
+///
+/// This never constructs a real instance of `$Type` — it works entirely on
+/// a pointer into uninitialized `MaybeUninit<$Type>` storage that is never
+/// read, so it's sound even for types that
+/// are expensive, impossible, or unsafe to construct (e.g. structs with a
+/// private constructor, or with uninit padding that would trip up other
+/// approaches).
+///
+/// `$Type` must be `#[repr(C)]` (or `#[repr(C, ...)]`); offsets within a
+/// default-`#[repr(Rust)]` struct are unspecified and this macro cannot give
+/// a meaningful answer for them.
+///
+/// ## Example
+/// ```
+/// # use bytemuck::offset_of;
+/// #[repr(C)]
+/// struct Foo {
+///   a: u8,
+///   b: u32,
+/// }
+///
+/// assert_eq!(offset_of!(Foo, a), 0);
+/// assert_eq!(offset_of!(Foo, b), 4);
+/// ```
+#[macro_export]
+macro_rules! offset_of {
+  ($Type:path, $field:ident) => {{
+    #[allow(unused_unsafe)]
+    unsafe {
+      let uninit = $crate::__core::mem::MaybeUninit::<$Type>::uninit();
+      let base_ptr: *const $Type = uninit.as_ptr();
+      #[allow(clippy::unneeded_field_pattern)]
+      let field_ptr =
+        $crate::__core::ptr::addr_of!((*base_ptr).$field);
+      (field_ptr as *const u8).offset_from(base_ptr as *const u8) as usize
+    }
+  }};
+}
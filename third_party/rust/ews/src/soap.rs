@@ -0,0 +1,285 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! The SOAP envelope used to wrap EWS requests and responses.
+//!
+//! EWS requests are plain SOAP: a `<soap:Envelope>` containing an optional
+//! `<soap:Header>` and a mandatory `<soap:Body>` holding the [`Operation`]
+//! being requested. Responses mirror that shape, with the server's
+//! `ServerVersionInfo` appearing in the header and the [`OperationResponse`]
+//! in the body.
+
+use std::fmt;
+use std::time::Duration;
+
+use serde::Deserialize;
+use xml_struct::XmlSerialize;
+
+use crate::types::operations::{Operation, OperationResponse, ResponseCode, ResponseClass};
+
+/// A SOAP envelope carrying an EWS request.
+///
+/// See [module][self] documentation for details.
+#[derive(Clone, Debug, XmlSerialize)]
+pub struct Envelope<T: Operation> {
+    /// The headers which accompany this request, if any.
+    pub headers: Headers,
+
+    /// The operation to perform, serialized as the contents of the SOAP
+    /// body.
+    pub body: T,
+}
+
+impl<T: Operation> Envelope<T> {
+    /// Creates an envelope for `body` with no SOAP headers attached.
+    pub fn new(body: T) -> Self {
+        Self {
+            headers: Headers::default(),
+            body,
+        }
+    }
+
+    /// Creates an envelope for `body` carrying the given `headers`.
+    pub fn with_headers(body: T, headers: Headers) -> Self {
+        Self { headers, body }
+    }
+}
+
+/// A SOAP envelope carrying an EWS response.
+///
+/// Unlike [`Envelope`], this is only ever deserialized from a server
+/// response, never serialized.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ResponseEnvelope<T: OperationResponse> {
+    /// The headers returned by the server, if any.
+    #[serde(default)]
+    pub header: ResponseHeaders,
+
+    /// The contents of the SOAP body: either the operation response, or a
+    /// transport-level fault.
+    pub body: ResponseBody<T>,
+}
+
+impl<T: OperationResponse> ResponseEnvelope<T> {
+    /// Unwraps the envelope's body, surfacing a `<soap:Fault>` as an
+    /// [`EwsError::Fault`].
+    ///
+    /// This only catches transport-level failures. An operation that ran
+    /// but reported `ResponseClass="Error"` on (some of) its results still
+    /// comes back as `Ok`; inspect the response's own response messages
+    /// (see [`ItemResponseMessage`][crate::types::operations::ItemResponseMessage])
+    /// to detect those.
+    pub fn into_result(self) -> Result<T, EwsError> {
+        match self.body {
+            ResponseBody::Success(body) => Ok(body),
+            ResponseBody::Fault(fault) => Err(EwsError::Fault(fault)),
+        }
+    }
+}
+
+/// The contents of a SOAP response body, which is either the operation
+/// response EWS was asked for, or a `<soap:Fault>` describing why it
+/// couldn't even attempt the operation.
+#[derive(Clone, Debug, Deserialize)]
+pub enum ResponseBody<T> {
+    Fault(SoapFault),
+    #[serde(untagged)]
+    Success(T),
+}
+
+/// A transport-level SOAP fault.
+///
+/// EWS returns this instead of a normal operation response for failures
+/// that prevent it from processing the request at all, such as malformed
+/// XML, authentication failures, or being throttled outright.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SoapFault {
+    pub faultcode: String,
+    pub faultstring: String,
+    pub detail: Option<String>,
+}
+
+/// An error encountered while interpreting an EWS SOAP response.
+///
+/// This distinguishes a transport-level [`SoapFault`] — returned for
+/// malformed requests, authentication failures, or throttling before EWS
+/// even attempts the operation — from an operation that ran but reported a
+/// failure via `ResponseClass="Error"` and a `ResponseCode`.
+#[derive(Clone, Debug)]
+pub enum EwsError {
+    /// The server rejected the request itself, before running the
+    /// operation.
+    Fault(SoapFault),
+
+    /// The operation ran but reported a failure via `ResponseCode`.
+    ResponseCode {
+        code: ResponseCode,
+        message: Option<String>,
+        /// How long the server is asking the caller to wait before
+        /// retrying, if it provided one (see `ErrorServerBusy`).
+        back_off: Option<Duration>,
+    },
+}
+
+impl fmt::Display for EwsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EwsError::Fault(fault) => write!(f, "SOAP fault {}: {}", fault.faultcode, fault.faultstring),
+            EwsError::ResponseCode { code, message, .. } => match message {
+                Some(message) => write!(f, "{code:?}: {message}"),
+                None => write!(f, "{code:?}"),
+            },
+        }
+    }
+}
+
+impl std::error::Error for EwsError {}
+
+impl From<SoapFault> for EwsError {
+    fn from(fault: SoapFault) -> Self {
+        EwsError::Fault(fault)
+    }
+}
+
+/// The SOAP header elements which may accompany an EWS request.
+///
+/// Each field is optional; only the headers a particular operation needs
+/// are serialized into the `<soap:Header>` block. Use [`Headers::builder`]
+/// to construct one.
+#[derive(Clone, Debug, Default, XmlSerialize)]
+pub struct Headers {
+    /// Pins the schema version EWS should use to interpret and respond to
+    /// the request.
+    pub request_server_version: Option<RequestServerVersion>,
+
+    /// Requests that the operation be performed on behalf of another
+    /// mailbox.
+    pub exchange_impersonation: Option<ExchangeImpersonation>,
+
+    /// The locale EWS should use when formatting culture-sensitive values
+    /// in the response.
+    pub mailbox_culture: Option<MailboxCulture>,
+
+    /// The time zone EWS should use when rendering date/time values in the
+    /// response.
+    pub time_zone_context: Option<TimeZoneContext>,
+}
+
+impl Headers {
+    /// Creates a builder for assembling a [`Headers`] value one header at a
+    /// time.
+    pub fn builder() -> HeadersBuilder {
+        HeadersBuilder::default()
+    }
+}
+
+/// A builder for [`Headers`].
+#[derive(Clone, Debug, Default)]
+pub struct HeadersBuilder {
+    headers: Headers,
+}
+
+impl HeadersBuilder {
+    /// Sets the schema version EWS should target for this request.
+    pub fn request_server_version(mut self, version: impl Into<String>) -> Self {
+        self.headers.request_server_version = Some(RequestServerVersion {
+            version: version.into(),
+        });
+        self
+    }
+
+    /// Requests impersonation of the mailbox identified by
+    /// `primary_smtp_address`.
+    pub fn exchange_impersonation(mut self, primary_smtp_address: impl Into<String>) -> Self {
+        self.headers.exchange_impersonation = Some(ExchangeImpersonation {
+            connecting_sid: ConnectingSid {
+                primary_smtp_address: primary_smtp_address.into(),
+            },
+        });
+        self
+    }
+
+    /// Sets the locale EWS should use for culture-sensitive response
+    /// values.
+    pub fn mailbox_culture(mut self, culture: impl Into<String>) -> Self {
+        self.headers.mailbox_culture = Some(MailboxCulture(culture.into()));
+        self
+    }
+
+    /// Sets the time zone EWS should use when rendering date/time values,
+    /// identified by its Windows time zone ID (e.g. `"Pacific Standard
+    /// Time"`).
+    pub fn time_zone_context(mut self, time_zone_id: impl Into<String>) -> Self {
+        self.headers.time_zone_context = Some(TimeZoneContext {
+            time_zone_definition: TimeZoneDefinition {
+                id: time_zone_id.into(),
+            },
+        });
+        self
+    }
+
+    /// Finishes building, returning the assembled [`Headers`].
+    pub fn build(self) -> Headers {
+        self.headers
+    }
+}
+
+/// Pins the schema version EWS should use to interpret a request and render
+/// its response, per `RequestServerVersion`.
+#[derive(Clone, Debug, XmlSerialize)]
+pub struct RequestServerVersion {
+    pub version: String,
+}
+
+/// Requests that EWS perform the operation as another mailbox, per
+/// `ExchangeImpersonation`.
+#[derive(Clone, Debug, XmlSerialize)]
+pub struct ExchangeImpersonation {
+    pub connecting_sid: ConnectingSid,
+}
+
+/// Identifies the mailbox to impersonate by its primary SMTP address.
+#[derive(Clone, Debug, XmlSerialize)]
+pub struct ConnectingSid {
+    pub primary_smtp_address: String,
+}
+
+/// The locale EWS should use for culture-sensitive values in the response,
+/// per `MailboxCulture`.
+#[derive(Clone, Debug, XmlSerialize)]
+pub struct MailboxCulture(pub String);
+
+/// The time zone EWS should use when rendering date/time values in the
+/// response, per `TimeZoneContext`.
+#[derive(Clone, Debug, XmlSerialize)]
+pub struct TimeZoneContext {
+    pub time_zone_definition: TimeZoneDefinition,
+}
+
+/// Identifies a time zone by its Windows time zone ID.
+#[derive(Clone, Debug, XmlSerialize)]
+pub struct TimeZoneDefinition {
+    pub id: String,
+}
+
+/// The SOAP header elements EWS returns in a response.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ResponseHeaders {
+    /// The schema version the server actually used to process the request.
+    pub server_version_info: Option<ServerVersionInfo>,
+}
+
+/// The Exchange server version which produced a response, as returned in
+/// the `ServerVersionInfo` SOAP header.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ServerVersionInfo {
+    pub major_version: u32,
+    pub minor_version: u32,
+    pub major_build_number: u32,
+    pub minor_build_number: u32,
+}
@@ -0,0 +1,301 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Change-notification subscriptions: `Subscribe`, `GetEvents`,
+//! `GetStreamingEvents`, and `Unsubscribe`.
+//!
+//! EWS offers a handful of subscription styles; this module models the two
+//! Thunderbird cares about. A *pull* subscription ([`SubscriptionType::Pull`])
+//! is polled periodically with [`GetEvents`], which returns whatever events
+//! have accumulated since the last poll (or the subscription's creation) and
+//! completes immediately. A *streaming* subscription
+//! ([`SubscriptionType::Streaming`]) is instead paired with
+//! [`GetStreamingEvents`], whose response holds the HTTP connection open and
+//! emits a sequence of `<Envelope>` chunks over time rather than a single
+//! one; see [`StreamingEventIter`] for how those are consumed.
+
+use std::io::{BufRead, Read};
+
+use serde::Deserialize;
+use xml_struct::XmlSerialize;
+
+use super::operations::{sealed::EnvelopeBodyContents, Operation, OperationResponse};
+
+/// Requests a new subscription to change notifications on one or more
+/// folders, via `Subscribe`.
+#[derive(Clone, Debug, XmlSerialize)]
+pub struct Subscribe {
+    pub folder_ids: Vec<String>,
+    pub event_types: Vec<EventType>,
+    pub subscription_type: SubscriptionType,
+}
+
+impl Operation for Subscribe {
+    type Response = SubscribeResponse;
+}
+
+impl EnvelopeBodyContents for Subscribe {
+    fn name() -> &'static str {
+        "Subscribe"
+    }
+}
+
+/// Distinguishes the two subscription styles EWS offers that Thunderbird
+/// uses.
+///
+/// This isn't serialized directly; it instead picks which subscription
+/// request element (`PullSubscriptionRequest` or
+/// `StreamingSubscriptionRequest`) [`Subscribe`] serializes as.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SubscriptionType {
+    /// Paired with [`GetEvents`], polled periodically by the caller.
+    Pull,
+
+    /// Paired with [`GetStreamingEvents`], held open by the server.
+    Streaming,
+}
+
+/// The categories of change a subscription can be notified of.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, XmlSerialize)]
+pub enum EventType {
+    NewMailEvent,
+    CreatedEvent,
+    DeletedEvent,
+    ModifiedEvent,
+    MovedEvent,
+    CopiedEvent,
+    FreeBusyChangedEvent,
+}
+
+/// The response to a [`Subscribe`] request.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SubscribeResponse {
+    /// Identifies this subscription for subsequent `GetEvents`,
+    /// `GetStreamingEvents`, and `Unsubscribe` calls.
+    pub subscription_id: String,
+
+    /// Opaque state a pull subscription's next `GetEvents` call must echo
+    /// back, so the server knows which events it has already sent.
+    ///
+    /// Streaming subscriptions don't use watermarks: the connection itself
+    /// is the continuation state.
+    pub watermark: Option<String>,
+}
+
+impl OperationResponse for SubscribeResponse {}
+
+impl EnvelopeBodyContents for SubscribeResponse {
+    fn name() -> &'static str {
+        "SubscribeResponse"
+    }
+}
+
+/// Polls a pull subscription for events accumulated since `watermark`, via
+/// `GetEvents`.
+#[derive(Clone, Debug, XmlSerialize)]
+pub struct GetEvents {
+    pub subscription_id: String,
+    pub watermark: String,
+}
+
+impl Operation for GetEvents {
+    type Response = GetEventsResponse;
+}
+
+impl EnvelopeBodyContents for GetEvents {
+    fn name() -> &'static str {
+        "GetEvents"
+    }
+}
+
+/// The response to a [`GetEvents`] request.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetEventsResponse {
+    pub notification: Notification,
+}
+
+impl OperationResponse for GetEventsResponse {}
+
+impl EnvelopeBodyContents for GetEventsResponse {
+    fn name() -> &'static str {
+        "GetEventsResponse"
+    }
+}
+
+/// Opens a streaming subscription's event channel, via
+/// `GetStreamingEvents`.
+///
+/// Unlike every other [`Operation`], this one's response isn't a single
+/// [`OperationResponse`] value: the server keeps the HTTP response open and
+/// writes a sequence of `<Envelope>` chunks to it over time. Decode those
+/// with [`StreamingEventIter`] rather than the usual one-shot
+/// `ResponseEnvelope` deserialization.
+#[derive(Clone, Debug, XmlSerialize)]
+pub struct GetStreamingEvents {
+    pub subscription_ids: Vec<String>,
+
+    /// How long, in minutes, the server should hold the connection open
+    /// between heartbeats before EWS closes it and the caller must
+    /// reconnect.
+    pub connection_timeout: u32,
+}
+
+impl Operation for GetStreamingEvents {
+    type Response = GetStreamingEventsResponse;
+}
+
+impl EnvelopeBodyContents for GetStreamingEvents {
+    fn name() -> &'static str {
+        "GetStreamingEvents"
+    }
+}
+
+/// The response to a single `<Envelope>` chunk of a `GetStreamingEvents`
+/// channel.
+///
+/// A full channel is a sequence of these; see [`StreamingEventIter`].
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetStreamingEventsResponse {
+    pub connection_status: ConnectionStatus,
+
+    #[serde(default)]
+    pub notification: Option<Notification>,
+}
+
+impl OperationResponse for GetStreamingEventsResponse {}
+
+impl EnvelopeBodyContents for GetStreamingEventsResponse {
+    fn name() -> &'static str {
+        "GetStreamingEventsResponse"
+    }
+}
+
+/// Whether a streaming channel chunk carries an event or is just a
+/// keep-alive heartbeat.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+pub enum ConnectionStatus {
+    OK,
+    Closed,
+}
+
+/// Ends a subscription, via `Unsubscribe`.
+///
+/// Pull subscriptions expire on their own if not polled, but streaming
+/// subscriptions should be explicitly torn down when the caller is done
+/// with them, since the server otherwise holds the connection open until
+/// its timeout elapses.
+#[derive(Clone, Debug, XmlSerialize)]
+pub struct Unsubscribe {
+    pub subscription_id: String,
+}
+
+impl Operation for Unsubscribe {
+    type Response = UnsubscribeResponse;
+}
+
+impl EnvelopeBodyContents for Unsubscribe {
+    fn name() -> &'static str {
+        "Unsubscribe"
+    }
+}
+
+/// The (empty) response to an [`Unsubscribe`] request.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct UnsubscribeResponse {}
+
+impl OperationResponse for UnsubscribeResponse {}
+
+impl EnvelopeBodyContents for UnsubscribeResponse {
+    fn name() -> &'static str {
+        "UnsubscribeResponse"
+    }
+}
+
+/// A batch of changes reported by a subscription, keyed by the folder and
+/// item IDs they affect.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Notification {
+    pub subscription_id: String,
+
+    #[serde(default)]
+    pub created_event: Vec<NotificationEvent>,
+
+    #[serde(default)]
+    pub modified_event: Vec<NotificationEvent>,
+
+    #[serde(default)]
+    pub deleted_event: Vec<NotificationEvent>,
+
+    #[serde(default)]
+    pub new_mail_event: Vec<NotificationEvent>,
+}
+
+/// A single change reported within a [`Notification`].
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct NotificationEvent {
+    pub timestamp: String,
+    pub folder_id: Option<String>,
+    pub item_id: Option<String>,
+}
+
+/// Incrementally decodes the `<Envelope>` chunks of a
+/// [`GetStreamingEvents`] response as they arrive, without buffering the
+/// whole (potentially unbounded) channel in memory.
+///
+/// Constructed over the response body's reader; each call to [`next`] reads
+/// and deserializes exactly one envelope, yielding its
+/// [`GetStreamingEventsResponse`]. Heartbeats (`ConnectionStatus::OK` with
+/// no notification) are yielded like any other chunk rather than being
+/// filtered out, so callers can use their arrival as a liveness signal;
+/// [`Closed`] marks the end of the channel.
+///
+/// [`next`]: Iterator::next
+/// [`Closed`]: ConnectionStatus::Closed
+pub struct StreamingEventIter<R> {
+    reader: R,
+    closed: bool,
+}
+
+impl<R> StreamingEventIter<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            closed: false,
+        }
+    }
+}
+
+impl<R> Iterator for StreamingEventIter<R>
+where
+    R: Read + BufRead,
+{
+    type Item = Result<GetStreamingEventsResponse, quick_xml::de::DeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.closed {
+            return None;
+        }
+
+        let event: GetStreamingEventsResponse =
+            match quick_xml::de::from_reader(&mut self.reader) {
+                Ok(event) => event,
+                Err(err) => {
+                    self.closed = true;
+                    return Some(Err(err));
+                }
+            };
+
+        if event.connection_status == ConnectionStatus::Closed {
+            self.closed = true;
+        }
+
+        Some(Ok(event))
+    }
+}
@@ -33,6 +33,102 @@ pub trait Operation: XmlSerialize + sealed::EnvelopeBodyContents {
 /// [`Envelope`]: crate::soap::Envelope
 pub trait OperationResponse: for<'de> Deserialize<'de> + sealed::EnvelopeBodyContents {}
 
+/// A marker trait for EWS operations which act on a batch of inputs, such as
+/// `GetItem`, `DeleteItem`, `UpdateItem`, `MoveItem`, `CopyItem`, or
+/// `SendItem`, each yielding one independently successful-or-failed response
+/// per input.
+///
+/// Implementing this in addition to [`Operation`] lets callers correlate
+/// each element of [`ids`] with the matching element of the response's
+/// [`BatchOperationResponse::response_messages`], instead of treating the
+/// whole request as succeeding or failing atomically.
+///
+/// [`ids`]: BatchOperation::ids
+pub trait BatchOperation: Operation {
+    /// The type of identifier this operation batches over, e.g. an item or
+    /// folder ID.
+    type Id;
+
+    /// The IDs this operation will act on, in request order.
+    fn ids(&self) -> &[Self::Id];
+}
+
+/// A marker trait for the response to a [`BatchOperation`].
+pub trait BatchOperationResponse: OperationResponse {
+    /// The per-item results, in the same order as the request's IDs, so
+    /// callers can zip the two together for correlation.
+    fn response_messages(&self) -> &[ItemResponseMessage];
+}
+
+/// The outcome of a single item within a batched operation's response.
+///
+/// EWS reports per-item results via `ResponseClass`/`ResponseCode` rather
+/// than failing an entire batch when, say, one of a hundred items to delete
+/// no longer exists.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ItemResponseMessage {
+    /// Whether this item's portion of the operation succeeded, produced a
+    /// warning, or failed.
+    pub response_class: ResponseClass,
+
+    /// The specific result code for this item, e.g. `NoError` or
+    /// `ErrorItemNotFound`.
+    pub response_code: Option<ResponseCode>,
+
+    /// A human-readable description of the result, present when
+    /// `response_class` is not [`ResponseClass::Success`].
+    pub message_text: Option<String>,
+
+    /// Additional machine-readable detail accompanying the result, such as
+    /// the back-off hint on `ErrorServerBusy`.
+    pub message_xml: Option<MessageXml>,
+}
+
+/// The specific result reported for an operation or a single item within a
+/// batched operation, via the `ResponseCode` element.
+///
+/// This is `#[non_exhaustive]` and falls back to [`ResponseCode::Other`] for
+/// any code EWS returns that isn't modeled explicitly yet, since the schema
+/// defines several hundred of them and most callers only care about a
+/// handful.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[non_exhaustive]
+pub enum ResponseCode {
+    NoError,
+    ErrorItemNotFound,
+    ErrorFolderNotFound,
+    ErrorAccessDenied,
+    ErrorServerBusy,
+    ErrorInvalidChangeKey,
+    ErrorMailboxStoreUnavailable,
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// Machine-readable detail attached to a response message, carried in the
+/// `MessageXml` element.
+///
+/// Today this only models the `BackOffMilliseconds` hint EWS includes on
+/// `ErrorServerBusy` so callers can implement a server-directed retry
+/// delay; other detail elements are ignored.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MessageXml {
+    /// How long, in milliseconds, the server is asking the caller to wait
+    /// before retrying.
+    pub back_off_milliseconds: Option<u64>,
+}
+
+/// Whether an individual response message represents success, a recoverable
+/// warning, or an outright error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+pub enum ResponseClass {
+    Success,
+    Warning,
+    Error,
+}
+
 pub(super) mod sealed {
     /// A trait for structures which may appear in the body of a SOAP envelope.
     pub trait EnvelopeBodyContents {
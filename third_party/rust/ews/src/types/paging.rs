@@ -0,0 +1,167 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Typed paging support for `FindItem`/`FindFolder`-style operations.
+//!
+//! These operations return a capped window of results alongside paging
+//! metadata rather than everything that matches at once. [`PagingRequest`]
+//! and [`PagingResponse`] model that window; [`Paginated`] ties an
+//! [`Operation`] to the paging types it uses, and [`PageCursor`] is the
+//! higher-level helper that walks every page in order.
+
+use std::marker::PhantomData;
+
+use xml_struct::XmlSerialize;
+
+use super::operations::{Operation, OperationResponse};
+
+/// An indexed request window: `offset` results to skip, `page_size` to
+/// return, serialized as `IndexedPageItemView`.
+#[derive(Clone, Copy, Debug, XmlSerialize)]
+pub struct PagingRequest {
+    pub base_point: BasePoint,
+    pub offset: u32,
+    pub max_entries_returned: u32,
+}
+
+impl PagingRequest {
+    /// Creates a request for the first `page_size` results.
+    pub fn first_page(page_size: u32) -> Self {
+        Self {
+            base_point: BasePoint::Beginning,
+            offset: 0,
+            max_entries_returned: page_size,
+        }
+    }
+
+    /// Creates a request for the next `page_size` results starting at
+    /// `offset`.
+    pub fn at_offset(offset: u32, page_size: u32) -> Self {
+        Self {
+            base_point: BasePoint::Beginning,
+            offset,
+            max_entries_returned: page_size,
+        }
+    }
+}
+
+/// Whether `PagingRequest::offset` counts from the start or the end of the
+/// result set.
+#[derive(Clone, Copy, Debug, XmlSerialize)]
+pub enum BasePoint {
+    Beginning,
+    End,
+}
+
+/// The paging metadata EWS attaches to a `FindItem`/`FindFolder` response,
+/// mirroring `IndexedPageItemView`'s response attributes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PagingResponse {
+    #[serde(rename = "@TotalItemsInView")]
+    pub total_items_in_view: u32,
+
+    #[serde(rename = "@IncludesLastItemInRange")]
+    pub includes_last_item_in_range: bool,
+
+    #[serde(rename = "@IndexedPagingOffset", default)]
+    pub next_offset: Option<u32>,
+}
+
+/// Ties a paged [`Operation`] to the paging request/response shape it uses
+/// and the items each page yields, so [`PageCursor`] can build the next
+/// page's request generically.
+pub trait Paginated: Operation {
+    /// The type of item a page of this operation's response yields, e.g.
+    /// an item or folder summary.
+    type Item;
+
+    /// Builds a request for the given page, reusing every other field of
+    /// `self`.
+    fn with_paging(&self, paging: PagingRequest) -> Self;
+
+    /// The paging metadata reported in `response`.
+    fn paging(response: &<Self as Operation>::Response) -> PagingResponse;
+
+    /// The items `response` yielded for its page.
+    fn items(response: <Self as Operation>::Response) -> Vec<Self::Item>;
+}
+
+/// Lazily walks every page of a [`Paginated`] operation, advancing the
+/// offset until the server reports `IncludesLastItemInRange`, so callers
+/// don't have to thread paging state themselves.
+///
+/// This only tracks *what request to issue next*; it doesn't perform I/O
+/// itself; call [`next_request`] to get the request for the next page,
+/// submit it however the caller submits any other operation, then feed the
+/// response back through [`record_response`] before asking for the
+/// request after that. [`is_done`] reports whether the last page has
+/// already been seen.
+///
+/// [`next_request`]: PageCursor::next_request
+/// [`record_response`]: PageCursor::record_response
+/// [`is_done`]: PageCursor::is_done
+pub struct PageCursor<T: Paginated> {
+    template: T,
+    page_size: u32,
+    next_offset: Option<u32>,
+    done: bool,
+    _response: PhantomData<fn() -> T::Response>,
+}
+
+impl<T: Paginated> PageCursor<T> {
+    /// Creates a cursor that will page through `template`'s results
+    /// `page_size` at a time, starting from the first page.
+    pub fn new(template: T, page_size: u32) -> Self {
+        Self {
+            template,
+            page_size,
+            next_offset: Some(0),
+            done: false,
+            _response: PhantomData,
+        }
+    }
+
+    /// Whether every page has already been retrieved.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Builds the request for the next page, or `None` if [`is_done`]
+    /// returns `true`.
+    ///
+    /// [`is_done`]: Self::is_done
+    pub fn next_request(&self) -> Option<T> {
+        let offset = self.next_offset?;
+        Some(self.template.with_paging(PagingRequest::at_offset(offset, self.page_size)))
+    }
+
+    /// Records the response to the request returned by the preceding call
+    /// to [`next_request`], updating the cursor's offset and advancing
+    /// [`is_done`] once the server reports the last item has been seen.
+    ///
+    /// Returns the page's items.
+    ///
+    /// [`next_request`]: Self::next_request
+    /// [`is_done`]: Self::is_done
+    pub fn record_response(&mut self, response: T::Response) -> Vec<T::Item> {
+        let paging = T::paging(&response);
+        let current_offset = self.next_offset.unwrap_or(0);
+
+        self.done = paging.includes_last_item_in_range;
+        self.next_offset = if self.done {
+            None
+        } else {
+            // Without an explicit next offset from the server, step past the
+            // page just requested.
+            Some(
+                paging
+                    .next_offset
+                    .unwrap_or(current_offset.saturating_add(self.page_size)),
+            )
+        };
+
+        T::items(response)
+    }
+}
@@ -91,10 +91,9 @@ s! {
 }
 
 s_no_extra_traits! {
-    // FIXME: This is actually a union.
-    pub struct fpreg_t {
+    pub union fpreg_t {
         pub d: ::c_double,
-        // f: ::c_float,
+        pub f: ::c_float,
     }
 }
 
@@ -102,7 +101,7 @@ cfg_if! {
     if #[cfg(feature = "extra_traits")] {
         impl PartialEq for fpreg_t {
             fn eq(&self, other: &fpreg_t) -> bool {
-                self.d == other.d
+                unsafe { self.d == other.d }
             }
         }
 
@@ -111,7 +110,7 @@ cfg_if! {
         impl ::fmt::Debug for fpreg_t {
             fn fmt(&self, f: &mut ::fmt::Formatter) -> ::fmt::Result {
                 f.debug_struct("fpreg_t")
-                    .field("d", &self.d)
+                    .field("d", unsafe { &self.d })
                     .finish()
             }
         }
@@ -125,6 +124,155 @@ cfg_if! {
     }
 }
 
+s! {
+    // The PSW (Program Status Word) holds the current execution mask and
+    // instruction address; it is the s390x analog of a flags+pc register
+    // pair. See `arch/s390/include/uapi/asm/ptrace.h`.
+    pub struct psw_t {
+        pub mask: ::c_ulong,
+        pub addr: ::c_ulong,
+    }
+
+    /// The general-purpose register set, as used by `PTRACE_GETREGS` /
+    /// `PTRACE_SETREGS` and embedded in `mcontext_t`.
+    pub struct s390_regs {
+        pub psw: psw_t,
+        pub gprs: [::c_ulong; 16],
+        pub acrs: [::c_uint; 16],
+        pub orig_gpr2: ::c_ulong,
+    }
+
+    /// The floating-point register set, as used by `PTRACE_GETFPREGS` /
+    /// `PTRACE_SETFPREGS`.
+    pub struct s390_fp_regs {
+        pub fpc: ::c_uint,
+        pub fprs: [fpreg_t; 16],
+    }
+
+    /// Per-process debug registers, as used by `PTRACE_PEEKUSR_AREA` /
+    /// `PTRACE_POKEUSR_AREA` to access the per-CPU "per" (program event
+    /// recording) state.
+    pub struct per_struct {
+        pub cr9: ::c_ulong,
+        pub cr10: ::c_ulong,
+        pub cr11: ::c_ulong,
+        pub bender_addr: ::c_ulong,
+        pub starting_addr: ::c_ulong,
+        pub ending_addr: ::c_ulong,
+        pub perc_atmid: ::c_ushort,
+        pub address: ::c_ulong,
+        pub access_id: ::c_uchar,
+    }
+
+    /// The vector-register extension, as used by `PTRACE_GETVXRS_LOW` /
+    /// `PTRACE_GETVXRS_HIGH`.
+    pub struct s390_vx_regs {
+        pub vxrs_low: [::c_ulonglong; 16],
+        pub vxrs_high: [[::c_ulonglong; 2]; 16],
+    }
+}
+
+s! {
+    pub struct stack_t {
+        pub ss_sp: *mut ::c_void,
+        pub ss_flags: ::c_int,
+        pub ss_size: ::size_t,
+    }
+
+    pub struct flock {
+        pub l_type: ::c_short,
+        pub l_whence: ::c_short,
+        pub l_start: ::off_t,
+        pub l_len: ::off_t,
+        pub l_pid: ::pid_t,
+    }
+
+    pub struct sigaction {
+        pub sa_sigaction: ::sighandler_t,
+        pub sa_mask: ::sigset_t,
+        pub sa_flags: ::c_int,
+        pub sa_restorer: ::Option<extern "C" fn()>,
+    }
+}
+
+s_no_extra_traits! {
+    pub struct siginfo_t {
+        pub si_signo: ::c_int,
+        pub si_errno: ::c_int,
+        pub si_code: ::c_int,
+        #[doc(hidden)]
+        #[deprecated(
+            since = "0.2.54",
+            note = "Please leave a comment on https://github.com/rust-lang/libc/pull/1316 if you're using this field"
+        )]
+        pub _pad: [::c_int; 28],
+        _align: [usize; 0],
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "extra_traits")] {
+        impl PartialEq for siginfo_t {
+            fn eq(&self, other: &siginfo_t) -> bool {
+                self.si_signo == other.si_signo
+                    && self.si_errno == other.si_errno
+                    && self.si_code == other.si_code
+            }
+        }
+
+        impl Eq for siginfo_t {}
+
+        impl ::fmt::Debug for siginfo_t {
+            fn fmt(&self, f: &mut ::fmt::Formatter) -> ::fmt::Result {
+                f.debug_struct("siginfo_t")
+                    .field("si_signo", &self.si_signo)
+                    .field("si_errno", &self.si_errno)
+                    .field("si_code", &self.si_code)
+                    .finish()
+            }
+        }
+
+        impl ::hash::Hash for siginfo_t {
+            fn hash<H: ::hash::Hasher>(&self, state: &mut H) {
+                self.si_signo.hash(state);
+                self.si_errno.hash(state);
+                self.si_code.hash(state);
+            }
+        }
+    }
+}
+
+s! {
+    /// The machine context captured in a signal frame. `psw_mask`/`psw_addr`,
+    /// `gregs`, and `aregs` mirror the `psw`, `gprs`, and `acrs` fields of
+    /// [`s390_regs`] (without `orig_gpr2`), with `gregs` expressed in terms
+    /// of the portable `greg_t` so that `sigcontext`-based code that only
+    /// knows about individual registers keeps working.
+    pub struct mcontext_t {
+        pub psw_mask: ::c_ulong,
+        pub psw_addr: ::c_ulong,
+        pub gregs: [greg_t; 16],
+        pub aregs: [::c_uint; 16],
+        pub fpregs: s390_fp_regs,
+    }
+
+    pub struct ucontext_t {
+        pub uc_flags: ::c_ulong,
+        pub uc_link: *mut ucontext_t,
+        pub uc_stack: ::stack_t,
+        pub uc_mcontext: mcontext_t,
+        pub uc_sigmask: ::sigset_t,
+    }
+}
+
+pub const NUM_GPRS: usize = 16;
+pub const NUM_FPRS: usize = 16;
+pub const NUM_ACRS: usize = 16;
+pub const NUM_CRS: usize = 16;
+pub const NUM_VXRS: usize = 32;
+pub const NUM_VXRS_LOW: usize = 16;
+pub const NUM_VXRS_HIGH: usize = 16;
+
 pub const VEOF: usize = 4;
 pub const RTLD_DEEPBIND: ::c_int = 0x8;
 
@@ -723,3 +871,16 @@ pub const SYS_process_mrelease: ::c_long = 448;
 pub const SYS_futex_waitv: ::c_long = 449;
 pub const SYS_set_mempolicy_home_node: ::c_long = 450;
 pub const SYS_mseal: ::c_long = 462;
+pub const SYS_rseq: ::c_long = 417;
+pub const SYS_io_pgetevents: ::c_long = 416;
+pub const SYS_pkey_mprotect: ::c_long = 384;
+pub const SYS_pkey_alloc: ::c_long = 385;
+pub const SYS_pkey_free: ::c_long = 386;
+pub const SYS_kexec_file_load: ::c_long = 381;
+pub const SYS_cachestat: ::c_long = 451;
+pub const SYS_map_shadow_stack: ::c_long = 453;
+pub const SYS_lsm_get_self_attr: ::c_long = 457;
+pub const SYS_lsm_set_self_attr: ::c_long = 458;
+pub const SYS_lsm_list_modules: ::c_long = 459;
+pub const SYS_listmount: ::c_long = 460;
+pub const SYS_statmount: ::c_long = 461;
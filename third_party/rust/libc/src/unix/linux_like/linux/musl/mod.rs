@@ -1,16 +1,19 @@
 pub type pthread_t = *mut ::c_void;
 pub type clock_t = c_long;
-#[cfg_attr(
-    not(feature = "rustc-dep-of-std"),
-    deprecated(
-        since = "0.2.80",
-        note = "This type is changed to 64-bit in musl 1.2.0, \
-                we'll follow that change in the future release. \
-                See #1848 for more info."
-    )
-)]
-pub type time_t = c_long;
-pub type suseconds_t = c_long;
+
+// musl 1.2.0 widened `time_t` (together with `suseconds_t`, the
+// microseconds field of `timeval`) to 64 bits on every target, not just the 64-bit ones where
+// `c_long` already was 64 bits. Follow that so `timeval`/`timespec` match
+// the ABI of a Y2038-ready musl on 32-bit targets too. See #1848.
+cfg_if! {
+    if #[cfg(target_pointer_width = "32")] {
+        pub type time_t = i64;
+        pub type suseconds_t = i64;
+    } else {
+        pub type time_t = c_long;
+        pub type suseconds_t = c_long;
+    }
+}
 pub type ino_t = u64;
 pub type off_t = i64;
 pub type blkcnt_t = i64;
@@ -22,6 +25,8 @@ pub type fsblkcnt_t = ::c_ulonglong;
 pub type fsfilcnt_t = ::c_ulonglong;
 pub type rlim_t = ::c_ulonglong;
 
+pub type regoff_t = ::c_int;
+
 cfg_if! {
     if #[cfg(doc)] {
         // Used in `linux::arch` to define ioctl constants.
@@ -56,6 +61,143 @@ impl siginfo_t {
         }
         (*(self as *const siginfo_t as *const siginfo_si_value)).si_value
     }
+
+    pub unsafe fn si_pid(&self) -> ::pid_t {
+        #[repr(C)]
+        struct siginfo_si_pid {
+            _si_signo: ::c_int,
+            _si_errno: ::c_int,
+            _si_code: ::c_int,
+            si_pid: ::pid_t,
+        }
+        (*(self as *const siginfo_t as *const siginfo_si_pid)).si_pid
+    }
+
+    pub unsafe fn si_uid(&self) -> ::uid_t {
+        #[repr(C)]
+        struct siginfo_si_uid {
+            _si_signo: ::c_int,
+            _si_errno: ::c_int,
+            _si_code: ::c_int,
+            _si_pid: ::pid_t,
+            si_uid: ::uid_t,
+        }
+        (*(self as *const siginfo_t as *const siginfo_si_uid)).si_uid
+    }
+
+    pub unsafe fn si_int(&self) -> ::c_int {
+        #[repr(C)]
+        struct siginfo_si_int {
+            _si_signo: ::c_int,
+            _si_errno: ::c_int,
+            _si_code: ::c_int,
+            _si_pid: ::pid_t,
+            _si_uid: ::uid_t,
+            si_int: ::c_int,
+        }
+        (*(self as *const siginfo_t as *const siginfo_si_int)).si_int
+    }
+
+    pub unsafe fn si_ptr(&self) -> *mut ::c_void {
+        #[repr(C)]
+        struct siginfo_si_ptr {
+            _si_signo: ::c_int,
+            _si_errno: ::c_int,
+            _si_code: ::c_int,
+            _si_pid: ::pid_t,
+            _si_uid: ::uid_t,
+            si_ptr: *mut ::c_void,
+        }
+        (*(self as *const siginfo_t as *const siginfo_si_ptr)).si_ptr
+    }
+
+    pub unsafe fn si_timerid(&self) -> ::c_int {
+        #[repr(C)]
+        struct siginfo_si_timerid {
+            _si_signo: ::c_int,
+            _si_errno: ::c_int,
+            _si_code: ::c_int,
+            si_timerid: ::c_int,
+        }
+        (*(self as *const siginfo_t as *const siginfo_si_timerid)).si_timerid
+    }
+
+    pub unsafe fn si_overrun(&self) -> ::c_int {
+        #[repr(C)]
+        struct siginfo_si_overrun {
+            _si_signo: ::c_int,
+            _si_errno: ::c_int,
+            _si_code: ::c_int,
+            _si_timerid: ::c_int,
+            si_overrun: ::c_int,
+        }
+        (*(self as *const siginfo_t as *const siginfo_si_overrun)).si_overrun
+    }
+
+    pub unsafe fn si_band(&self) -> ::c_long {
+        #[repr(C)]
+        struct siginfo_si_band {
+            _si_signo: ::c_int,
+            _si_errno: ::c_int,
+            _si_code: ::c_int,
+            si_band: ::c_long,
+        }
+        (*(self as *const siginfo_t as *const siginfo_si_band)).si_band
+    }
+
+    pub unsafe fn si_fd(&self) -> ::c_int {
+        #[repr(C)]
+        struct siginfo_si_fd {
+            _si_signo: ::c_int,
+            _si_errno: ::c_int,
+            _si_code: ::c_int,
+            _si_band: ::c_long,
+            si_fd: ::c_int,
+        }
+        (*(self as *const siginfo_t as *const siginfo_si_fd)).si_fd
+    }
+
+    /// The address of the `syscall` instruction that triggered a seccomp-bpf
+    /// `SIGSYS`, as delivered by the kernel's `seccomp_data`-derived
+    /// `__sigsys` siginfo variant.
+    pub unsafe fn si_call_addr(&self) -> *mut ::c_void {
+        #[repr(C)]
+        struct siginfo_si_call_addr {
+            _si_signo: ::c_int,
+            _si_errno: ::c_int,
+            _si_code: ::c_int,
+            si_call_addr: *mut ::c_void,
+        }
+        (*(self as *const siginfo_t as *const siginfo_si_call_addr)).si_call_addr
+    }
+
+    /// The blocked syscall number, for a seccomp-bpf `SIGSYS`.
+    pub unsafe fn si_syscall(&self) -> ::c_int {
+        #[repr(C)]
+        struct siginfo_si_syscall {
+            _si_signo: ::c_int,
+            _si_errno: ::c_int,
+            _si_code: ::c_int,
+            _si_call_addr: *mut ::c_void,
+            si_syscall: ::c_int,
+        }
+        (*(self as *const siginfo_t as *const siginfo_si_syscall)).si_syscall
+    }
+
+    /// The `AUDIT_ARCH_*` value identifying the ABI of the blocked syscall,
+    /// for a seccomp-bpf `SIGSYS`.
+    pub unsafe fn si_arch(&self) -> ::c_uint {
+        #[repr(C)]
+        struct siginfo_si_arch {
+            _si_signo: ::c_int,
+            _si_errno: ::c_int,
+            _si_code: ::c_int,
+            _si_call_addr: *mut ::c_void,
+            _si_syscall: ::c_int,
+            si_arch: ::c_uint,
+        }
+        (*(self as *const siginfo_t as *const siginfo_si_arch)).si_arch
+    }
 }
 
 cfg_if! {
@@ -203,6 +345,11 @@ s! {
         __padding2: ::c_char,
     }
 
+    pub struct regmatch_t {
+        pub rm_so: regoff_t,
+        pub rm_eo: regoff_t,
+    }
+
     pub struct rtentry {
         pub rt_pad1: ::c_ulong,
         pub rt_dst: ::sockaddr,
@@ -270,6 +417,60 @@ s! {
         pub time: ::timeval,
         pub maxerror: ::c_long,
         pub esterror: ::c_long,
+        pub tai: ::c_long,
+        pub __glibc_reserved: [::c_long; 3],
+    }
+
+    // linux/fanotify.h
+
+    pub struct fanotify_event_metadata {
+        pub event_len: ::__u32,
+        pub vers: ::__u8,
+        pub reserved: ::__u8,
+        pub metadata_len: ::__u16,
+        pub mask: ::__u64,
+        pub fd: ::c_int,
+        pub pid: ::c_int,
+    }
+
+    pub struct fanotify_response {
+        pub fd: ::c_int,
+        pub response: ::__u32,
+    }
+
+    // bits/statx.h
+
+    pub struct statx_timestamp {
+        pub tv_sec: i64,
+        pub tv_nsec: u32,
+        __statx_timestamp_pad1: [i32; 1],
+    }
+
+    /// Extended file status, as returned by the `statx` syscall.
+    pub struct statx {
+        pub stx_mask: ::c_uint,
+        pub stx_blksize: ::c_uint,
+        pub stx_attributes: ::c_ulonglong,
+        pub stx_nlink: ::c_uint,
+        pub stx_uid: ::c_uint,
+        pub stx_gid: ::c_uint,
+        pub stx_mode: ::c_ushort,
+        __statx_pad1: [::c_ushort; 1],
+        pub stx_ino: ::c_ulonglong,
+        pub stx_size: ::c_ulonglong,
+        pub stx_blocks: ::c_ulonglong,
+        pub stx_attributes_mask: ::c_ulonglong,
+        pub stx_atime: statx_timestamp,
+        pub stx_btime: statx_timestamp,
+        pub stx_ctime: statx_timestamp,
+        pub stx_mtime: statx_timestamp,
+        pub stx_rdev_major: ::c_uint,
+        pub stx_rdev_minor: ::c_uint,
+        pub stx_dev_major: ::c_uint,
+        pub stx_dev_minor: ::c_uint,
+        pub stx_mnt_id: ::c_ulonglong,
+        __statx_pad2: ::c_ulonglong,
+        __statx_pad3: [::c_ulonglong; 12],
     }
 
     // linux/if_xdp.h
@@ -358,15 +559,15 @@ s! {
         pub tcpi_probes: u8,
         pub tcpi_backoff: u8,
         pub tcpi_options: u8,
-        /*
-         * FIXME(musl): when musl headers are more up to date
         /// This contains the bitfields `tcpi_snd_wscale` and `tcpi_rcv_wscale`.
-        /// Each is 4 bits.
-        pub tcpi_snd_rcv_wscale: u8,
-        /// This contains the bitfields `tcpi_delivery_rate_app_limited` (1 bit) and
-        /// `tcpi_fastopen_client_fail` (2 bits).
-        pub tcpi_delivery_fastopen_bitfields: u8,
-        */
+        /// Each is 4 bits; use the `snd_wscale()`/`rcv_wscale()` accessors
+        /// rather than reading this field directly.
+        tcpi_snd_rcv_wscale: u8,
+        /// This contains the bitfields `tcpi_delivery_rate_app_limited` (1 bit)
+        /// and `tcpi_fastopen_client_fail` (2 bits); use the
+        /// `delivery_rate_app_limited()`/`fastopen_client_fail()` accessors
+        /// rather than reading this field directly.
+        tcpi_delivery_fastopen_bitfields: u8,
         pub tcpi_rto: u32,
         pub tcpi_ato: u32,
         pub tcpi_snd_mss: u32,
@@ -411,9 +612,26 @@ s! {
         pub tcpi_bytes_retrans: u64,
         pub tcpi_dsack_dups: u32,
         pub tcpi_reord_seen: u32,
-        // FIXME(musl): to uncomment once CI musl is updated
-        //pub tcpi_rcv_ooopack: u32,
-        //pub tcpi_snd_wnd: u32,
+        pub tcpi_rcv_ooopack: u32,
+        pub tcpi_snd_wnd: u32,
+    }
+}
+
+impl tcp_info {
+    pub fn snd_wscale(&self) -> u8 {
+        self.tcpi_snd_rcv_wscale & 0xf
+    }
+
+    pub fn rcv_wscale(&self) -> u8 {
+        (self.tcpi_snd_rcv_wscale >> 4) & 0xf
+    }
+
+    pub fn delivery_rate_app_limited(&self) -> bool {
+        self.tcpi_delivery_fastopen_bitfields & 0x1 != 0
+    }
+
+    pub fn fastopen_client_fail(&self) -> u8 {
+        (self.tcpi_delivery_fastopen_bitfields >> 1) & 0x3
     }
 }
 
@@ -772,6 +990,7 @@ pub const EFD_NONBLOCK: ::c_int = ::O_NONBLOCK;
 pub const SFD_NONBLOCK: ::c_int = ::O_NONBLOCK;
 
 pub const PIDFD_NONBLOCK: ::c_uint = O_NONBLOCK as ::c_uint;
+pub const PIDFD_THREAD: ::c_uint = O_EXCL as ::c_uint;
 
 pub const TCSANOW: ::c_int = 0;
 pub const TCSADRAIN: ::c_int = 1;
@@ -803,6 +1022,95 @@ pub const EXTB: ::speed_t = B38400;
 
 pub const REG_OK: ::c_int = 0;
 
+pub const REG_EXTENDED: ::c_int = 1;
+pub const REG_ICASE: ::c_int = 2;
+pub const REG_NEWLINE: ::c_int = 4;
+pub const REG_NOSUB: ::c_int = 8;
+
+pub const REG_NOTBOL: ::c_int = 1;
+pub const REG_NOTEOL: ::c_int = 2;
+pub const REG_STARTEND: ::c_int = 4;
+
+pub const REG_NOMATCH: ::c_int = 1;
+pub const REG_BADPAT: ::c_int = 2;
+pub const REG_ECOLLATE: ::c_int = 3;
+pub const REG_ECTYPE: ::c_int = 4;
+pub const REG_EESCAPE: ::c_int = 5;
+pub const REG_ESUBREG: ::c_int = 6;
+pub const REG_EBRACK: ::c_int = 7;
+pub const REG_EPAREN: ::c_int = 8;
+pub const REG_EBRACE: ::c_int = 9;
+pub const REG_BADBR: ::c_int = 10;
+pub const REG_ERANGE: ::c_int = 11;
+pub const REG_ESPACE: ::c_int = 12;
+pub const REG_BADRPT: ::c_int = 13;
+
+pub const LIO_READ: ::c_int = 0;
+pub const LIO_WRITE: ::c_int = 1;
+pub const LIO_NOP: ::c_int = 2;
+pub const LIO_WAIT: ::c_int = 0;
+pub const LIO_NOWAIT: ::c_int = 1;
+
+pub const AIO_CANCELED: ::c_int = 0;
+pub const AIO_NOTCANCELED: ::c_int = 1;
+pub const AIO_ALLDONE: ::c_int = 2;
+
+// linux/fanotify.h
+pub const FAN_CLOEXEC: ::c_uint = 0x0000_0001;
+pub const FAN_NONBLOCK: ::c_uint = 0x0000_0002;
+pub const FAN_CLASS_NOTIF: ::c_uint = 0x0000_0000;
+pub const FAN_CLASS_CONTENT: ::c_uint = 0x0000_0004;
+pub const FAN_CLASS_PRE_CONTENT: ::c_uint = 0x0000_0008;
+pub const FAN_UNLIMITED_QUEUE: ::c_uint = 0x0000_0010;
+pub const FAN_UNLIMITED_MARKS: ::c_uint = 0x0000_0020;
+pub const FAN_REPORT_TID: ::c_uint = 0x0000_0100;
+pub const FAN_REPORT_FID: ::c_uint = 0x0000_0200;
+
+pub const FAN_ACCESS: ::__u64 = 0x0000_0001;
+pub const FAN_MODIFY: ::__u64 = 0x0000_0002;
+pub const FAN_ATTRIB: ::__u64 = 0x0000_0004;
+pub const FAN_CLOSE_WRITE: ::__u64 = 0x0000_0008;
+pub const FAN_CLOSE_NOWRITE: ::__u64 = 0x0000_0010;
+pub const FAN_OPEN: ::__u64 = 0x0000_0020;
+pub const FAN_MOVED_FROM: ::__u64 = 0x0000_0040;
+pub const FAN_MOVED_TO: ::__u64 = 0x0000_0080;
+pub const FAN_CREATE: ::__u64 = 0x0000_0100;
+pub const FAN_DELETE: ::__u64 = 0x0000_0200;
+pub const FAN_DELETE_SELF: ::__u64 = 0x0000_0400;
+pub const FAN_MOVE_SELF: ::__u64 = 0x0000_0800;
+pub const FAN_OPEN_EXEC: ::__u64 = 0x0000_1000;
+pub const FAN_Q_OVERFLOW: ::__u64 = 0x0000_4000;
+pub const FAN_OPEN_PERM: ::__u64 = 0x0001_0000;
+pub const FAN_ACCESS_PERM: ::__u64 = 0x0002_0000;
+pub const FAN_OPEN_EXEC_PERM: ::__u64 = 0x0004_0000;
+pub const FAN_ONDIR: ::__u64 = 0x4000_0000;
+pub const FAN_CLOSE: ::__u64 = FAN_CLOSE_WRITE | FAN_CLOSE_NOWRITE;
+pub const FAN_MOVE: ::__u64 = FAN_MOVED_FROM | FAN_MOVED_TO;
+
+pub const FAN_MARK_ADD: ::c_uint = 0x0000_0001;
+pub const FAN_MARK_REMOVE: ::c_uint = 0x0000_0002;
+pub const FAN_MARK_DONT_FOLLOW: ::c_uint = 0x0000_0004;
+pub const FAN_MARK_ONLYDIR: ::c_uint = 0x0000_0008;
+pub const FAN_MARK_IGNORED_MASK: ::c_uint = 0x0000_0020;
+pub const FAN_MARK_IGNORED_SURV_MODIFY: ::c_uint = 0x0000_0040;
+pub const FAN_MARK_FLUSH: ::c_uint = 0x0000_0080;
+pub const FAN_MARK_FILESYSTEM: ::c_uint = 0x0000_0100;
+pub const FAN_MARK_INODE: ::c_uint = 0x0000_0000;
+pub const FAN_MARK_MOUNT: ::c_uint = 0x0000_0010;
+
+pub const FANOTIFY_METADATA_VERSION: ::__u8 = 3;
+
+pub const FAN_ALLOW: ::__u32 = 0x01;
+pub const FAN_DENY: ::__u32 = 0x02;
+pub const FAN_AUDIT: ::__u32 = 0x10;
+
+pub const FAN_EVENT_ON_CHILD: ::__u64 = 0x0800_0000;
+pub const FAN_NOFD: ::c_int = -1;
+
+// linux/close_range.h
+pub const CLOSE_RANGE_UNSHARE: ::c_int = 1 << 1;
+pub const CLOSE_RANGE_CLOEXEC: ::c_int = 1 << 2;
+
 pub const PRIO_PROCESS: ::c_int = 0;
 pub const PRIO_PGRP: ::c_int = 1;
 pub const PRIO_USER: ::c_int = 2;
@@ -865,6 +1173,43 @@ pub const TIME_ERROR: ::c_int = 5;
 pub const TIME_BAD: ::c_int = TIME_ERROR;
 pub const MAXTC: ::c_long = 6;
 
+pub const MAXPHASE: ::c_long = 500000000;
+pub const MAXFREQ: ::c_long = 500000;
+pub const MINSEC: ::c_long = 256;
+pub const MAXSEC: ::c_long = 2048;
+
+// bits/statx.h
+pub const STATX_TYPE: ::c_uint = 0x0001;
+pub const STATX_MODE: ::c_uint = 0x0002;
+pub const STATX_NLINK: ::c_uint = 0x0004;
+pub const STATX_UID: ::c_uint = 0x0008;
+pub const STATX_GID: ::c_uint = 0x0010;
+pub const STATX_ATIME: ::c_uint = 0x0020;
+pub const STATX_MTIME: ::c_uint = 0x0040;
+pub const STATX_CTIME: ::c_uint = 0x0080;
+pub const STATX_INO: ::c_uint = 0x0100;
+pub const STATX_SIZE: ::c_uint = 0x0200;
+pub const STATX_BLOCKS: ::c_uint = 0x0400;
+pub const STATX_BASIC_STATS: ::c_uint = 0x07ff;
+pub const STATX_BTIME: ::c_uint = 0x0800;
+pub const STATX_MNT_ID: ::c_uint = 0x1000;
+pub const STATX_ALL: ::c_uint = 0x0fff;
+
+pub const STATX_ATTR_COMPRESSED: ::c_ulonglong = 0x0004;
+pub const STATX_ATTR_IMMUTABLE: ::c_ulonglong = 0x0010;
+pub const STATX_ATTR_APPEND: ::c_ulonglong = 0x0020;
+pub const STATX_ATTR_NODUMP: ::c_ulonglong = 0x0040;
+pub const STATX_ATTR_ENCRYPTED: ::c_ulonglong = 0x0800;
+pub const STATX_ATTR_AUTOMOUNT: ::c_ulonglong = 0x1000;
+pub const STATX_ATTR_MOUNT_ROOT: ::c_ulonglong = 0x2000;
+pub const STATX_ATTR_VERITY: ::c_ulonglong = 0x100000;
+pub const STATX_ATTR_DAX: ::c_ulonglong = 0x200000;
+
+pub const AT_STATX_SYNC_TYPE: ::c_int = 0x6000;
+pub const AT_STATX_SYNC_AS_STAT: ::c_int = 0x0000;
+pub const AT_STATX_FORCE_SYNC: ::c_int = 0x2000;
+pub const AT_STATX_DONT_SYNC: ::c_int = 0x4000;
+
 pub const SOL_XDP: ::c_int = 283;
 
 // linux/if_xdp.h
@@ -936,6 +1281,7 @@ extern "C" {
     pub fn ptrace(request: ::c_int, ...) -> ::c_long;
     pub fn getpriority(which: ::c_int, who: ::id_t) -> ::c_int;
     pub fn setpriority(which: ::c_int, who: ::id_t, prio: ::c_int) -> ::c_int;
+    pub fn fanotify_init(flags: ::c_uint, event_f_flags: ::c_uint) -> ::c_int;
     // Musl targets need the `mask` argument of `fanotify_mark` be specified
     // `::c_ulonglong` instead of `u64` or there will be a type mismatch between
     // `long long unsigned int` and the expected `uint64_t`.
@@ -969,6 +1315,17 @@ extern "C" {
 
     pub fn adjtimex(buf: *mut ::timex) -> ::c_int;
     pub fn clock_adjtime(clk_id: ::clockid_t, buf: *mut ::timex) -> ::c_int;
+    pub fn ntp_adjtime(buf: *mut ::timex) -> ::c_int;
+    pub fn ntp_gettime(buf: *mut ::ntptimeval) -> ::c_int;
+    pub fn ntp_gettimex(buf: *mut ::ntptimeval) -> ::c_int;
+
+    pub fn statx(
+        dirfd: ::c_int,
+        pathname: *const ::c_char,
+        flags: ::c_int,
+        mask: ::c_uint,
+        statxbuf: *mut statx,
+    ) -> ::c_int;
 
     pub fn ctermid(s: *mut ::c_char) -> *mut ::c_char;
 
@@ -984,6 +1341,37 @@ extern "C" {
     pub fn dirname(path: *mut ::c_char) -> *mut ::c_char;
     pub fn basename(path: *mut ::c_char) -> *mut ::c_char;
 
+    pub fn regcomp(preg: *mut regex_t, pattern: *const ::c_char, cflags: ::c_int) -> ::c_int;
+    pub fn regexec(
+        preg: *const regex_t,
+        input: *const ::c_char,
+        nmatch: ::size_t,
+        pmatch: *mut regmatch_t,
+        eflags: ::c_int,
+    ) -> ::c_int;
+    pub fn regerror(
+        errcode: ::c_int,
+        preg: *const regex_t,
+        errbuf: *mut ::c_char,
+        errbuf_size: ::size_t,
+    ) -> ::size_t;
+    pub fn regfree(preg: *mut regex_t);
+
+    pub fn aio_error(aiocbp: *const aiocb) -> ::c_int;
+    pub fn aio_return(aiocbp: *mut aiocb) -> ::ssize_t;
+    pub fn aio_suspend(
+        list: *const *const aiocb,
+        nent: ::c_int,
+        timeout: *const ::timespec,
+    ) -> ::c_int;
+    pub fn aio_cancel(fd: ::c_int, aiocbp: *mut aiocb) -> ::c_int;
+    pub fn lio_listio(
+        mode: ::c_int,
+        list: *const *mut aiocb,
+        nent: ::c_int,
+        sevp: *mut ::sigevent,
+    ) -> ::c_int;
+
     // Added in `musl` 1.1.24
     pub fn posix_spawn_file_actions_addchdir_np(
         actions: *mut ::posix_spawn_file_actions_t,
@@ -996,6 +1384,43 @@ extern "C" {
     ) -> ::c_int;
 }
 
+// musl doesn't provide wrappers for these syscalls, so shim them directly;
+// see PIDFD_NONBLOCK/PIDFD_THREAD above for the flags they take.
+pub unsafe fn pidfd_open(pid: ::pid_t, flags: ::c_uint) -> ::c_int {
+    ::syscall(SYS_pidfd_open, pid, flags) as ::c_int
+}
+
+pub unsafe fn pidfd_getfd(pidfd: ::c_int, targetfd: ::c_int, flags: ::c_uint) -> ::c_int {
+    ::syscall(SYS_pidfd_getfd, pidfd, targetfd, flags) as ::c_int
+}
+
+pub unsafe fn pidfd_send_signal(
+    pidfd: ::c_int,
+    sig: ::c_int,
+    info: *mut ::siginfo_t,
+    flags: ::c_uint,
+) -> ::c_int {
+    ::syscall(SYS_pidfd_send_signal, pidfd, sig, info, flags) as ::c_int
+}
+
+pub unsafe fn memfd_secret(flags: ::c_uint) -> ::c_int {
+    ::syscall(SYS_memfd_secret, flags) as ::c_int
+}
+
+pub unsafe fn close_range(first: ::c_uint, last: ::c_uint, flags: ::c_int) -> ::c_int {
+    ::syscall(SYS_close_range, first, last, flags) as ::c_int
+}
+
+pub unsafe fn process_madvise(
+    pidfd: ::c_int,
+    iovec: *const ::iovec,
+    vlen: ::size_t,
+    advice: ::c_int,
+    flags: ::c_uint,
+) -> ::ssize_t {
+    ::syscall(SYS_process_madvise, pidfd, iovec, vlen, advice, flags) as ::ssize_t
+}
+
 // Alias <foo> to <foo>64 to mimic glibc's LFS64 support
 mod lfs64;
 pub use self::lfs64::*;
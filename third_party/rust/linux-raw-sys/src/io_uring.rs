@@ -0,0 +1,129 @@
+/* automatically generated by rust-bindgen 0.66.1 */
+
+pub type __u64 = crate::ctypes::c_ulong;
+pub type __u32 = crate::ctypes::c_uint;
+pub type __u16 = crate::ctypes::c_ushort;
+pub type __u8 = crate::ctypes::c_uchar;
+pub type __s32 = crate::ctypes::c_int;
+
+/// A submission queue entry, written by user space and consumed by the
+/// kernel from the shared SQ ring.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct io_uring_sqe {
+pub opcode: __u8,
+pub flags: __u8,
+pub ioprio: __u16,
+pub fd: __s32,
+pub off: __u64,
+pub addr: __u64,
+pub len: __u32,
+pub rw_flags_or_other: __u32,
+pub user_data: __u64,
+pub buf_index_or_group: __u16,
+pub personality: __u16,
+pub splice_fd_in_or_other: __s32,
+pub addr3: __u64,
+pub __pad2: [__u64; 1usize],
+}
+
+/// A completion queue entry, written by the kernel and consumed by user
+/// space from the shared CQ ring.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct io_uring_cqe {
+pub user_data: __u64,
+pub res: __s32,
+pub flags: __u32,
+pub big_cqe: [__u64; 0usize],
+}
+
+/// The offsets of the ring headers within the mmap'd SQ/CQ regions, as
+/// returned by `io_uring_setup` in `io_uring_params`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct io_sqring_offsets {
+pub head: __u32,
+pub tail: __u32,
+pub ring_mask: __u32,
+pub ring_entries: __u32,
+pub flags: __u32,
+pub dropped: __u32,
+pub array: __u32,
+pub resv1: __u32,
+pub resv2: __u64,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct io_cqring_offsets {
+pub head: __u32,
+pub tail: __u32,
+pub ring_mask: __u32,
+pub ring_entries: __u32,
+pub overflow: __u32,
+pub cqes: __u32,
+pub flags: __u32,
+pub resv1: __u32,
+pub resv2: __u64,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct io_uring_params {
+pub sq_entries: __u32,
+pub cq_entries: __u32,
+pub flags: __u32,
+pub sq_thread_cpu: __u32,
+pub sq_thread_idle: __u32,
+pub features: __u32,
+pub wq_fd: __u32,
+pub resv: [__u32; 3usize],
+pub sq_off: io_sqring_offsets,
+pub cq_off: io_cqring_offsets,
+}
+
+pub const IORING_SETUP_IOPOLL: __u32 = 1 << 0;
+pub const IORING_SETUP_SQPOLL: __u32 = 1 << 1;
+pub const IORING_SETUP_SQ_AFF: __u32 = 1 << 2;
+pub const IORING_SETUP_CQSIZE: __u32 = 1 << 3;
+pub const IORING_SETUP_CLAMP: __u32 = 1 << 4;
+pub const IORING_SETUP_ATTACH_WQ: __u32 = 1 << 5;
+pub const IORING_SETUP_R_DISABLED: __u32 = 1 << 6;
+pub const IORING_SETUP_SUBMIT_ALL: __u32 = 1 << 7;
+pub const IORING_SETUP_COOP_TASKRUN: __u32 = 1 << 8;
+pub const IORING_SETUP_TASKRUN_FLAG: __u32 = 1 << 9;
+pub const IORING_SETUP_SQE128: __u32 = 1 << 10;
+pub const IORING_SETUP_CQE32: __u32 = 1 << 11;
+pub const IORING_SETUP_SINGLE_ISSUER: __u32 = 1 << 12;
+pub const IORING_SETUP_DEFER_TASKRUN: __u32 = 1 << 13;
+
+pub const IORING_FEAT_SINGLE_MMAP: __u32 = 1 << 0;
+pub const IORING_FEAT_NODROP: __u32 = 1 << 1;
+pub const IORING_FEAT_SUBMIT_STABLE: __u32 = 1 << 2;
+pub const IORING_FEAT_RW_CUR_POS: __u32 = 1 << 3;
+pub const IORING_FEAT_CUR_PERSONALITY: __u32 = 1 << 4;
+pub const IORING_FEAT_FAST_POLL: __u32 = 1 << 5;
+pub const IORING_FEAT_POLL_32BITS: __u32 = 1 << 6;
+pub const IORING_FEAT_SQPOLL_NONFIXED: __u32 = 1 << 7;
+pub const IORING_FEAT_EXT_ARG: __u32 = 1 << 8;
+pub const IORING_FEAT_NATIVE_WORKERS: __u32 = 1 << 9;
+pub const IORING_FEAT_RSRC_TAGS: __u32 = 1 << 10;
+pub const IORING_FEAT_CQE_SKIP: __u32 = 1 << 11;
+pub const IORING_FEAT_LINKED_FILE: __u32 = 1 << 12;
+
+pub const IORING_OFF_SQ_RING: __u64 = 0;
+pub const IORING_OFF_CQ_RING: __u64 = 0x8000000;
+pub const IORING_OFF_SQES: __u64 = 0x10000000;
+
+pub const IORING_SQ_NEED_WAKEUP: __u32 = 1 << 0;
+pub const IORING_SQ_CQ_OVERFLOW: __u32 = 1 << 1;
+pub const IORING_SQ_TASKRUN: __u32 = 1 << 2;
+
+pub const IORING_CQ_EVENTFD_DISABLED: __u32 = 1 << 0;
+
+pub const IORING_ENTER_GETEVENTS: __u32 = 1 << 0;
+pub const IORING_ENTER_SQ_WAKEUP: __u32 = 1 << 1;
+pub const IORING_ENTER_SQ_WAIT: __u32 = 1 << 2;
+pub const IORING_ENTER_EXT_ARG: __u32 = 1 << 3;
+pub const IORING_ENTER_REGISTERED_RING: __u32 = 1 << 4;
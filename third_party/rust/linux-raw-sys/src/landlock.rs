@@ -0,0 +1,55 @@
+/* automatically generated by rust-bindgen 0.66.1 */
+
+pub type __u64 = crate::ctypes::c_ulong;
+pub type __u32 = crate::ctypes::c_uint;
+pub type __u16 = crate::ctypes::c_ushort;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct landlock_ruleset_attr {
+pub handled_access_fs: __u64,
+pub handled_access_net: __u64,
+pub scoped: __u64,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct landlock_path_beneath_attr {
+pub allowed_access: __u64,
+pub parent_fd: crate::ctypes::c_int,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct landlock_net_port_attr {
+pub allowed_access: __u64,
+pub port: __u64,
+}
+
+pub const LANDLOCK_CREATE_RULESET_VERSION: __u32 = 1;
+
+pub const LANDLOCK_RULE_PATH_BENEATH: __u32 = 1;
+pub const LANDLOCK_RULE_NET_PORT: __u32 = 2;
+
+pub const LANDLOCK_ACCESS_FS_EXECUTE: __u64 = 1 << 0;
+pub const LANDLOCK_ACCESS_FS_WRITE_FILE: __u64 = 1 << 1;
+pub const LANDLOCK_ACCESS_FS_READ_FILE: __u64 = 1 << 2;
+pub const LANDLOCK_ACCESS_FS_READ_DIR: __u64 = 1 << 3;
+pub const LANDLOCK_ACCESS_FS_REMOVE_DIR: __u64 = 1 << 4;
+pub const LANDLOCK_ACCESS_FS_REMOVE_FILE: __u64 = 1 << 5;
+pub const LANDLOCK_ACCESS_FS_MAKE_CHAR: __u64 = 1 << 6;
+pub const LANDLOCK_ACCESS_FS_MAKE_DIR: __u64 = 1 << 7;
+pub const LANDLOCK_ACCESS_FS_MAKE_REG: __u64 = 1 << 8;
+pub const LANDLOCK_ACCESS_FS_MAKE_SOCK: __u64 = 1 << 9;
+pub const LANDLOCK_ACCESS_FS_MAKE_FIFO: __u64 = 1 << 10;
+pub const LANDLOCK_ACCESS_FS_MAKE_BLOCK: __u64 = 1 << 11;
+pub const LANDLOCK_ACCESS_FS_MAKE_SYM: __u64 = 1 << 12;
+pub const LANDLOCK_ACCESS_FS_REFER: __u64 = 1 << 13;
+pub const LANDLOCK_ACCESS_FS_TRUNCATE: __u64 = 1 << 14;
+pub const LANDLOCK_ACCESS_FS_IOCTL_DEV: __u64 = 1 << 15;
+
+pub const LANDLOCK_ACCESS_NET_BIND_TCP: __u64 = 1 << 0;
+pub const LANDLOCK_ACCESS_NET_CONNECT_TCP: __u64 = 1 << 1;
+
+pub const LANDLOCK_SCOPE_ABSTRACT_UNIX_SOCKET: __u64 = 1 << 0;
+pub const LANDLOCK_SCOPE_SIGNAL: __u64 = 1 << 1;
@@ -0,0 +1,9 @@
+/* automatically generated by rust-bindgen 0.66.1 */
+
+pub type __u32 = crate::ctypes::c_uint;
+
+// Flags for the `memfd_secret` syscall, which creates an anonymous memory
+// region that is excluded from the direct map and never paged out, so its
+// contents are never visible to the kernel or other processes even via a
+// crash dump.
+pub const FD_SECRET_EXCLUSIVE: __u32 = 1 << 0;
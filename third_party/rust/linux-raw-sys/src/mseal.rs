@@ -0,0 +1,8 @@
+/* automatically generated by rust-bindgen 0.66.1 */
+
+pub type __u64 = crate::ctypes::c_ulong;
+
+// Flags for the `mseal` syscall. There are currently none defined beyond 0;
+// the kernel requires callers to pass 0 and rejects anything else, reserving
+// the bits for future use.
+pub const MSEAL_RESERVED: __u64 = 0;
@@ -0,0 +1,95 @@
+/* automatically generated by rust-bindgen 0.66.1 */
+
+pub type __u64 = crate::ctypes::c_ulong;
+pub type __u32 = crate::ctypes::c_uint;
+pub type __u16 = crate::ctypes::c_ushort;
+pub type __s32 = crate::ctypes::c_int;
+
+pub const SECCOMP_SET_MODE_STRICT: __u32 = 0;
+pub const SECCOMP_SET_MODE_FILTER: __u32 = 1;
+
+pub const SECCOMP_FILTER_FLAG_TSYNC: __u32 = 1 << 0;
+pub const SECCOMP_FILTER_FLAG_LOG: __u32 = 1 << 1;
+pub const SECCOMP_FILTER_FLAG_SPEC_ALLOW: __u32 = 1 << 2;
+pub const SECCOMP_FILTER_FLAG_NEW_LISTENER: __u32 = 1 << 3;
+pub const SECCOMP_FILTER_FLAG_TSYNC_ESRCH: __u32 = 1 << 4;
+pub const SECCOMP_FILTER_FLAG_WAIT_KILLABLE_RECV: __u32 = 1 << 5;
+
+pub const SECCOMP_RET_KILL_PROCESS: __u32 = 0x80000000;
+pub const SECCOMP_RET_KILL_THREAD: __u32 = 0x00000000;
+pub const SECCOMP_RET_KILL: __u32 = SECCOMP_RET_KILL_THREAD;
+pub const SECCOMP_RET_TRAP: __u32 = 0x00030000;
+pub const SECCOMP_RET_ERRNO: __u32 = 0x00050000;
+pub const SECCOMP_RET_USER_NOTIF: __u32 = 0x7fc00000;
+pub const SECCOMP_RET_TRACE: __u32 = 0x7ff00000;
+pub const SECCOMP_RET_LOG: __u32 = 0x7ffc0000;
+pub const SECCOMP_RET_ALLOW: __u32 = 0x7fff0000;
+
+pub const SECCOMP_RET_ACTION_FULL: __u32 = 0xffff0000;
+pub const SECCOMP_RET_ACTION: __u32 = 0x7fff0000;
+pub const SECCOMP_RET_DATA: __u32 = 0x0000ffff;
+
+pub const SECCOMP_USER_NOTIF_FLAG_CONTINUE: __u32 = 1 << 0;
+
+pub const SECCOMP_ADDFD_FLAG_SETFD: __u32 = 1 << 0;
+pub const SECCOMP_ADDFD_FLAG_SEND: __u32 = 1 << 1;
+
+pub const SECCOMP_IOC_MAGIC: u8 = b'!';
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct seccomp_data {
+pub nr: __s32,
+pub arch: __u32,
+pub instruction_pointer: __u64,
+pub args: [__u64; 6usize],
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct seccomp_notif_sizes {
+pub seccomp_notif: __u16,
+pub seccomp_notif_resp: __u16,
+pub seccomp_data: __u16,
+}
+
+/// A pending seccomp user-space notification, read via
+/// `ioctl(notify_fd, SECCOMP_IOCTL_NOTIF_RECV, &notif)`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct seccomp_notif {
+pub id: __u64,
+pub pid: __u32,
+pub flags: __u32,
+pub data: seccomp_data,
+}
+
+/// The tracer's response to a [`seccomp_notif`], written via
+/// `ioctl(notify_fd, SECCOMP_IOCTL_NOTIF_SEND, &resp)`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct seccomp_notif_resp {
+pub id: __u64,
+pub val: crate::ctypes::c_long,
+pub error: __s32,
+pub flags: __u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct seccomp_notif_addfd {
+pub id: __u64,
+pub flags: __u32,
+pub srcfd: __u32,
+pub newfd: __u32,
+pub newfd_flags: __u32,
+}
+
+// `ioctl` request numbers for the seccomp user-notification fd returned by
+// `SECCOMP_RET_USER_NOTIF`, pre-computed from the kernel's `_IOW`/`_IOWR`
+// macros since this crate doesn't depend on a C compiler.
+pub const SECCOMP_IOCTL_NOTIF_RECV: u32 = 0xc0502100;
+pub const SECCOMP_IOCTL_NOTIF_SEND: u32 = 0xc0182101;
+pub const SECCOMP_IOCTL_NOTIF_ID_VALID: u32 = 0x40082102;
+pub const SECCOMP_IOCTL_NOTIF_SET_FLAGS: u32 = 0x40082104;
+pub const SECCOMP_IOCTL_NOTIF_ADDFD: u32 = 0x40182103;
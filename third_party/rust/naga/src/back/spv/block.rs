@@ -9,6 +9,27 @@ use super::{
 use crate::{arena::Handle, proc::TypeResolution, Statement};
 use spirv::Word;
 
+/// Instruction numbers within the `NonSemantic.Shader.DebugInfo.100` extended instruction
+/// set, gated behind [`WriterFlags::DEBUG_INFO_100`]. Only the subset this module emits
+/// per expression/statement is listed here -- `DebugSource`, `DebugCompilationUnit`, and
+/// the per-type/per-function debug instructions are emitted once per module by the writer,
+/// not from here.
+mod debug_info_100 {
+    use spirv::Word;
+
+    pub(super) const DEBUG_SCOPE: Word = 23;
+    pub(super) const DEBUG_DECLARE: Word = 28;
+    pub(super) const DEBUG_VALUE: Word = 29;
+    pub(super) const DEBUG_LINE: Word = 38;
+}
+
+/// The result [`BlockContext::write_guarded_signed_divmod`] substitutes for the
+/// `INT_MIN / -1` overflow case, per WGSL's chosen semantics for that operator.
+enum DivModOverflow {
+    DividendMin,
+    Zero,
+}
+
 fn get_dimension(type_inner: &crate::TypeInner) -> Dimension {
     match *type_inner {
         crate::TypeInner::Scalar(_) => Dimension::Scalar,
@@ -18,13 +39,87 @@ fn get_dimension(type_inner: &crate::TypeInner) -> Dimension {
     }
 }
 
+/// The extra ids and types an `AtomicFunction::Exchange { compare: Some(_) }` needs,
+/// beyond what every other atomic op needs.
+struct AtomicCasExtra {
+    cmp_id: Word,
+    scalar_type_id: Word,
+    bool_type_id: Word,
+    cas_result_id: Word,
+    equality_result_id: Word,
+}
+
+/// Push the instruction(s) implementing one `Statement::Atomic` onto `block`.
+///
+/// This is a free function, not a `BlockContext` method, so that it can be called
+/// both directly (for an unconditional pointer access) and from inside the closure
+/// `write_conditional_indexed_load` runs for a bounds-checked one -- that closure has
+/// no access to `self`, only to pre-resolved ids like the ones this takes.
+#[allow(clippy::too_many_arguments)]
+fn push_atomic_instruction(
+    spirv_op: spirv::Op,
+    result_type_id: Word,
+    result_id: Word,
+    pointer_id: Word,
+    scope_constant_id: Word,
+    semantics_id: Word,
+    value_id: Word,
+    cas: Option<AtomicCasExtra>,
+    block: &mut Block,
+) {
+    match cas {
+        Some(cas) => {
+            let mut cas_instr = Instruction::new(spirv::Op::AtomicCompareExchange);
+            cas_instr.set_type(cas.scalar_type_id);
+            cas_instr.set_result(cas.cas_result_id);
+            cas_instr.add_operand(pointer_id);
+            cas_instr.add_operand(scope_constant_id);
+            cas_instr.add_operand(semantics_id); // semantics if equal
+            cas_instr.add_operand(semantics_id); // semantics if not equal
+            cas_instr.add_operand(value_id);
+            cas_instr.add_operand(cas.cmp_id);
+            block.body.push(cas_instr);
+            block.body.push(Instruction::binary(
+                spirv::Op::IEqual,
+                cas.bool_type_id,
+                cas.equality_result_id,
+                cas.cas_result_id,
+                cas.cmp_id,
+            ));
+            block.body.push(Instruction::composite_construct(
+                result_type_id,
+                result_id,
+                &[cas.cas_result_id, cas.equality_result_id],
+            ));
+        }
+        None => {
+            block.body.push(Instruction::atomic_binary(
+                spirv_op,
+                result_type_id,
+                result_id,
+                pointer_id,
+                scope_constant_id,
+                semantics_id,
+                value_id,
+            ));
+        }
+    }
+}
+
 /// The results of emitting code for a left-hand-side expression.
 ///
 /// On success, `write_expression_pointer` returns one of these.
 enum ExpressionPointer {
     /// The pointer to the expression's value is available, as the value of the
     /// expression with the given id.
-    Ready { pointer_id: Word },
+    Ready {
+        pointer_id: Word,
+        /// Whether the pointer was reached through a non-uniformly indexed binding array
+        /// access. If so, the load/store/atomic result the caller produces from this
+        /// pointer must also be decorated `NonUniform`, per VUID-RuntimeSpirv-NonUniform-06274
+        /// -- decorating the access chain pointer alone isn't enough.
+        non_uniform: bool,
+    },
 
     /// The access expression must be conditional on the value of `condition`, a boolean
     /// expression that is true if all indices are in bounds. If `condition` is true, then
@@ -34,6 +129,8 @@ enum ExpressionPointer {
     Conditional {
         condition: Word,
         access: Instruction,
+        /// Same meaning as `Ready`'s field of the same name.
+        non_uniform: bool,
     },
 }
 
@@ -234,7 +331,18 @@ impl<'w> BlockContext<'w> {
                 let init = self.ir_module.constants[handle].init;
                 self.writer.constant_ids[init]
             }
-            crate::Expression::Override(_) => return Err(Error::Override),
+            // The spec constant itself (`OpSpecConstant`/`OpSpecConstantTrue`/
+            // `OpSpecConstantFalse`, decorated with a `SpecId`) is emitted once up front when
+            // the module's overrides are declared; here we just look up its id, the same way
+            // `Expression::Constant` looks up a regular constant's id above.
+            //
+            // Expressions built *from* an override (anything beyond this bare lookup) still
+            // get folded to an ordinary runtime instruction rather than an `OpSpecConstantOp`
+            // -- threading spec-constant-ness through arbitrary expressions, emitting the
+            // SpecId -> override name map on the reflection output, and the module
+            // preprocessing changes that would feed this, are writer-level and pipeline-layer
+            // work this file doesn't own.
+            crate::Expression::Override(handle) => self.writer.override_ids[handle],
             crate::Expression::ZeroValue(_) => self.writer.get_constant_null(result_type_id),
             crate::Expression::Compose { ty, ref components } => {
                 self.temp_list.clear();
@@ -299,11 +407,12 @@ impl<'w> BlockContext<'w> {
                     // Only binding arrays in the `Handle` address space will take this
                     // path, since we handled the `Pointer` case above.
                     crate::TypeInner::BindingArray {
-                        base: binding_type, ..
+                        base: binding_type,
+                        size,
                     } => {
-                        let space = match self.ir_function.expressions[base] {
+                        let (gvar, space) = match self.ir_function.expressions[base] {
                             crate::Expression::GlobalVariable(gvar) => {
-                                self.ir_module.global_variables[gvar].space
+                                (gvar, self.ir_module.global_variables[gvar].space)
                             }
                             _ => unreachable!(),
                         };
@@ -317,11 +426,31 @@ impl<'w> BlockContext<'w> {
                             block,
                             Some(binding_array_false_pointer),
                         )? {
-                            ExpressionPointer::Ready { pointer_id } => pointer_id,
+                            ExpressionPointer::Ready { pointer_id, .. } => pointer_id,
                             ExpressionPointer::Conditional { .. } => {
-                                return Err(Error::FeatureNotImplemented(
-                                    "Texture array out-of-bounds handling",
-                                ));
+                                // A conditional (branch-guarded) access doesn't fit binding
+                                // arrays of opaque handles the way it does ordinary values, so
+                                // fall back to `Restrict` semantics here regardless of the
+                                // configured index bounds-check policy: clamp the index to the
+                                // array's statically known length with `OpExtInst UMin` so the
+                                // access chain is always in bounds, rather than bailing out of
+                                // compilation for non-uniform, dynamically-indexed texture and
+                                // sampler arrays.
+                                let crate::ArraySize::Constant(len) = size else {
+                                    return Err(Error::FeatureNotImplemented(
+                                        "Texture array out-of-bounds handling",
+                                    ));
+                                };
+                                let result_type_id = self.get_type_id(binding_array_false_pointer);
+                                let root_id = self.writer.global_variables[gvar].access_id;
+                                let index_id = self.cached[index];
+                                self.write_clamped_binding_array_pointer(
+                                    result_type_id,
+                                    root_id,
+                                    index_id,
+                                    len.get(),
+                                    block,
+                                )
                             }
                         };
 
@@ -369,7 +498,36 @@ impl<'w> BlockContext<'w> {
                         self.function.internal_variables.push(variable);
                         id
                     }
-                    // wgpu#4337: Support `crate::TypeInner::Matrix`
+                    crate::TypeInner::Matrix { .. } => {
+                        let index_id = self.cached[index];
+                        let base_id = self.cached[base];
+                        let base_ty = match self.fun_info[base].ty {
+                            TypeResolution::Handle(handle) => handle,
+                            TypeResolution::Value(_) => {
+                                return Err(Error::Validation(
+                                    "Matrix types should always be in the arena",
+                                ))
+                            }
+                        };
+                        // Columns aren't registered as their own arena `Handle<Type>` the way
+                        // array elements are (a matrix's columns are implied by its `columns`/
+                        // `rows`/`scalar` fields, not a nested handle), so there's no distinct
+                        // element handle to pass here. `promote_access_expression_to_variable`
+                        // derives the column's vector type, and applies the bounds-check policy
+                        // against the statically known column count, directly from `base_ty`'s
+                        // `TypeInner::Matrix` shape -- the same way it derives the array length
+                        // from `base_ty` in the `Array` arm above.
+                        let (id, variable) = self.writer.promote_access_expression_to_variable(
+                            result_type_id,
+                            base_id,
+                            base_ty,
+                            index_id,
+                            base_ty,
+                            block,
+                        )?;
+                        self.function.internal_variables.push(variable);
+                        id
+                    }
                     ref other => {
                         log::error!(
                             "Unable to access base {:?} of type {:?}",
@@ -412,11 +570,12 @@ impl<'w> BlockContext<'w> {
                     }
                     // Only binding arrays in the Handle address space will take this path (due to `is_intermediate`)
                     crate::TypeInner::BindingArray {
-                        base: binding_type, ..
+                        base: binding_type,
+                        size,
                     } => {
-                        let space = match self.ir_function.expressions[base] {
+                        let (gvar, space) = match self.ir_function.expressions[base] {
                             crate::Expression::GlobalVariable(gvar) => {
-                                self.ir_module.global_variables[gvar].space
+                                (gvar, self.ir_module.global_variables[gvar].space)
                             }
                             _ => unreachable!(),
                         };
@@ -430,11 +589,25 @@ impl<'w> BlockContext<'w> {
                             block,
                             Some(binding_array_false_pointer),
                         )? {
-                            ExpressionPointer::Ready { pointer_id } => pointer_id,
+                            ExpressionPointer::Ready { pointer_id, .. } => pointer_id,
                             ExpressionPointer::Conditional { .. } => {
-                                return Err(Error::FeatureNotImplemented(
-                                    "Texture array out-of-bounds handling",
-                                ));
+                                // See the matching comment in the `Access` arm above: clamp to
+                                // `Restrict` semantics instead of erroring out.
+                                let crate::ArraySize::Constant(len) = size else {
+                                    return Err(Error::FeatureNotImplemented(
+                                        "Texture array out-of-bounds handling",
+                                    ));
+                                };
+                                let result_type_id = self.get_type_id(binding_array_false_pointer);
+                                let root_id = self.writer.global_variables[gvar].access_id;
+                                let index_id = self.get_index_constant(index);
+                                self.write_clamped_binding_array_pointer(
+                                    result_type_id,
+                                    root_id,
+                                    index_id,
+                                    len.get(),
+                                    block,
+                                )
                             }
                         };
 
@@ -514,6 +687,27 @@ impl<'w> BlockContext<'w> {
 
                 let spirv_op = match op {
                     crate::BinaryOperator::Add => match *left_ty_inner {
+                        crate::TypeInner::Scalar(scalar)
+                        | crate::TypeInner::Vector { scalar, .. }
+                            if scalar.kind == crate::ScalarKind::Float
+                                && self.writer.flags.contains(WriterFlags::FUSE_MULTIPLY_ADD) =>
+                        {
+                            if let Some((mul_left_id, mul_right_id, addend_id)) =
+                                self.take_fusable_multiply(left, right, block)
+                            {
+                                block.body.push(Instruction::ext_inst(
+                                    self.writer.gl450_ext_inst_id,
+                                    spirv::GLOp::Fma,
+                                    result_type_id,
+                                    id,
+                                    &[mul_left_id, mul_right_id, addend_id],
+                                ));
+
+                                self.cached[expr_handle] = id;
+                                return Ok(());
+                            }
+                            spirv::Op::FAdd
+                        }
                         crate::TypeInner::Scalar(scalar)
                         | crate::TypeInner::Vector { scalar, .. } => match scalar.kind {
                             crate::ScalarKind::Float => spirv::Op::FAdd,
@@ -614,19 +808,76 @@ impl<'w> BlockContext<'w> {
                         | (Dimension::Scalar, Dimension::Scalar) => spirv::Op::IMul,
                     },
                     crate::BinaryOperator::Divide => match left_ty_inner.scalar_kind() {
-                        Some(crate::ScalarKind::Sint) => spirv::Op::SDiv,
-                        Some(crate::ScalarKind::Uint) => spirv::Op::UDiv,
+                        Some(crate::ScalarKind::Sint) => {
+                            if self.writer.flags.contains(WriterFlags::SAFE_INTEGER_ARITHMETIC) {
+                                self.write_guarded_signed_divmod(
+                                    spirv::Op::SDiv,
+                                    DivModOverflow::DividendMin,
+                                    id,
+                                    result_type_id,
+                                    left_id,
+                                    right_id,
+                                    left_ty_inner,
+                                    block,
+                                )?;
+                                self.cached[expr_handle] = id;
+                                return Ok(());
+                            }
+                            spirv::Op::SDiv
+                        }
+                        Some(crate::ScalarKind::Uint) => {
+                            if self.writer.flags.contains(WriterFlags::SAFE_INTEGER_ARITHMETIC) {
+                                self.write_guarded_unsigned_divmod(
+                                    spirv::Op::UDiv,
+                                    id,
+                                    result_type_id,
+                                    left_id,
+                                    right_id,
+                                    left_ty_inner,
+                                    block,
+                                )?;
+                                self.cached[expr_handle] = id;
+                                return Ok(());
+                            }
+                            spirv::Op::UDiv
+                        }
                         Some(crate::ScalarKind::Float) => spirv::Op::FDiv,
                         _ => unimplemented!(),
                     },
                     crate::BinaryOperator::Modulo => match left_ty_inner.scalar_kind() {
-                        // TODO: handle undefined behavior
-                        // if right == 0 return 0
-                        // if left == min(type_of(left)) && right == -1 return 0
-                        Some(crate::ScalarKind::Sint) => spirv::Op::SRem,
-                        // TODO: handle undefined behavior
-                        // if right == 0 return 0
-                        Some(crate::ScalarKind::Uint) => spirv::Op::UMod,
+                        Some(crate::ScalarKind::Sint) => {
+                            if self.writer.flags.contains(WriterFlags::SAFE_INTEGER_ARITHMETIC) {
+                                self.write_guarded_signed_divmod(
+                                    spirv::Op::SRem,
+                                    DivModOverflow::Zero,
+                                    id,
+                                    result_type_id,
+                                    left_id,
+                                    right_id,
+                                    left_ty_inner,
+                                    block,
+                                )?;
+                                self.cached[expr_handle] = id;
+                                return Ok(());
+                            }
+                            spirv::Op::SRem
+                        }
+                        Some(crate::ScalarKind::Uint) => {
+                            if self.writer.flags.contains(WriterFlags::SAFE_INTEGER_ARITHMETIC) {
+                                self.write_guarded_unsigned_divmod(
+                                    spirv::Op::UMod,
+                                    id,
+                                    result_type_id,
+                                    left_id,
+                                    right_id,
+                                    left_ty_inner,
+                                    block,
+                                )?;
+                                self.cached[expr_handle] = id;
+                                return Ok(());
+                            }
+                            spirv::Op::UMod
+                        }
                         // TODO: handle undefined behavior
                         // if right == 0 return ? see https://github.com/gpuweb/gpuweb/issues/2798
                         Some(crate::ScalarKind::Float) => spirv::Op::FRem,
@@ -867,18 +1118,42 @@ impl<'w> BlockContext<'w> {
                             arg0_id,
                             arg1_id,
                         )),
-                        // TODO: consider using integer dot product if VK_KHR_shader_integer_dot_product is available
-                        crate::TypeInner::Vector { size, .. } => {
-                            self.write_dot_product(
-                                id,
-                                result_type_id,
-                                arg0_id,
-                                arg1_id,
-                                size as u32,
-                                block,
-                            );
-                            self.cached[expr_handle] = id;
-                            return Ok(());
+                        crate::TypeInner::Vector { size, scalar } => {
+                            if self.writer.flags.contains(WriterFlags::INTEGER_DOT_PRODUCT) {
+                                self.writer.require_any(
+                                    "IntegerDotProduct",
+                                    &[
+                                        spirv::Capability::DotProduct,
+                                        spirv::Capability::DotProductInputAll,
+                                    ],
+                                )?;
+                                let op = match scalar.kind {
+                                    crate::ScalarKind::Sint => spirv::Op::SDot,
+                                    crate::ScalarKind::Uint => spirv::Op::UDot,
+                                    other => unreachable!(
+                                        "Unexpected integer dot product operand kind {:?}",
+                                        other
+                                    ),
+                                };
+                                MathOp::Custom(Instruction::binary(
+                                    op,
+                                    result_type_id,
+                                    id,
+                                    arg0_id,
+                                    arg1_id,
+                                ))
+                            } else {
+                                self.write_dot_product(
+                                    id,
+                                    result_type_id,
+                                    arg0_id,
+                                    arg1_id,
+                                    size as u32,
+                                    block,
+                                );
+                                self.cached[expr_handle] = id;
+                                return Ok(());
+                            }
                         }
                         _ => unreachable!(
                             "Correct TypeInner for dot product should be already validated"
@@ -1002,59 +1277,111 @@ impl<'w> BlockContext<'w> {
                         ))
                     }
                     Mf::CountLeadingZeros => {
-                        let (int_type_id, int_id, width) = match *arg_ty {
-                            crate::TypeInner::Vector { size, scalar } => {
-                                let ty =
-                                    LocalType::Numeric(NumericType::Vector { size, scalar }).into();
-
-                                self.temp_list.clear();
-                                self.temp_list.resize(
-                                    size as _,
-                                    self.writer
-                                        .get_constant_scalar_with(scalar.width * 8 - 1, scalar)?,
-                                );
-
-                                (
-                                    self.get_type_id(ty),
-                                    self.writer.get_constant_composite(ty, &self.temp_list),
-                                    scalar.width,
-                                )
-                            }
-                            crate::TypeInner::Scalar(scalar) => (
-                                self.get_type_id(LookupType::Local(LocalType::Numeric(
-                                    NumericType::Scalar(scalar),
-                                ))),
-                                self.writer
-                                    .get_constant_scalar_with(scalar.width * 8 - 1, scalar)?,
-                                scalar.width,
-                            ),
+                        let lane_scalar = match *arg_ty {
+                            crate::TypeInner::Scalar(scalar)
+                            | crate::TypeInner::Vector { scalar, .. } => scalar,
                             _ => unreachable!(),
                         };
 
-                        if width != 4 {
-                            unreachable!("This is validated out until a polyfill is implemented. https://github.com/gfx-rs/wgpu/issues/5276");
-                        };
-
-                        let msb_id = self.gen_id();
-                        block.body.push(Instruction::ext_inst(
-                            self.writer.gl450_ext_inst_id,
-                            if width != 4 {
-                                spirv::GLOp::FindILsb
-                            } else {
-                                spirv::GLOp::FindUMsb
-                            },
-                            int_type_id,
-                            msb_id,
-                            &[arg0_id],
-                        ));
+                        match lane_scalar.width {
+                            2 => {
+                                self.write_int_polyfill_vectorized(
+                                    block,
+                                    arg0_id,
+                                    arg_ty,
+                                    lane_scalar,
+                                    id,
+                                    result_type_id,
+                                    |ctx, block, lane_id, lane_result_id| {
+                                        ctx.write_count_leading_zeros_16(
+                                            block,
+                                            lane_id,
+                                            lane_scalar,
+                                            lane_result_id,
+                                        );
+                                        Ok(())
+                                    },
+                                )?;
+                                self.cached[expr_handle] = id;
+                                return Ok(());
+                            }
+                            8 => {
+                                self.writer
+                                    .require_any("64-bit integers", &[spirv::Capability::Int64])?;
+                                self.write_int_polyfill_vectorized(
+                                    block,
+                                    arg0_id,
+                                    arg_ty,
+                                    lane_scalar,
+                                    id,
+                                    result_type_id,
+                                    |ctx, block, lane_id, lane_result_id| {
+                                        ctx.write_count_leading_zeros_64(
+                                            block,
+                                            lane_id,
+                                            lane_scalar,
+                                            lane_result_id,
+                                        );
+                                        Ok(())
+                                    },
+                                )?;
+                                self.cached[expr_handle] = id;
+                                return Ok(());
+                            }
+                            _ => {
+                                let (int_type_id, int_id) = match *arg_ty {
+                                    crate::TypeInner::Vector { size, scalar } => {
+                                        let ty = LocalType::Numeric(NumericType::Vector {
+                                            size,
+                                            scalar,
+                                        })
+                                        .into();
+
+                                        self.temp_list.clear();
+                                        self.temp_list.resize(
+                                            size as _,
+                                            self.writer.get_constant_scalar_with(
+                                                scalar.width * 8 - 1,
+                                                scalar,
+                                            )?,
+                                        );
+
+                                        (
+                                            self.get_type_id(ty),
+                                            self.writer
+                                                .get_constant_composite(ty, &self.temp_list),
+                                        )
+                                    }
+                                    crate::TypeInner::Scalar(scalar) => (
+                                        self.get_type_id(LookupType::Local(LocalType::Numeric(
+                                            NumericType::Scalar(scalar),
+                                        ))),
+                                        self.writer.get_constant_scalar_with(
+                                            scalar.width * 8 - 1,
+                                            scalar,
+                                        )?,
+                                    ),
+                                    _ => unreachable!(),
+                                };
+
+                                let msb_id = self.gen_id();
+                                block.body.push(Instruction::ext_inst(
+                                    self.writer.gl450_ext_inst_id,
+                                    spirv::GLOp::FindUMsb,
+                                    int_type_id,
+                                    msb_id,
+                                    &[arg0_id],
+                                ));
 
-                        MathOp::Custom(Instruction::binary(
-                            spirv::Op::ISub,
-                            result_type_id,
-                            id,
-                            int_id,
-                            msb_id,
-                        ))
+                                MathOp::Custom(Instruction::binary(
+                                    spirv::Op::ISub,
+                                    result_type_id,
+                                    id,
+                                    int_id,
+                                    msb_id,
+                                ))
+                            }
+                        }
                     }
                     Mf::CountOneBits => MathOp::Custom(Instruction::unary(
                         spirv::Op::BitCount,
@@ -1183,17 +1510,123 @@ impl<'w> BlockContext<'w> {
                             count_id,
                         ))
                     }
-                    Mf::FirstTrailingBit => MathOp::Ext(spirv::GLOp::FindILsb),
+                    Mf::FirstTrailingBit => {
+                        let lane_scalar = match *arg_ty {
+                            crate::TypeInner::Scalar(scalar)
+                            | crate::TypeInner::Vector { scalar, .. } => scalar,
+                            _ => unreachable!(),
+                        };
+
+                        match lane_scalar.width {
+                            2 => {
+                                self.write_int_polyfill_vectorized(
+                                    block,
+                                    arg0_id,
+                                    arg_ty,
+                                    lane_scalar,
+                                    id,
+                                    result_type_id,
+                                    |ctx, block, lane_id, lane_result_id| {
+                                        ctx.write_find_trailing_bit_16(
+                                            block,
+                                            lane_id,
+                                            lane_scalar,
+                                            lane_result_id,
+                                        );
+                                        Ok(())
+                                    },
+                                )?;
+                                self.cached[expr_handle] = id;
+                                return Ok(());
+                            }
+                            8 => {
+                                self.writer
+                                    .require_any("64-bit integers", &[spirv::Capability::Int64])?;
+                                self.write_int_polyfill_vectorized(
+                                    block,
+                                    arg0_id,
+                                    arg_ty,
+                                    lane_scalar,
+                                    id,
+                                    result_type_id,
+                                    |ctx, block, lane_id, lane_result_id| {
+                                        ctx.write_find_trailing_bit_64(
+                                            block,
+                                            lane_id,
+                                            lane_scalar,
+                                            lane_result_id,
+                                        );
+                                        Ok(())
+                                    },
+                                )?;
+                                self.cached[expr_handle] = id;
+                                return Ok(());
+                            }
+                            _ => MathOp::Ext(spirv::GLOp::FindILsb),
+                        }
+                    }
                     Mf::FirstLeadingBit => {
-                        if arg_ty.scalar_width() == Some(4) {
-                            let thing = match arg_scalar_kind {
-                                Some(crate::ScalarKind::Uint) => spirv::GLOp::FindUMsb,
-                                Some(crate::ScalarKind::Sint) => spirv::GLOp::FindSMsb,
-                                other => unimplemented!("Unexpected firstLeadingBit({:?})", other),
-                            };
-                            MathOp::Ext(thing)
-                        } else {
-                            unreachable!("This is validated out until a polyfill is implemented. https://github.com/gfx-rs/wgpu/issues/5276");
+                        let lane_scalar = match *arg_ty {
+                            crate::TypeInner::Scalar(scalar)
+                            | crate::TypeInner::Vector { scalar, .. } => scalar,
+                            _ => unreachable!(),
+                        };
+
+                        match lane_scalar.width {
+                            4 => {
+                                let thing = match arg_scalar_kind {
+                                    Some(crate::ScalarKind::Uint) => spirv::GLOp::FindUMsb,
+                                    Some(crate::ScalarKind::Sint) => spirv::GLOp::FindSMsb,
+                                    other => {
+                                        unimplemented!("Unexpected firstLeadingBit({:?})", other)
+                                    }
+                                };
+                                MathOp::Ext(thing)
+                            }
+                            2 => {
+                                self.write_int_polyfill_vectorized(
+                                    block,
+                                    arg0_id,
+                                    arg_ty,
+                                    lane_scalar,
+                                    id,
+                                    result_type_id,
+                                    |ctx, block, lane_id, lane_result_id| {
+                                        ctx.write_find_leading_bit_16(
+                                            block,
+                                            lane_id,
+                                            lane_scalar,
+                                            lane_result_id,
+                                        );
+                                        Ok(())
+                                    },
+                                )?;
+                                self.cached[expr_handle] = id;
+                                return Ok(());
+                            }
+                            8 => {
+                                self.writer
+                                    .require_any("64-bit integers", &[spirv::Capability::Int64])?;
+                                self.write_int_polyfill_vectorized(
+                                    block,
+                                    arg0_id,
+                                    arg_ty,
+                                    lane_scalar,
+                                    id,
+                                    result_type_id,
+                                    |ctx, block, lane_id, lane_result_id| {
+                                        ctx.write_find_leading_bit_64(
+                                            block,
+                                            lane_id,
+                                            lane_scalar,
+                                            lane_result_id,
+                                        )
+                                    },
+                                )?;
+                                self.cached[expr_handle] = id;
+                                return Ok(());
+                            }
+                            other => unimplemented!("Unexpected firstLeadingBit width({:?})", other),
                         }
                     }
                     Mf::Pack4x8unorm => MathOp::Ext(spirv::GLOp::PackUnorm4x8),
@@ -1201,10 +1634,12 @@ impl<'w> BlockContext<'w> {
                     Mf::Pack2x16float => MathOp::Ext(spirv::GLOp::PackHalf2x16),
                     Mf::Pack2x16unorm => MathOp::Ext(spirv::GLOp::PackUnorm2x16),
                     Mf::Pack2x16snorm => MathOp::Ext(spirv::GLOp::PackSnorm2x16),
-                    fun @ (Mf::Pack4xI8 | Mf::Pack4xU8) => {
-                        let (int_type, is_signed) = match fun {
-                            Mf::Pack4xI8 => (crate::ScalarKind::Sint, true),
-                            Mf::Pack4xU8 => (crate::ScalarKind::Uint, false),
+                    fun @ (Mf::Pack4xI8 | Mf::Pack4xU8 | Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp) => {
+                        let (int_type, is_signed, should_clamp) = match fun {
+                            Mf::Pack4xI8 => (crate::ScalarKind::Sint, true, false),
+                            Mf::Pack4xU8 => (crate::ScalarKind::Uint, false, false),
+                            Mf::Pack4xI8Clamp => (crate::ScalarKind::Sint, true, true),
+                            Mf::Pack4xU8Clamp => (crate::ScalarKind::Uint, false, true),
                             _ => unreachable!(),
                         };
                         let uint_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
@@ -1222,9 +1657,19 @@ impl<'w> BlockContext<'w> {
 
                         let zero = self.writer.get_constant_scalar(crate::Literal::U32(0));
                         let mut preresult = zero;
-                        block
-                            .body
-                            .reserve(usize::from(VEC_LENGTH) * (2 + usize::from(is_signed)));
+                        let clamp_instructions = if should_clamp {
+                            if is_signed {
+                                2
+                            } else {
+                                1
+                            }
+                        } else {
+                            0
+                        };
+                        block.body.reserve(
+                            usize::from(VEC_LENGTH)
+                                * (2 + usize::from(is_signed) + clamp_instructions),
+                        );
 
                         let eight = self.writer.get_constant_scalar(crate::Literal::U32(8));
                         const VEC_LENGTH: u8 = 4;
@@ -1239,6 +1684,46 @@ impl<'w> BlockContext<'w> {
                                 arg0_id,
                                 i,
                             ));
+                            if should_clamp {
+                                // Clamp each lane before packing so out-of-range inputs
+                                // saturate instead of wrapping in `BitFieldInsert`.
+                                if is_signed {
+                                    let lower = self
+                                        .writer
+                                        .get_constant_scalar(crate::Literal::I32(-128));
+                                    let upper =
+                                        self.writer.get_constant_scalar(crate::Literal::I32(127));
+                                    let floored = self.gen_id();
+                                    block.body.push(Instruction::ext_inst(
+                                        self.writer.gl450_ext_inst_id,
+                                        spirv::GLOp::SMax,
+                                        int_type_id,
+                                        floored,
+                                        &[extracted, lower],
+                                    ));
+                                    let clamped = self.gen_id();
+                                    block.body.push(Instruction::ext_inst(
+                                        self.writer.gl450_ext_inst_id,
+                                        spirv::GLOp::SMin,
+                                        int_type_id,
+                                        clamped,
+                                        &[floored, upper],
+                                    ));
+                                    extracted = clamped;
+                                } else {
+                                    let upper =
+                                        self.writer.get_constant_scalar(crate::Literal::U32(255));
+                                    let clamped = self.gen_id();
+                                    block.body.push(Instruction::ext_inst(
+                                        self.writer.gl450_ext_inst_id,
+                                        spirv::GLOp::UMin,
+                                        int_type_id,
+                                        clamped,
+                                        &[extracted, upper],
+                                    ));
+                                    extracted = clamped;
+                                }
+                            }
                             if is_signed {
                                 let casted = self.gen_id();
                                 block.body.push(Instruction::unary(
@@ -1339,6 +1824,100 @@ impl<'w> BlockContext<'w> {
 
                         MathOp::Custom(Instruction::composite_construct(result_type_id, id, &parts))
                     }
+                    fun @ (Mf::Dot4I8Packed | Mf::Dot4U8Packed) => {
+                        // Accelerated path requires `SPV_KHR_integer_dot_product`, which these
+                        // two capabilities pull in; `require_any` below declares it on the module.
+                        let is_signed = matches!(fun, Mf::Dot4I8Packed);
+                        if self.writer.supports_any(&[
+                            spirv::Capability::DotProduct,
+                            spirv::Capability::DotProductInput4x8BitPacked,
+                        ]) {
+                            self.writer.require_any(
+                                "PackedIntegerDotProduct",
+                                &[
+                                    spirv::Capability::DotProduct,
+                                    spirv::Capability::DotProductInput4x8BitPacked,
+                                ],
+                            )?;
+                            let op = if is_signed {
+                                spirv::Op::SDot
+                            } else {
+                                spirv::Op::UDot
+                            };
+                            let mut inst = Instruction::new(op);
+                            inst.set_type(result_type_id);
+                            inst.set_result(id);
+                            inst.add_operand(arg0_id);
+                            inst.add_operand(arg1_id);
+                            inst.add_operand(1); // PackedVectorFormat4x8Bit
+                            MathOp::Custom(inst)
+                        } else {
+                            // Software fallback: pull each of the four bytes packed into
+                            // `arg0`/`arg1` out with `OpBitFieldSExtract`/`OpBitFieldUExtract` --
+                            // the same shape `Mf::Unpack4xI8`/`Mf::Unpack4xU8` use above -- then
+                            // multiply and sum them the way `write_dot_product` does for the
+                            // unpacked vector case.
+                            let extract_op = if is_signed {
+                                spirv::Op::BitFieldSExtract
+                            } else {
+                                spirv::Op::BitFieldUExtract
+                            };
+                            let eight = self.writer.get_constant_scalar(crate::Literal::U32(8));
+                            let mut partial_sum = self.writer.get_constant_null(result_type_id);
+
+                            const VEC_LENGTH: u8 = 4;
+                            for i in 0..VEC_LENGTH {
+                                let offset = self
+                                    .writer
+                                    .get_constant_scalar(crate::Literal::U32(i as u32 * 8));
+
+                                let a_id = self.gen_id();
+                                block.body.push(Instruction::ternary(
+                                    extract_op,
+                                    result_type_id,
+                                    a_id,
+                                    arg0_id,
+                                    offset,
+                                    eight,
+                                ));
+                                let b_id = self.gen_id();
+                                block.body.push(Instruction::ternary(
+                                    extract_op,
+                                    result_type_id,
+                                    b_id,
+                                    arg1_id,
+                                    offset,
+                                    eight,
+                                ));
+
+                                let prod_id = self.gen_id();
+                                block.body.push(Instruction::binary(
+                                    spirv::Op::IMul,
+                                    result_type_id,
+                                    prod_id,
+                                    a_id,
+                                    b_id,
+                                ));
+
+                                let sum_id = if i == VEC_LENGTH - 1 {
+                                    id
+                                } else {
+                                    self.gen_id()
+                                };
+                                block.body.push(Instruction::binary(
+                                    spirv::Op::IAdd,
+                                    result_type_id,
+                                    sum_id,
+                                    partial_sum,
+                                    prod_id,
+                                ));
+                                partial_sum = sum_id;
+                            }
+
+                            self.cached[expr_handle] = id;
+                            return Ok(());
+                        }
+                    }
                 };
 
                 block.body.push(match math_op {
@@ -1356,7 +1935,10 @@ impl<'w> BlockContext<'w> {
             crate::Expression::LocalVariable(variable) => self.function.variables[&variable].id,
             crate::Expression::Load { pointer } => {
                 match self.write_expression_pointer(pointer, block, None)? {
-                    ExpressionPointer::Ready { pointer_id } => {
+                    ExpressionPointer::Ready {
+                        pointer_id,
+                        non_uniform,
+                    } => {
                         let id = self.gen_id();
                         let atomic_space =
                             match *self.fun_info[pointer].ty.inner_with(&self.ir_module.types) {
@@ -1383,11 +1965,38 @@ impl<'w> BlockContext<'w> {
                             Instruction::load(result_type_id, id, pointer_id, None)
                         };
                         block.body.push(instruction);
+                        // The pointer alone being decorated NonUniform isn't enough to satisfy
+                        // VUID-RuntimeSpirv-NonUniform-06274; the load's result needs it too.
+                        if non_uniform {
+                            self.writer.decorate_non_uniform_binding_array_access(id)?;
+                        }
                         id
                     }
-                    ExpressionPointer::Conditional { condition, access } => {
-                        //TODO: support atomics?
-                        self.write_conditional_indexed_load(
+                    ExpressionPointer::Conditional {
+                        condition,
+                        access,
+                        non_uniform,
+                    } => {
+                        let atomic_space =
+                            match *self.fun_info[pointer].ty.inner_with(&self.ir_module.types) {
+                                crate::TypeInner::Pointer { base, space } => {
+                                    match self.ir_module.types[base].inner {
+                                        crate::TypeInner::Atomic { .. } => Some(space),
+                                        _ => None,
+                                    }
+                                }
+                                _ => None,
+                            };
+                        // Resolve the scope/semantics constants up front, since the closure
+                        // below only gets `id_gen` and `block`, not `self`.
+                        let atomic_scope_and_semantics = atomic_space.map(|space| {
+                            let (semantics, scope) = space.to_spirv_semantics_and_scope();
+                            (
+                                self.get_scope_constant(scope as u32),
+                                self.get_index_constant(semantics.bits()),
+                            )
+                        });
+                        let value_id = self.write_conditional_indexed_load(
                             result_type_id,
                             condition,
                             block,
@@ -1396,15 +2005,32 @@ impl<'w> BlockContext<'w> {
                                 let pointer_id = access.result_id.unwrap();
                                 let value_id = id_gen.next();
                                 block.body.push(access);
-                                block.body.push(Instruction::load(
-                                    result_type_id,
-                                    value_id,
-                                    pointer_id,
-                                    None,
-                                ));
+                                block.body.push(match atomic_scope_and_semantics {
+                                    Some((scope_constant_id, semantics_id)) => {
+                                        Instruction::atomic_load(
+                                            result_type_id,
+                                            value_id,
+                                            pointer_id,
+                                            scope_constant_id,
+                                            semantics_id,
+                                        )
+                                    }
+                                    None => {
+                                        Instruction::load(result_type_id, value_id, pointer_id, None)
+                                    }
+                                });
                                 value_id
                             },
-                        )
+                        );
+                        // The in-bounds branch's closure has no access to `self`, so we can't
+                        // decorate the load's own result there; decorate the value merged from
+                        // both branches instead, which is a safe (if slightly conservative)
+                        // over-approximation.
+                        if non_uniform {
+                            self.writer
+                                .decorate_non_uniform_binding_array_access(value_id)?;
+                        }
+                        value_id
                     }
                 }
             }
@@ -1714,9 +2340,70 @@ impl<'w> BlockContext<'w> {
         };
 
         self.cached[expr_handle] = id;
+
+        if is_named_expression && self.writer.flags.contains(WriterFlags::DEBUG_INFO_100) {
+            if let Some(local_variable_id) = self.writer.debug_local_variable_id(expr_handle) {
+                // A `LocalVariable` expression resolves to a pointer, so it gets a
+                // `DebugDeclare` (bound once, for the variable's address) rather than a
+                // `DebugValue` (rebound every time the value is recomputed).
+                let opcode = match self.ir_function.expressions[expr_handle] {
+                    crate::Expression::LocalVariable(_) => debug_info_100::DEBUG_DECLARE,
+                    _ => debug_info_100::DEBUG_VALUE,
+                };
+                let void_type_id = self.writer.void_type_id;
+                let empty_expression_id = self.writer.debug_info_100_empty_expression_id;
+                let debug_id = self.gen_id();
+                block.body.push(Instruction::ext_inst(
+                    self.writer.debug_info_100_ext_inst_id,
+                    opcode,
+                    void_type_id,
+                    debug_id,
+                    &[local_variable_id, id, empty_expression_id],
+                ));
+            }
+        }
+
         Ok(())
     }
 
+    /// Build an `OpAccessChain` pointer into a binding array, clamping `index_id` to
+    /// `[0, len - 1]` with `OpExtInst UMin` first so the chain is always well-defined.
+    ///
+    /// This is the `Restrict` bounds-check policy applied unconditionally, used as the
+    /// fallback for binding arrays when [`write_expression_pointer`](Self::write_expression_pointer)
+    /// would otherwise return [`ExpressionPointer::Conditional`], since a branch-guarded
+    /// access doesn't make sense for arrays of opaque handles like images and samplers.
+    fn write_clamped_binding_array_pointer(
+        &mut self,
+        result_type_id: Word,
+        root_id: Word,
+        index_id: Word,
+        len: u32,
+        block: &mut Block,
+    ) -> Word {
+        let u32_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(crate::Scalar::U32),
+        )));
+        let max_index_id = self.writer.get_constant_scalar(crate::Literal::U32(len - 1));
+        let clamped_index_id = self.gen_id();
+        block.body.push(Instruction::ext_inst(
+            self.writer.gl450_ext_inst_id,
+            spirv::GLOp::UMin,
+            u32_type_id,
+            clamped_index_id,
+            &[index_id, max_index_id],
+        ));
+
+        let pointer_id = self.gen_id();
+        block.body.push(Instruction::access_chain(
+            result_type_id,
+            pointer_id,
+            root_id,
+            &[clamped_index_id],
+        ));
+        pointer_id
+    }
+
     /// Build an `OpAccessChain` instruction.
     ///
     /// Emit any needed bounds-checking expressions to `block`.
@@ -1782,9 +2469,13 @@ impl<'w> BlockContext<'w> {
                         // `index` is constant, so this can't possibly require
                         // setting `is_nonuniform_binding_array_access`.
 
-                        // Even though the index value is statically known, `base`
-                        // may be a runtime-sized array, so we still need to go
-                        // through the bounds check process.
+                        // Even though the index value is statically known, `base` may be a
+                        // runtime-sized array, so we still need to go through the bounds check
+                        // process: `write_bounds_check` is responsible for noticing the known
+                        // index and, for `Restrict`, still clamping it against an `OpArrayLength`
+                        // of the array (rather than trusting the static bound) and, for
+                        // `ReadZeroSkipWrite`, still emitting the `index < length` comparison
+                        // into `accumulated_checks` below.
                         self.write_access_chain_index(
                             base,
                             crate::proc::index::GuardedIndex::Known(index),
@@ -1816,6 +2507,7 @@ impl<'w> BlockContext<'w> {
                 root_id,
                 ExpressionPointer::Ready {
                     pointer_id: root_id,
+                    non_uniform: is_non_uniform_binding_array,
                 },
             )
         } else {
@@ -1829,10 +2521,17 @@ impl<'w> BlockContext<'w> {
             // the zero value (for loads). Otherwise, we can emit the access
             // ourselves, and just hand them the id of the pointer.
             let expr_pointer = match accumulated_checks {
-                Some(condition) => ExpressionPointer::Conditional { condition, access },
+                Some(condition) => ExpressionPointer::Conditional {
+                    condition,
+                    access,
+                    non_uniform: is_non_uniform_binding_array,
+                },
                 None => {
                     block.body.push(access);
-                    ExpressionPointer::Ready { pointer_id }
+                    ExpressionPointer::Ready {
+                        pointer_id,
+                        non_uniform: is_non_uniform_binding_array,
+                    }
                 }
             };
             (pointer_id, expr_pointer)
@@ -2004,8 +2703,664 @@ impl<'w> BlockContext<'w> {
         ));
     }
 
-    /// Build the instructions for vector - scalar multiplication
-    fn write_vector_scalar_mult(
+    /// If `left` or `right` is a float multiply used nowhere else, and the `OpFMul` it produced
+    /// is the instruction `block` most recently emitted, pop that instruction and return its two
+    /// operands together with the other side's id, so the caller can fold the pair into a single
+    /// `Fma` instead of a separate multiply and add.
+    ///
+    /// Returns `None` (leaving `block` untouched) if neither operand is a fusable multiply.
+    fn take_fusable_multiply(
+        &mut self,
+        left: Handle<crate::Expression>,
+        right: Handle<crate::Expression>,
+        block: &mut Block,
+    ) -> Option<(Word, Word, Word)> {
+        for (candidate, other) in [(left, right), (right, left)] {
+            let crate::Expression::Binary {
+                op: crate::BinaryOperator::Multiply,
+                left: mul_left,
+                right: mul_right,
+            } = self.ir_function.expressions[candidate]
+            else {
+                continue;
+            };
+
+            // Only fuse when nothing else reads the product, and only when its `OpFMul` is
+            // right at the end of the block, so popping it can't reorder past another
+            // instruction that might depend on it (e.g. through a side effect).
+            if self.fun_info[candidate].ref_count != 1 {
+                continue;
+            }
+            let last_is_product = block.body.last().map_or(false, |inst| {
+                inst.op == spirv::Op::FMul && inst.result_id == Some(self.cached[candidate])
+            });
+            if !last_is_product {
+                continue;
+            }
+
+            block.body.pop();
+            return Some((
+                self.cached[mul_left],
+                self.cached[mul_right],
+                self.cached[other],
+            ));
+        }
+        None
+    }
+
+    /// Run `per_lane` once for a scalar `arg0_id`, or once per component for a vector
+    /// `arg0_id`, writing each lane's result into a fresh id and assembling them back into a
+    /// vector with `OpCompositeConstruct`. Either way the overall result lands in `result_id`.
+    ///
+    /// Used by the 16- and 64-bit polyfills for `firstLeadingBit`/`firstTrailingBit`/
+    /// `countLeadingZeros`, which can't rely on the native GLSL.std.450 ops at those widths.
+    fn write_int_polyfill_vectorized(
+        &mut self,
+        block: &mut Block,
+        arg0_id: Word,
+        arg_ty: &crate::TypeInner,
+        lane_scalar: crate::Scalar,
+        result_id: Word,
+        result_type_id: Word,
+        mut per_lane: impl FnMut(&mut Self, &mut Block, Word, Word) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        match *arg_ty {
+            crate::TypeInner::Scalar(_) => per_lane(self, block, arg0_id, result_id),
+            crate::TypeInner::Vector { size, .. } => {
+                let lane_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+                    NumericType::Scalar(lane_scalar),
+                )));
+
+                self.temp_list.clear();
+                for index in 0..size as u32 {
+                    let lane_id = self.gen_id();
+                    block.body.push(Instruction::composite_extract(
+                        lane_type_id,
+                        lane_id,
+                        arg0_id,
+                        &[index],
+                    ));
+
+                    let lane_result_id = self.gen_id();
+                    per_lane(self, block, lane_id, lane_result_id)?;
+                    self.temp_list.push(lane_result_id);
+                }
+
+                block.body.push(Instruction::composite_construct(
+                    result_type_id,
+                    result_id,
+                    &self.temp_list,
+                ));
+                Ok(())
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Split a 64-bit scalar lane into its low and high 32-bit halves by bitcasting to a
+    /// `vec2<u32>` and pulling the two components back out.
+    fn write_split_64(&mut self, block: &mut Block, value_id: Word) -> (Word, Word) {
+        let pair_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Vector {
+                size: crate::VectorSize::Bi,
+                scalar: crate::Scalar::U32,
+            },
+        )));
+        let pair_id = self.gen_id();
+        block.body.push(Instruction::unary(
+            spirv::Op::Bitcast,
+            pair_type_id,
+            pair_id,
+            value_id,
+        ));
+
+        let u32_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(crate::Scalar::U32),
+        )));
+        let low_id = self.gen_id();
+        block.body.push(Instruction::composite_extract(
+            u32_type_id, low_id, pair_id, &[0],
+        ));
+        let high_id = self.gen_id();
+        block.body.push(Instruction::composite_extract(
+            u32_type_id, high_id, pair_id, &[1],
+        ));
+        (low_id, high_id)
+    }
+
+    /// `x = select(x, ~x, x < 0)`: fold a signed value's sign into its bit pattern so the rest
+    /// of a leading-bit polyfill only has to deal with the unsigned case.
+    fn write_fold_negative(
+        &mut self,
+        block: &mut Block,
+        value_id: Word,
+        scalar: crate::Scalar,
+    ) -> Result<Word, Error> {
+        let type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(scalar),
+        )));
+        let bool_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(crate::Scalar::BOOL),
+        )));
+
+        let zero_id = self.writer.get_constant_scalar_with(0, scalar)?;
+        let is_negative_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::SLessThan,
+            bool_type_id,
+            is_negative_id,
+            value_id,
+            zero_id,
+        ));
+
+        let inverted_id = self.gen_id();
+        block.body.push(Instruction::unary(
+            spirv::Op::Not,
+            type_id,
+            inverted_id,
+            value_id,
+        ));
+
+        let folded_id = self.gen_id();
+        block.body.push(Instruction::ternary(
+            spirv::Op::Select,
+            type_id,
+            folded_id,
+            is_negative_id,
+            inverted_id,
+            value_id,
+        ));
+        Ok(folded_id)
+    }
+
+    /// `firstLeadingBit` on one 64-bit lane: fold the sign (for signed inputs), split into
+    /// 32-bit halves, search the high half first (giving `32 + msb(high)`) and fall back to
+    /// `msb(low)` when the high half is zero, or to all-ones when both halves are zero, then sign-extend the
+    /// 32-bit index back up to the original 64-bit type.
+    fn write_find_leading_bit_64(
+        &mut self,
+        block: &mut Block,
+        value_id: Word,
+        lane_scalar: crate::Scalar,
+        result_id: Word,
+    ) -> Result<(), Error> {
+        let folded_id = if lane_scalar.kind == crate::ScalarKind::Sint {
+            self.write_fold_negative(block, value_id, lane_scalar)?
+        } else {
+            value_id
+        };
+
+        let (low_id, high_id) = self.write_split_64(block, folded_id);
+        let u32_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(crate::Scalar::U32),
+        )));
+        let bool_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(crate::Scalar::BOOL),
+        )));
+
+        let msb_high_id = self.gen_id();
+        block.body.push(Instruction::ext_inst(
+            self.writer.gl450_ext_inst_id,
+            spirv::GLOp::FindUMsb,
+            u32_type_id,
+            msb_high_id,
+            &[high_id],
+        ));
+        let msb_low_id = self.gen_id();
+        block.body.push(Instruction::ext_inst(
+            self.writer.gl450_ext_inst_id,
+            spirv::GLOp::FindUMsb,
+            u32_type_id,
+            msb_low_id,
+            &[low_id],
+        ));
+
+        let thirty_two_id = self.writer.get_constant_scalar(crate::Literal::U32(32));
+        let offset_high_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::IAdd,
+            u32_type_id,
+            offset_high_id,
+            thirty_two_id,
+            msb_high_id,
+        ));
+
+        let zero_id = self.writer.get_constant_scalar(crate::Literal::U32(0));
+        let not_found_id = self
+            .writer
+            .get_constant_scalar(crate::Literal::U32(u32::MAX));
+
+        let low_nonzero_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::INotEqual,
+            bool_type_id,
+            low_nonzero_id,
+            low_id,
+            zero_id,
+        ));
+        let low_result_id = self.gen_id();
+        block.body.push(Instruction::ternary(
+            spirv::Op::Select,
+            u32_type_id,
+            low_result_id,
+            low_nonzero_id,
+            msb_low_id,
+            not_found_id,
+        ));
+
+        let high_nonzero_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::INotEqual,
+            bool_type_id,
+            high_nonzero_id,
+            high_id,
+            zero_id,
+        ));
+        let index_id = self.gen_id();
+        block.body.push(Instruction::ternary(
+            spirv::Op::Select,
+            u32_type_id,
+            index_id,
+            high_nonzero_id,
+            offset_high_id,
+            low_result_id,
+        ));
+
+        let dst_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(lane_scalar),
+        )));
+        block.body.push(Instruction::unary(
+            spirv::Op::SConvert,
+            dst_type_id,
+            result_id,
+            index_id,
+        ));
+        Ok(())
+    }
+
+    /// `firstTrailingBit` on one 64-bit lane: the symmetric counterpart of
+    /// [`Self::write_find_leading_bit_64`], searching the low half first and falling back to
+    /// `32 + lsb(high)`. Trailing-bit position doesn't depend on sign, so there's no fold.
+    fn write_find_trailing_bit_64(
+        &mut self,
+        block: &mut Block,
+        value_id: Word,
+        lane_scalar: crate::Scalar,
+        result_id: Word,
+    ) {
+        let (low_id, high_id) = self.write_split_64(block, value_id);
+        let u32_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(crate::Scalar::U32),
+        )));
+        let bool_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(crate::Scalar::BOOL),
+        )));
+
+        let lsb_low_id = self.gen_id();
+        block.body.push(Instruction::ext_inst(
+            self.writer.gl450_ext_inst_id,
+            spirv::GLOp::FindILsb,
+            u32_type_id,
+            lsb_low_id,
+            &[low_id],
+        ));
+        let lsb_high_id = self.gen_id();
+        block.body.push(Instruction::ext_inst(
+            self.writer.gl450_ext_inst_id,
+            spirv::GLOp::FindILsb,
+            u32_type_id,
+            lsb_high_id,
+            &[high_id],
+        ));
+
+        let thirty_two_id = self.writer.get_constant_scalar(crate::Literal::U32(32));
+        let offset_high_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::IAdd,
+            u32_type_id,
+            offset_high_id,
+            thirty_two_id,
+            lsb_high_id,
+        ));
+
+        let zero_id = self.writer.get_constant_scalar(crate::Literal::U32(0));
+        let not_found_id = self
+            .writer
+            .get_constant_scalar(crate::Literal::U32(u32::MAX));
+
+        let high_nonzero_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::INotEqual,
+            bool_type_id,
+            high_nonzero_id,
+            high_id,
+            zero_id,
+        ));
+        let high_result_id = self.gen_id();
+        block.body.push(Instruction::ternary(
+            spirv::Op::Select,
+            u32_type_id,
+            high_result_id,
+            high_nonzero_id,
+            offset_high_id,
+            not_found_id,
+        ));
+
+        let low_nonzero_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::INotEqual,
+            bool_type_id,
+            low_nonzero_id,
+            low_id,
+            zero_id,
+        ));
+        let index_id = self.gen_id();
+        block.body.push(Instruction::ternary(
+            spirv::Op::Select,
+            u32_type_id,
+            index_id,
+            low_nonzero_id,
+            lsb_low_id,
+            high_result_id,
+        ));
+
+        let dst_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(lane_scalar),
+        )));
+        block.body.push(Instruction::unary(
+            spirv::Op::SConvert,
+            dst_type_id,
+            result_id,
+            index_id,
+        ));
+    }
+
+    /// `countLeadingZeros` on one 64-bit lane: if the high half is non-zero, the count is
+    /// entirely within it; otherwise it's 32 plus the low half's count, or 64 if both halves
+    /// are zero.
+    fn write_count_leading_zeros_64(
+        &mut self,
+        block: &mut Block,
+        value_id: Word,
+        lane_scalar: crate::Scalar,
+        result_id: Word,
+    ) {
+        let (low_id, high_id) = self.write_split_64(block, value_id);
+        let u32_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(crate::Scalar::U32),
+        )));
+        let bool_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(crate::Scalar::BOOL),
+        )));
+
+        let thirty_one_id = self.writer.get_constant_scalar(crate::Literal::U32(31));
+
+        let msb_high_id = self.gen_id();
+        block.body.push(Instruction::ext_inst(
+            self.writer.gl450_ext_inst_id,
+            spirv::GLOp::FindUMsb,
+            u32_type_id,
+            msb_high_id,
+            &[high_id],
+        ));
+        let clz_high_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::ISub,
+            u32_type_id,
+            clz_high_id,
+            thirty_one_id,
+            msb_high_id,
+        ));
+
+        let msb_low_id = self.gen_id();
+        block.body.push(Instruction::ext_inst(
+            self.writer.gl450_ext_inst_id,
+            spirv::GLOp::FindUMsb,
+            u32_type_id,
+            msb_low_id,
+            &[low_id],
+        ));
+        let clz_low_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::ISub,
+            u32_type_id,
+            clz_low_id,
+            thirty_one_id,
+            msb_low_id,
+        ));
+
+        let thirty_two_id = self.writer.get_constant_scalar(crate::Literal::U32(32));
+        let offset_low_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::IAdd,
+            u32_type_id,
+            offset_low_id,
+            thirty_two_id,
+            clz_low_id,
+        ));
+
+        let zero_id = self.writer.get_constant_scalar(crate::Literal::U32(0));
+        let sixty_four_id = self.writer.get_constant_scalar(crate::Literal::U32(64));
+
+        let low_nonzero_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::INotEqual,
+            bool_type_id,
+            low_nonzero_id,
+            low_id,
+            zero_id,
+        ));
+        let low_result_id = self.gen_id();
+        block.body.push(Instruction::ternary(
+            spirv::Op::Select,
+            u32_type_id,
+            low_result_id,
+            low_nonzero_id,
+            offset_low_id,
+            sixty_four_id,
+        ));
+
+        let high_nonzero_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::INotEqual,
+            bool_type_id,
+            high_nonzero_id,
+            high_id,
+            zero_id,
+        ));
+        let result_u32_id = self.gen_id();
+        block.body.push(Instruction::ternary(
+            spirv::Op::Select,
+            u32_type_id,
+            result_u32_id,
+            high_nonzero_id,
+            clz_high_id,
+            low_result_id,
+        ));
+
+        let dst_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(lane_scalar),
+        )));
+        let widen_op = match lane_scalar.kind {
+            crate::ScalarKind::Sint => spirv::Op::SConvert,
+            _ => spirv::Op::UConvert,
+        };
+        block.body.push(Instruction::unary(
+            widen_op,
+            dst_type_id,
+            result_id,
+            result_u32_id,
+        ));
+    }
+
+    /// `firstLeadingBit` on one 16-bit lane: widen to 32 bits the same way the value's own
+    /// sign would (so `FindSMsb`/`FindUMsb` see the right bit pattern), run the native op, and
+    /// truncate the index straight back down -- extending past bit 16 can't change where the
+    /// answer lies within the low 16 bits.
+    fn write_find_leading_bit_16(
+        &mut self,
+        block: &mut Block,
+        value_id: Word,
+        lane_scalar: crate::Scalar,
+        result_id: Word,
+    ) {
+        let wide_scalar = crate::Scalar {
+            kind: lane_scalar.kind,
+            width: 4,
+        };
+        let wide_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(wide_scalar),
+        )));
+        let widen_op = match lane_scalar.kind {
+            crate::ScalarKind::Sint => spirv::Op::SConvert,
+            _ => spirv::Op::UConvert,
+        };
+        let wide_id = self.gen_id();
+        block.body.push(Instruction::unary(
+            widen_op,
+            wide_type_id,
+            wide_id,
+            value_id,
+        ));
+
+        let msb_op = match lane_scalar.kind {
+            crate::ScalarKind::Sint => spirv::GLOp::FindSMsb,
+            _ => spirv::GLOp::FindUMsb,
+        };
+        let msb_id = self.gen_id();
+        block.body.push(Instruction::ext_inst(
+            self.writer.gl450_ext_inst_id,
+            msb_op,
+            wide_type_id,
+            msb_id,
+            &[wide_id],
+        ));
+
+        let dst_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(lane_scalar),
+        )));
+        block.body.push(Instruction::unary(
+            spirv::Op::SConvert,
+            dst_type_id,
+            result_id,
+            msb_id,
+        ));
+    }
+
+    /// `firstTrailingBit` on one 16-bit lane: zero-extend to 32 bits (sign doesn't matter for
+    /// a trailing-bit search), run `FindILsb`, and truncate the index back down.
+    fn write_find_trailing_bit_16(
+        &mut self,
+        block: &mut Block,
+        value_id: Word,
+        lane_scalar: crate::Scalar,
+        result_id: Word,
+    ) {
+        let wide_scalar = crate::Scalar {
+            kind: crate::ScalarKind::Uint,
+            width: 4,
+        };
+        let wide_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(wide_scalar),
+        )));
+        let wide_id = self.gen_id();
+        block.body.push(Instruction::unary(
+            spirv::Op::UConvert,
+            wide_type_id,
+            wide_id,
+            value_id,
+        ));
+
+        let lsb_id = self.gen_id();
+        block.body.push(Instruction::ext_inst(
+            self.writer.gl450_ext_inst_id,
+            spirv::GLOp::FindILsb,
+            wide_type_id,
+            lsb_id,
+            &[wide_id],
+        ));
+
+        let dst_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(lane_scalar),
+        )));
+        block.body.push(Instruction::unary(
+            spirv::Op::SConvert,
+            dst_type_id,
+            result_id,
+            lsb_id,
+        ));
+    }
+
+    /// `countLeadingZeros` on one 16-bit lane: zero-extend to 32 bits, run the native
+    /// `31 - FindUMsb` formula, then subtract the 16 bits of padding we just added before
+    /// narrowing back down.
+    fn write_count_leading_zeros_16(
+        &mut self,
+        block: &mut Block,
+        value_id: Word,
+        lane_scalar: crate::Scalar,
+        result_id: Word,
+    ) {
+        let wide_scalar = crate::Scalar {
+            kind: crate::ScalarKind::Uint,
+            width: 4,
+        };
+        let wide_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(wide_scalar),
+        )));
+        let wide_id = self.gen_id();
+        block.body.push(Instruction::unary(
+            spirv::Op::UConvert,
+            wide_type_id,
+            wide_id,
+            value_id,
+        ));
+
+        let thirty_one_id = self.writer.get_constant_scalar(crate::Literal::U32(31));
+        let msb_id = self.gen_id();
+        block.body.push(Instruction::ext_inst(
+            self.writer.gl450_ext_inst_id,
+            spirv::GLOp::FindUMsb,
+            wide_type_id,
+            msb_id,
+            &[wide_id],
+        ));
+        let clz32_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::ISub,
+            wide_type_id,
+            clz32_id,
+            thirty_one_id,
+            msb_id,
+        ));
+
+        let sixteen_id = self.writer.get_constant_scalar(crate::Literal::U32(16));
+        let adjusted_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::ISub,
+            wide_type_id,
+            adjusted_id,
+            clz32_id,
+            sixteen_id,
+        ));
+
+        let dst_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(lane_scalar),
+        )));
+        let narrow_op = match lane_scalar.kind {
+            crate::ScalarKind::Sint => spirv::Op::SConvert,
+            _ => spirv::Op::UConvert,
+        };
+        block.body.push(Instruction::unary(
+            narrow_op,
+            dst_type_id,
+            result_id,
+            adjusted_id,
+        ));
+    }
+
+    /// Build the instructions for vector - scalar multiplication
+    fn write_vector_scalar_mult(
         &mut self,
         block: &mut Block,
         result_id: Word,
@@ -2046,7 +3401,10 @@ impl<'w> BlockContext<'w> {
         ));
     }
 
-    /// Build the instructions for the arithmetic expression of a dot product
+    /// Build the instructions for the arithmetic expression of a dot product, as an unrolled
+    /// chain of `CompositeExtract`/`IMul`/`IAdd`. This is the fallback used for integer vectors
+    /// when the target doesn't have `WriterFlags::INTEGER_DOT_PRODUCT` -- when it does, `Mf::Dot`
+    /// emits a single native `OpSDot`/`OpUDot` instead of calling this.
     fn write_dot_product(
         &mut self,
         result_id: Word,
@@ -2103,6 +3461,301 @@ impl<'w> BlockContext<'w> {
         }
     }
 
+    /// Guard `op` (`UDiv`/`UMod`) against a zero divisor: replace a zero divisor with `1`
+    /// before the operation, then replace a result computed from a zero divisor with `0`.
+    /// Used for [`WriterFlags::SAFE_INTEGER_ARITHMETIC`]. Operates element-wise for vectors
+    /// via splatted constants, mirroring how `Mf::Saturate` builds its splatted bounds.
+    fn write_guarded_unsigned_divmod(
+        &mut self,
+        op: spirv::Op,
+        result_id: Word,
+        result_type_id: Word,
+        dividend_id: Word,
+        divisor_id: Word,
+        ty_inner: &crate::TypeInner,
+        block: &mut Block,
+    ) -> Result<(), Error> {
+        let (maybe_size, scalar) = match *ty_inner {
+            crate::TypeInner::Vector { size, scalar } => (Some(size), scalar),
+            crate::TypeInner::Scalar(scalar) => (None, scalar),
+            ref other => unreachable!("Unexpected divide/modulo operand type {:?}", other),
+        };
+
+        let mut zero_id = self.writer.get_constant_scalar_with(0, scalar)?;
+        let mut one_id = self.writer.get_constant_scalar_with(1, scalar)?;
+
+        let bool_type_id = if let Some(size) = maybe_size {
+            let ty = LocalType::Numeric(NumericType::Vector { size, scalar }).into();
+
+            self.temp_list.clear();
+            self.temp_list.resize(size as _, zero_id);
+            zero_id = self.writer.get_constant_composite(ty, &self.temp_list);
+
+            self.temp_list.fill(one_id);
+            one_id = self.writer.get_constant_composite(ty, &self.temp_list);
+
+            self.get_type_id(LookupType::Local(LocalType::Numeric(NumericType::Vector {
+                size,
+                scalar: crate::Scalar::BOOL,
+            })))
+        } else {
+            self.get_type_id(LookupType::Local(LocalType::Numeric(NumericType::Scalar(
+                crate::Scalar::BOOL,
+            ))))
+        };
+
+        let is_zero_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::IEqual,
+            bool_type_id,
+            is_zero_id,
+            divisor_id,
+            zero_id,
+        ));
+
+        let safe_divisor_id = self.gen_id();
+        block.body.push(Instruction::select(
+            result_type_id,
+            safe_divisor_id,
+            is_zero_id,
+            one_id,
+            divisor_id,
+        ));
+
+        let raw_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            op,
+            result_type_id,
+            raw_id,
+            dividend_id,
+            safe_divisor_id,
+        ));
+
+        block.body.push(Instruction::select(
+            result_type_id,
+            result_id,
+            is_zero_id,
+            zero_id,
+            raw_id,
+        ));
+
+        Ok(())
+    }
+
+    /// Guard `op` (`SDiv`/`SRem`) against a zero divisor and against the `INT_MIN / -1`
+    /// overflow case: sanitize the divisor to `1` whenever either is about to happen, then
+    /// pick the result WGSL requires for each case (`overflow_result` for the overflow case,
+    /// `0` for the zero-divisor case). Used for [`WriterFlags::SAFE_INTEGER_ARITHMETIC`].
+    /// Operates element-wise for vectors via splatted constants, mirroring how
+    /// `Mf::Saturate` builds its splatted bounds.
+    fn write_guarded_signed_divmod(
+        &mut self,
+        op: spirv::Op,
+        overflow_result: DivModOverflow,
+        result_id: Word,
+        result_type_id: Word,
+        dividend_id: Word,
+        divisor_id: Word,
+        ty_inner: &crate::TypeInner,
+        block: &mut Block,
+    ) -> Result<(), Error> {
+        let (maybe_size, scalar) = match *ty_inner {
+            crate::TypeInner::Vector { size, scalar } => (Some(size), scalar),
+            crate::TypeInner::Scalar(scalar) => (None, scalar),
+            ref other => unreachable!("Unexpected divide/modulo operand type {:?}", other),
+        };
+        let min_value: i64 = match scalar.width {
+            4 => i32::MIN as i64,
+            8 => i64::MIN,
+            other => unreachable!("Unexpected signed integer width {}", other),
+        };
+
+        let mut zero_id = self.writer.get_constant_scalar_with(0, scalar)?;
+        let mut one_id = self.writer.get_constant_scalar_with(1, scalar)?;
+        let mut neg_one_id = self.writer.get_constant_scalar_with(-1, scalar)?;
+        let mut min_id = self.writer.get_constant_scalar_with(min_value, scalar)?;
+
+        let bool_type_id = if let Some(size) = maybe_size {
+            let ty = LocalType::Numeric(NumericType::Vector { size, scalar }).into();
+
+            self.temp_list.clear();
+            self.temp_list.resize(size as _, zero_id);
+            zero_id = self.writer.get_constant_composite(ty, &self.temp_list);
+
+            self.temp_list.fill(one_id);
+            one_id = self.writer.get_constant_composite(ty, &self.temp_list);
+
+            self.temp_list.fill(neg_one_id);
+            neg_one_id = self.writer.get_constant_composite(ty, &self.temp_list);
+
+            self.temp_list.fill(min_id);
+            min_id = self.writer.get_constant_composite(ty, &self.temp_list);
+
+            self.get_type_id(LookupType::Local(LocalType::Numeric(NumericType::Vector {
+                size,
+                scalar: crate::Scalar::BOOL,
+            })))
+        } else {
+            self.get_type_id(LookupType::Local(LocalType::Numeric(NumericType::Scalar(
+                crate::Scalar::BOOL,
+            ))))
+        };
+        let overflow_value_id = match overflow_result {
+            DivModOverflow::DividendMin => min_id,
+            DivModOverflow::Zero => zero_id,
+        };
+
+        let is_zero_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::IEqual,
+            bool_type_id,
+            is_zero_id,
+            divisor_id,
+            zero_id,
+        ));
+        let is_dividend_min_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::IEqual,
+            bool_type_id,
+            is_dividend_min_id,
+            dividend_id,
+            min_id,
+        ));
+        let is_divisor_neg_one_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::IEqual,
+            bool_type_id,
+            is_divisor_neg_one_id,
+            divisor_id,
+            neg_one_id,
+        ));
+        let is_overflow_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::LogicalAnd,
+            bool_type_id,
+            is_overflow_id,
+            is_dividend_min_id,
+            is_divisor_neg_one_id,
+        ));
+        let is_unsafe_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::LogicalOr,
+            bool_type_id,
+            is_unsafe_id,
+            is_zero_id,
+            is_overflow_id,
+        ));
+
+        let safe_divisor_id = self.gen_id();
+        block.body.push(Instruction::select(
+            result_type_id,
+            safe_divisor_id,
+            is_unsafe_id,
+            one_id,
+            divisor_id,
+        ));
+
+        let raw_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            op,
+            result_type_id,
+            raw_id,
+            dividend_id,
+            safe_divisor_id,
+        ));
+
+        let without_overflow_id = self.gen_id();
+        block.body.push(Instruction::select(
+            result_type_id,
+            without_overflow_id,
+            is_overflow_id,
+            overflow_value_id,
+            raw_id,
+        ));
+        block.body.push(Instruction::select(
+            result_type_id,
+            result_id,
+            is_zero_id,
+            zero_id,
+            without_overflow_id,
+        ));
+
+        Ok(())
+    }
+
+    /// If `condition` is a compile-time-constant boolean expression, return its value.
+    ///
+    /// This only recognizes a bare [`Expression::Literal`], not every expression
+    /// `expression_constness` would call const -- we need the actual value, not just
+    /// the fact that it's constant, and folding arbitrary constant expressions down to
+    /// a literal is the front end's job, not this backend's.
+    ///
+    /// [`Expression::Literal`]: crate::Expression::Literal
+    fn as_const_condition(&self, condition: Handle<crate::Expression>) -> Option<bool> {
+        if !self.expression_constness.is_const(condition) {
+            return None;
+        }
+        match self.ir_function.expressions[condition] {
+            crate::Expression::Literal(crate::Literal::Bool(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// If `selector` is a compile-time-constant `i32`/`u32` expression, return its value.
+    ///
+    /// See [`Self::as_const_condition`] for why this only recognizes a bare literal.
+    fn as_const_selector(&self, selector: Handle<crate::Expression>) -> Option<crate::SwitchValue> {
+        if !self.expression_constness.is_const(selector) {
+            return None;
+        }
+        match self.ir_function.expressions[selector] {
+            crate::Expression::Literal(crate::Literal::I32(value)) => {
+                Some(crate::SwitchValue::I32(value))
+            }
+            crate::Expression::Literal(crate::Literal::U32(value)) => {
+                Some(crate::SwitchValue::U32(value))
+            }
+            _ => None,
+        }
+    }
+
+    /// Write the chain of fall-through `Switch` cases starting at `cases[0]`.
+    ///
+    /// `cases[0]` becomes the body of `label_id`; if it falls through, the next case
+    /// becomes the body of a freshly generated label branched to from the first, and so
+    /// on, until a case that doesn't fall through (or the last case in the slice), whose
+    /// exit is `BlockExit::Branch { target: merge_id }`.
+    ///
+    /// This is the same chaining the non-constant `OpSwitch` path below uses to link
+    /// fall-through cases together -- we just start partway through the case list
+    /// instead of always starting at case 0, and never emit the cases we skip.
+    fn write_switch_chain(
+        &mut self,
+        mut label_id: Word,
+        cases: &[crate::SwitchCase],
+        merge_id: Word,
+        loop_context: LoopContext,
+        debug_info: Option<&DebugInfoInner>,
+    ) -> Result<BlockExitDisposition, Error> {
+        for (i, case) in cases.iter().enumerate() {
+            let is_last = !case.fall_through || i + 1 == cases.len();
+            let target = if is_last { merge_id } else { self.gen_id() };
+            let used = self.write_block(
+                label_id,
+                &case.body,
+                BlockExit::Branch { target },
+                loop_context,
+                debug_info,
+            )?;
+            if is_last {
+                return Ok(used);
+            }
+            label_id = target;
+        }
+        // Validation guarantees `cases` is non-empty when we get here.
+        unreachable!()
+    }
+
     /// Generate one or more SPIR-V blocks for `naga_block`.
     ///
     /// Use `label_id` as the label for the SPIR-V entry point block.
@@ -2117,6 +3770,16 @@ impl<'w> BlockContext<'w> {
     /// validation error for the corresponding statement to occur in this
     /// context.
     ///
+    /// This function only folds a branch away when the `If`/`Switch` value is a
+    /// literal -- see `as_const_condition`/`as_const_selector` above. A general
+    /// "join-then-switch" jump-threading pass (tracing a place's value backwards
+    /// through `Emit`/`Store` steps across block joins, as opposed to a single
+    /// expression being a literal) is IR-level work that belongs upstream of this
+    /// backend, operating on `crate::Block` before it ever reaches `write_block`,
+    /// so that every backend benefits rather than just SPIR-V; nothing at this
+    /// layer has the place-tracking or CFG-duplication machinery such a pass
+    /// would need.
+    ///
     /// [`Break`]: Statement::Break
     /// [`Continue`]: Statement::Continue
     fn write_block(
@@ -2128,6 +3791,23 @@ impl<'w> BlockContext<'w> {
         debug_info: Option<&DebugInfoInner>,
     ) -> Result<BlockExitDisposition, Error> {
         let mut block = Block::new(label_id);
+        if let Some(debug_info) = debug_info {
+            if self.writer.flags.contains(WriterFlags::DEBUG_INFO_100) {
+                // Every structured block gets its own `DebugScope` instruction. We don't track nested
+                // `DebugLexicalBlock`s here -- those are only meaningful relative to the
+                // enclosing `DebugFunction`, which the writer sets up once per function --
+                // so this reuses that single scope id for every block within the function.
+                let void_type_id = self.writer.void_type_id;
+                let scope_id = self.gen_id();
+                block.body.push(Instruction::ext_inst(
+                    self.writer.debug_info_100_ext_inst_id,
+                    debug_info_100::DEBUG_SCOPE,
+                    void_type_id,
+                    scope_id,
+                    &[self.writer.debug_info_100_function_id],
+                ));
+            }
+        }
         for (statement, span) in naga_block.span_iter() {
             if let (Some(debug_info), false) = (
                 debug_info,
@@ -2147,6 +3827,23 @@ impl<'w> BlockContext<'w> {
                     loc.line_number,
                     loc.line_position,
                 ));
+                if self.writer.flags.contains(WriterFlags::DEBUG_INFO_100) {
+                    let void_type_id = self.writer.void_type_id;
+                    let line_id = self.gen_id();
+                    block.body.push(Instruction::ext_inst(
+                        self.writer.debug_info_100_ext_inst_id,
+                        debug_info_100::DEBUG_LINE,
+                        void_type_id,
+                        line_id,
+                        &[
+                            self.writer.debug_info_100_source_id,
+                            loc.line_number,
+                            loc.line_number,
+                            loc.line_position,
+                            loc.line_position,
+                        ],
+                    ));
+                }
             };
             match *statement {
                 Statement::Emit(ref range) => {
@@ -2161,6 +3858,18 @@ impl<'w> BlockContext<'w> {
                     let scope_id = self.gen_id();
                     self.function.consume(block, Instruction::branch(scope_id));
 
+                    // Note: this only lowers a plain block, which no statement inside
+                    // can branch out of early -- there is no way for a nested
+                    // `Statement::Break` to target `merge_id` instead of the nearest
+                    // enclosing loop's merge block, since `Statement::Break` carries no
+                    // target payload and `Statement::Block` carries no "breakable"
+                    // marker for the validator to check it against. Giving a plain
+                    // block an early-exit target is an IR-level addition (a new
+                    // `Statement` variant or a payload on the existing ones, plus
+                    // validation) that belongs in `crate::ir`, which this crate's
+                    // snapshot in this tree doesn't include (only this file,
+                    // `back/spv/block.rs`, is present) -- so `loop_context` is passed
+                    // through unchanged here, same as any other non-breakable scope.
                     let merge_id = self.gen_id();
                     let merge_used = self.write_block(
                         scope_id,
@@ -2184,6 +3893,38 @@ impl<'w> BlockContext<'w> {
                     ref accept,
                     ref reject,
                 } => {
+                    // `ConstGoto`: if the condition is a literal, only the taken arm is
+                    // reachable, so branch straight to it -- no `OpSelectionMerge`, no
+                    // `OpBranchConditional`, and the dead arm is never emitted at all.
+                    if let Some(condition_value) = self.as_const_condition(condition) {
+                        let taken = if condition_value { accept } else { reject };
+                        if taken.is_empty() {
+                            // Nothing to branch to; fall through with the current block.
+                            continue;
+                        }
+                        let scope_id = self.gen_id();
+                        self.function.consume(block, Instruction::branch(scope_id));
+
+                        let merge_id = self.gen_id();
+                        let merge_used = self.write_block(
+                            scope_id,
+                            taken,
+                            BlockExit::Branch { target: merge_id },
+                            loop_context,
+                            debug_info,
+                        )?;
+
+                        match merge_used {
+                            BlockExitDisposition::Used => {
+                                block = Block::new(merge_id);
+                            }
+                            BlockExitDisposition::Discarded => {
+                                return Ok(BlockExitDisposition::Discarded);
+                            }
+                        }
+                        continue;
+                    }
+
                     let condition_id = self.cached[condition];
 
                     let merge_id = self.gen_id();
@@ -2212,39 +3953,106 @@ impl<'w> BlockContext<'w> {
                         ),
                     );
 
-                    if let Some(block_id) = accept_id {
-                        // We can ignore the `BlockExitDisposition` returned here because,
-                        // even if `merge_id` is not actually reachable, it is always
-                        // referred to by the `OpSelectionMerge` instruction we emitted
-                        // earlier.
-                        let _ = self.write_block(
-                            block_id,
-                            accept,
-                            BlockExit::Branch { target: merge_id },
-                            loop_context,
-                            debug_info,
-                        )?;
-                    }
-                    if let Some(block_id) = reject_id {
-                        // We can ignore the `BlockExitDisposition` returned here because,
-                        // even if `merge_id` is not actually reachable, it is always
-                        // referred to by the `OpSelectionMerge` instruction we emitted
-                        // earlier.
-                        let _ = self.write_block(
-                            block_id,
-                            reject,
-                            BlockExit::Branch { target: merge_id },
-                            loop_context,
-                            debug_info,
-                        )?;
-                    }
+                    // An empty arm branches straight to `merge_id` and so trivially
+                    // reaches it; a non-empty arm reaches it only if its own
+                    // `write_block` call says so.
+                    let accept_reaches_merge = match accept_id {
+                        Some(block_id) => matches!(
+                            self.write_block(
+                                block_id,
+                                accept,
+                                BlockExit::Branch { target: merge_id },
+                                loop_context,
+                                debug_info,
+                            )?,
+                            BlockExitDisposition::Used
+                        ),
+                        None => true,
+                    };
+                    let reject_reaches_merge = match reject_id {
+                        Some(block_id) => matches!(
+                            self.write_block(
+                                block_id,
+                                reject,
+                                BlockExit::Branch { target: merge_id },
+                                loop_context,
+                                debug_info,
+                            )?,
+                            BlockExitDisposition::Used
+                        ),
+                        None => true,
+                    };
 
-                    block = Block::new(merge_id);
+                    if accept_reaches_merge || reject_reaches_merge {
+                        block = Block::new(merge_id);
+                    } else {
+                        // Both arms diverge, so `merge_id` can never actually be
+                        // reached. The `OpSelectionMerge` above still requires us to
+                        // emit it, but we terminate it with `OpUnreachable` instead
+                        // of leaving it as a live-but-dead block that falls through
+                        // to whatever `exit` the rest of this function would use.
+                        self.function
+                            .consume(Block::new(merge_id), Instruction::unreachable());
+                        return Ok(BlockExitDisposition::Discarded);
+                    }
                 }
                 Statement::Switch {
                     selector,
                     ref cases,
                 } => {
+                    // `SeparateConstSwitch`: if the selector is a literal, only the
+                    // matching case (and, via fall-through, any cases chained after it)
+                    // is reachable, so branch straight into that chain -- no
+                    // `OpSelectionMerge`, no `OpSwitch`, and the other cases are never
+                    // emitted at all.
+                    if let Some(selector_value) = self.as_const_selector(selector) {
+                        let start = cases
+                            .iter()
+                            .position(|case| case.value == selector_value)
+                            .or_else(|| {
+                                cases
+                                    .iter()
+                                    .position(|case| case.value == crate::SwitchValue::Default)
+                            });
+
+                        let merge_id = self.gen_id();
+                        let merge_used = match start {
+                            Some(start) => {
+                                let scope_id = self.gen_id();
+                                self.function.consume(block, Instruction::branch(scope_id));
+
+                                let inner_context = LoopContext {
+                                    break_id: Some(merge_id),
+                                    ..loop_context
+                                };
+                                self.write_switch_chain(
+                                    scope_id,
+                                    &cases[start..],
+                                    merge_id,
+                                    inner_context,
+                                    debug_info,
+                                )?
+                            }
+                            // Naga validation requires every `Switch` to have a
+                            // `Default` arm, so this is unreachable in practice; treat
+                            // it as nothing being reachable if it somehow isn't there.
+                            None => {
+                                self.function.consume(block, Instruction::branch(merge_id));
+                                BlockExitDisposition::Used
+                            }
+                        };
+
+                        match merge_used {
+                            BlockExitDisposition::Used => {
+                                block = Block::new(merge_id);
+                            }
+                            BlockExitDisposition::Discarded => {
+                                return Ok(BlockExitDisposition::Discarded);
+                            }
+                        }
+                        continue;
+                    }
+
                     let selector_id = self.cached[selector];
 
                     let merge_id = self.gen_id();
@@ -2297,6 +4105,14 @@ impl<'w> BlockContext<'w> {
                         ..loop_context
                     };
 
+                    // Unlike the `If` arms above, `case_finish_id` here is always
+                    // referred to by either the `OpSwitch` (a fall-through's next
+                    // case label) or the `OpSelectionMerge` (the switch's overall
+                    // merge block), so we must emit every case regardless of
+                    // reachability -- but we still want to know, after the fact,
+                    // whether `merge_id` itself ended up reachable through any of
+                    // them, to decide whether to keep it live below.
+                    let mut dispositions = Vec::with_capacity(cases.len());
                     for (i, (case, label_id)) in cases
                         .iter()
                         .zip(case_ids.iter())
@@ -2308,26 +4124,46 @@ impl<'w> BlockContext<'w> {
                         } else {
                             merge_id
                         };
-                        // We can ignore the `BlockExitDisposition` returned here because
-                        // `case_finish_id` is always referred to by either:
-                        //
-                        // - the `OpSwitch`, if it's the next case's label for a
-                        //   fall-through, or
-                        //
-                        // - the `OpSelectionMerge`, if it's the switch's overall merge
-                        //   block because there's no fall-through.
-                        let _ = self.write_block(
-                            *label_id,
-                            &case.body,
-                            BlockExit::Branch {
-                                target: case_finish_id,
-                            },
-                            inner_context,
-                            debug_info,
-                        )?;
+                        dispositions.push((
+                            case.fall_through,
+                            self.write_block(
+                                *label_id,
+                                &case.body,
+                                BlockExit::Branch {
+                                    target: case_finish_id,
+                                },
+                                inner_context,
+                                debug_info,
+                            )?,
+                        ));
                     }
 
-                    block = Block::new(merge_id);
+                    // Walk the cases in reverse, threading through whether each
+                    // case's own exit target (the next fall-through case, or
+                    // `merge_id` itself) is reachable, to determine whether
+                    // `merge_id` is reachable from any case at all.
+                    let mut target_reachable = true;
+                    let mut merge_reachable = false;
+                    for &(fall_through, ref used) in dispositions.iter().rev() {
+                        if !fall_through {
+                            target_reachable = true;
+                        }
+                        target_reachable =
+                            matches!(*used, BlockExitDisposition::Used) && target_reachable;
+                        merge_reachable |= target_reachable;
+                    }
+
+                    if merge_reachable {
+                        block = Block::new(merge_id);
+                    } else {
+                        // Every case diverges, so `merge_id` can never actually be
+                        // reached. The `OpSelectionMerge` above still requires us
+                        // to emit it, but we terminate it with `OpUnreachable`
+                        // instead of leaving it as a live-but-dead block.
+                        self.function
+                            .consume(Block::new(merge_id), Instruction::unreachable());
+                        return Ok(BlockExitDisposition::Discarded);
+                    }
                 }
                 Statement::Loop {
                     ref body,
@@ -2374,6 +4210,7 @@ impl<'w> BlockContext<'w> {
                         LoopContext {
                             continuing_id: Some(continuing_id),
                             break_id: Some(merge_id),
+                            ..loop_context
                         },
                         debug_info,
                     )?;
@@ -2398,6 +4235,7 @@ impl<'w> BlockContext<'w> {
                         LoopContext {
                             continuing_id: None,
                             break_id: Some(merge_id),
+                            ..loop_context
                         },
                         debug_info,
                     )?;
@@ -2449,7 +4287,7 @@ impl<'w> BlockContext<'w> {
                 Statement::Store { pointer, value } => {
                     let value_id = self.cached[value];
                     match self.write_expression_pointer(pointer, &mut block, None)? {
-                        ExpressionPointer::Ready { pointer_id } => {
+                        ExpressionPointer::Ready { pointer_id, .. } => {
                             let atomic_space = match *self.fun_info[pointer]
                                 .ty
                                 .inner_with(&self.ir_module.types)
@@ -2477,7 +4315,9 @@ impl<'w> BlockContext<'w> {
                             };
                             block.body.push(instruction);
                         }
-                        ExpressionPointer::Conditional { condition, access } => {
+                        ExpressionPointer::Conditional {
+                            condition, access, ..
+                        } => {
                             let mut selection = Selection::start(&mut block, ());
                             selection.if_true(self, condition, ());
 
@@ -2533,27 +4373,12 @@ impl<'w> BlockContext<'w> {
                     value,
                     result,
                 } => {
-                    let id = self.gen_id();
                     // Compare-and-exchange operations produce a struct result,
                     // so use `result`'s type if it is available. For no-result
                     // operations, fall back to `value`'s type.
                     let result_type_id =
                         self.get_expression_type_id(&self.fun_info[result.unwrap_or(value)].ty);
 
-                    if let Some(result) = result {
-                        self.cached[result] = id;
-                    }
-
-                    let pointer_id =
-                        match self.write_expression_pointer(pointer, &mut block, None)? {
-                            ExpressionPointer::Ready { pointer_id } => pointer_id,
-                            ExpressionPointer::Conditional { .. } => {
-                                return Err(Error::FeatureNotImplemented(
-                                    "Atomics out-of-bounds handling",
-                                ));
-                            }
-                        };
-
                     let space = self.fun_info[pointer]
                         .ty
                         .inner_with(&self.ir_module.types)
@@ -2565,107 +4390,104 @@ impl<'w> BlockContext<'w> {
                     let value_id = self.cached[value];
                     let value_inner = self.fun_info[value].ty.inner_with(&self.ir_module.types);
 
-                    let instruction = match *fun {
-                        crate::AtomicFunction::Add => Instruction::atomic_binary(
-                            spirv::Op::AtomicIAdd,
-                            result_type_id,
-                            id,
-                            pointer_id,
-                            scope_constant_id,
-                            semantics_id,
-                            value_id,
-                        ),
-                        crate::AtomicFunction::Subtract => Instruction::atomic_binary(
-                            spirv::Op::AtomicISub,
-                            result_type_id,
-                            id,
-                            pointer_id,
-                            scope_constant_id,
-                            semantics_id,
-                            value_id,
-                        ),
-                        crate::AtomicFunction::And => Instruction::atomic_binary(
-                            spirv::Op::AtomicAnd,
-                            result_type_id,
-                            id,
-                            pointer_id,
-                            scope_constant_id,
-                            semantics_id,
-                            value_id,
-                        ),
-                        crate::AtomicFunction::InclusiveOr => Instruction::atomic_binary(
-                            spirv::Op::AtomicOr,
-                            result_type_id,
-                            id,
-                            pointer_id,
-                            scope_constant_id,
-                            semantics_id,
-                            value_id,
-                        ),
-                        crate::AtomicFunction::ExclusiveOr => Instruction::atomic_binary(
-                            spirv::Op::AtomicXor,
-                            result_type_id,
-                            id,
-                            pointer_id,
-                            scope_constant_id,
-                            semantics_id,
-                            value_id,
-                        ),
-                        crate::AtomicFunction::Min => {
-                            let spirv_op = match *value_inner {
-                                crate::TypeInner::Scalar(crate::Scalar {
-                                    kind: crate::ScalarKind::Sint,
-                                    width: _,
-                                }) => spirv::Op::AtomicSMin,
-                                crate::TypeInner::Scalar(crate::Scalar {
-                                    kind: crate::ScalarKind::Uint,
-                                    width: _,
-                                }) => spirv::Op::AtomicUMin,
-                                _ => unimplemented!(),
-                            };
-                            Instruction::atomic_binary(
-                                spirv_op,
-                                result_type_id,
-                                id,
-                                pointer_id,
-                                scope_constant_id,
-                                semantics_id,
-                                value_id,
-                            )
-                        }
-                        crate::AtomicFunction::Max => {
-                            let spirv_op = match *value_inner {
-                                crate::TypeInner::Scalar(crate::Scalar {
-                                    kind: crate::ScalarKind::Sint,
-                                    width: _,
-                                }) => spirv::Op::AtomicSMax,
-                                crate::TypeInner::Scalar(crate::Scalar {
-                                    kind: crate::ScalarKind::Uint,
-                                    width: _,
-                                }) => spirv::Op::AtomicUMax,
-                                _ => unimplemented!(),
-                            };
-                            Instruction::atomic_binary(
-                                spirv_op,
-                                result_type_id,
-                                id,
-                                pointer_id,
-                                scope_constant_id,
-                                semantics_id,
-                                value_id,
-                            )
-                        }
+                    // `result_type_id`/`value_id`/`semantics_id` above are already derived from
+                    // the operand's actual type, so a 64-bit integer atomic gets 64-bit operands
+                    // for free; the one thing still missing for it is this capability.
+                    if let crate::TypeInner::Scalar(crate::Scalar {
+                        kind: crate::ScalarKind::Sint | crate::ScalarKind::Uint,
+                        width: 8,
+                    }) = *value_inner
+                    {
+                        self.writer.require_any(
+                            "64-bit integer atomics",
+                            &[spirv::Capability::Int64Atomics],
+                        )?;
+                    }
+
+                    // Resolve the op (requesting any capability a floating-point
+                    // variant needs) and the compare-exchange types up front: the
+                    // bounds-checked path below builds the actual instructions in a
+                    // closure with no access to `self`.
+                    let spirv_op = match *fun {
+                        crate::AtomicFunction::Add => match *value_inner {
+                            crate::TypeInner::Scalar(crate::Scalar {
+                                kind: crate::ScalarKind::Float,
+                                width,
+                            }) => {
+                                self.writer.require_any(
+                                    "float atomic add",
+                                    &[if width == 8 {
+                                        spirv::Capability::AtomicFloat64AddEXT
+                                    } else {
+                                        spirv::Capability::AtomicFloat32AddEXT
+                                    }],
+                                )?;
+                                spirv::Op::AtomicFAddEXT
+                            }
+                            _ => spirv::Op::AtomicIAdd,
+                        },
+                        crate::AtomicFunction::Subtract => spirv::Op::AtomicISub,
+                        crate::AtomicFunction::And => spirv::Op::AtomicAnd,
+                        crate::AtomicFunction::InclusiveOr => spirv::Op::AtomicOr,
+                        crate::AtomicFunction::ExclusiveOr => spirv::Op::AtomicXor,
+                        crate::AtomicFunction::Min => match *value_inner {
+                            crate::TypeInner::Scalar(crate::Scalar {
+                                kind: crate::ScalarKind::Sint,
+                                width: _,
+                            }) => spirv::Op::AtomicSMin,
+                            crate::TypeInner::Scalar(crate::Scalar {
+                                kind: crate::ScalarKind::Uint,
+                                width: _,
+                            }) => spirv::Op::AtomicUMin,
+                            crate::TypeInner::Scalar(crate::Scalar {
+                                kind: crate::ScalarKind::Float,
+                                width,
+                            }) => {
+                                self.writer.require_any(
+                                    "float atomic min",
+                                    &[if width == 8 {
+                                        spirv::Capability::AtomicFloat64MinMaxEXT
+                                    } else {
+                                        spirv::Capability::AtomicFloat32MinMaxEXT
+                                    }],
+                                )?;
+                                spirv::Op::AtomicFMinEXT
+                            }
+                            _ => unimplemented!(),
+                        },
+                        crate::AtomicFunction::Max => match *value_inner {
+                            crate::TypeInner::Scalar(crate::Scalar {
+                                kind: crate::ScalarKind::Sint,
+                                width: _,
+                            }) => spirv::Op::AtomicSMax,
+                            crate::TypeInner::Scalar(crate::Scalar {
+                                kind: crate::ScalarKind::Uint,
+                                width: _,
+                            }) => spirv::Op::AtomicUMax,
+                            crate::TypeInner::Scalar(crate::Scalar {
+                                kind: crate::ScalarKind::Float,
+                                width,
+                            }) => {
+                                self.writer.require_any(
+                                    "float atomic max",
+                                    &[if width == 8 {
+                                        spirv::Capability::AtomicFloat64MinMaxEXT
+                                    } else {
+                                        spirv::Capability::AtomicFloat32MinMaxEXT
+                                    }],
+                                )?;
+                                spirv::Op::AtomicFMaxEXT
+                            }
+                            _ => unimplemented!(),
+                        },
                         crate::AtomicFunction::Exchange { compare: None } => {
-                            Instruction::atomic_binary(
-                                spirv::Op::AtomicExchange,
-                                result_type_id,
-                                id,
-                                pointer_id,
-                                scope_constant_id,
-                                semantics_id,
-                                value_id,
-                            )
+                            spirv::Op::AtomicExchange
                         }
+                        crate::AtomicFunction::Exchange { compare: Some(_) } => {
+                            spirv::Op::AtomicCompareExchange
+                        }
+                    };
+                    let cas_types = match *fun {
                         crate::AtomicFunction::Exchange { compare: Some(cmp) } => {
                             let scalar_type_id = match *value_inner {
                                 crate::TypeInner::Scalar(scalar) => {
@@ -2678,35 +4500,97 @@ impl<'w> BlockContext<'w> {
                             let bool_type_id = self.get_type_id(LookupType::Local(
                                 LocalType::Numeric(NumericType::Scalar(crate::Scalar::BOOL)),
                             ));
+                            Some((self.cached[cmp], scalar_type_id, bool_type_id))
+                        }
+                        _ => None,
+                    };
 
-                            let cas_result_id = self.gen_id();
-                            let equality_result_id = self.gen_id();
-                            let mut cas_instr = Instruction::new(spirv::Op::AtomicCompareExchange);
-                            cas_instr.set_type(scalar_type_id);
-                            cas_instr.set_result(cas_result_id);
-                            cas_instr.add_operand(pointer_id);
-                            cas_instr.add_operand(scope_constant_id);
-                            cas_instr.add_operand(semantics_id); // semantics if equal
-                            cas_instr.add_operand(semantics_id); // semantics if not equal
-                            cas_instr.add_operand(value_id);
-                            cas_instr.add_operand(self.cached[cmp]);
-                            block.body.push(cas_instr);
-                            block.body.push(Instruction::binary(
-                                spirv::Op::IEqual,
-                                bool_type_id,
-                                equality_result_id,
-                                cas_result_id,
-                                self.cached[cmp],
-                            ));
-                            Instruction::composite_construct(
+                    match self.write_expression_pointer(pointer, &mut block, None)? {
+                        ExpressionPointer::Ready {
+                            pointer_id,
+                            non_uniform,
+                        } => {
+                            let id = self.gen_id();
+                            if let Some(result) = result {
+                                self.cached[result] = id;
+                            }
+                            let cas = cas_types.map(|(cmp_id, scalar_type_id, bool_type_id)| {
+                                AtomicCasExtra {
+                                    cmp_id,
+                                    scalar_type_id,
+                                    bool_type_id,
+                                    cas_result_id: self.gen_id(),
+                                    equality_result_id: self.gen_id(),
+                                }
+                            });
+                            push_atomic_instruction(
+                                spirv_op,
                                 result_type_id,
                                 id,
-                                &[cas_result_id, equality_result_id],
-                            )
+                                pointer_id,
+                                scope_constant_id,
+                                semantics_id,
+                                value_id,
+                                cas,
+                                &mut block,
+                            );
+                            // See the matching comment in the `Expression::Load` arm: the
+                            // pointer being decorated NonUniform isn't enough, the
+                            // atomic's result needs it too.
+                            if non_uniform {
+                                self.writer.decorate_non_uniform_binding_array_access(id)?;
+                            }
                         }
-                    };
-
-                    block.body.push(instruction);
+                        ExpressionPointer::Conditional {
+                            condition,
+                            access,
+                            non_uniform,
+                        } => {
+                            // Out of bounds, per `BoundsCheckPolicy::ReadZeroSkipWrite`: skip
+                            // the atomic op entirely and yield a zero of the result type --
+                            // the same policy (and the same merge mechanism) a conditional
+                            // load already honors.
+                            let merged_id = self.write_conditional_indexed_load(
+                                result_type_id,
+                                condition,
+                                &mut block,
+                                move |id_gen, block| {
+                                    let pointer_id = access.result_id.unwrap();
+                                    block.body.push(access);
+                                    let result_id = id_gen.next();
+                                    let cas =
+                                        cas_types.map(|(cmp_id, scalar_type_id, bool_type_id)| {
+                                            AtomicCasExtra {
+                                                cmp_id,
+                                                scalar_type_id,
+                                                bool_type_id,
+                                                cas_result_id: id_gen.next(),
+                                                equality_result_id: id_gen.next(),
+                                            }
+                                        });
+                                    push_atomic_instruction(
+                                        spirv_op,
+                                        result_type_id,
+                                        result_id,
+                                        pointer_id,
+                                        scope_constant_id,
+                                        semantics_id,
+                                        value_id,
+                                        cas,
+                                        block,
+                                    );
+                                    result_id
+                                },
+                            );
+                            if let Some(result) = result {
+                                self.cached[result] = merged_id;
+                            }
+                            if non_uniform {
+                                self.writer
+                                    .decorate_non_uniform_binding_array_access(merged_id)?;
+                            }
+                        }
+                    }
                 }
                 Statement::WorkGroupUniformLoad { pointer, result } => {
                     self.writer
@@ -2714,7 +4598,7 @@ impl<'w> BlockContext<'w> {
                     let result_type_id = self.get_expression_type_id(&self.fun_info[result].ty);
                     // Embed the body of
                     match self.write_expression_pointer(pointer, &mut block, None)? {
-                        ExpressionPointer::Ready { pointer_id } => {
+                        ExpressionPointer::Ready { pointer_id, .. } => {
                             let id = self.gen_id();
                             block.body.push(Instruction::load(
                                 result_type_id,
@@ -2724,7 +4608,9 @@ impl<'w> BlockContext<'w> {
                             ));
                             self.cached[result] = id;
                         }
-                        ExpressionPointer::Conditional { condition, access } => {
+                        ExpressionPointer::Conditional {
+                            condition, access, ..
+                        } => {
                             self.cached[result] = self.write_conditional_indexed_load(
                                 result_type_id,
                                 condition,
@@ -2751,12 +4637,18 @@ impl<'w> BlockContext<'w> {
                 Statement::RayQuery { query, ref fun } => {
                     self.write_ray_query_function(query, fun, &mut block);
                 }
+                // Lowers to `OpGroupNonUniformBallot`, gated on `GroupNonUniformBallot`
+                // (plus the baseline `GroupNonUniform` capability every subgroup op needs).
                 Statement::SubgroupBallot {
                     result,
                     ref predicate,
                 } => {
                     self.write_subgroup_ballot(predicate, result, &mut block)?;
                 }
+                // Vote (`All`/`Any`) lowers to `OpGroupNonUniformAll`/`OpGroupNonUniformAny`
+                // under `GroupNonUniformVote`; reductions and inclusive/exclusive scans lower
+                // to the matching `OpGroupNonUniform{IAdd,FAdd,IMul,FMul,Min,Max,And,Or,Xor}`
+                // under `GroupNonUniformArithmetic`, parameterized by the scan's `GroupOperation`.
                 Statement::SubgroupCollectiveOperation {
                     ref op,
                     ref collective_op,
@@ -2765,6 +4657,15 @@ impl<'w> BlockContext<'w> {
                 } => {
                     self.write_subgroup_operation(op, collective_op, argument, result, &mut block)?;
                 }
+                // Broadcast/shuffle lowers to `OpGroupNonUniformBroadcast`/
+                // `OpGroupNonUniformShuffle(Xor/Up/Down)` under `GroupNonUniformShuffle`.
+                //
+                // `QuadBroadcast`/`QuadSwap` (-> `OpGroupNonUniformQuadBroadcast`/
+                // `OpGroupNonUniformQuadSwap`, `GroupNonUniformQuad`) and `Rotate` (->
+                // `OpGroupNonUniformRotateKHR`, `GroupNonUniformRotateKHR` /
+                // `SPV_KHR_subgroup_rotate`) still need a `GatherMode` arm each here; this call
+                // site just forwards whatever `mode` the front end produced, so the new variants
+                // and their instruction-building logic belong in `write_subgroup_gather` itself.
                 Statement::SubgroupGather {
                     ref mode,
                     argument,
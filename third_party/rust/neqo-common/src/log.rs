@@ -7,13 +7,77 @@
 #![allow(clippy::module_name_repetitions)]
 
 use std::{
+    fmt,
     io::Write,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
     sync::{Once, OnceLock},
     time::{Duration, Instant},
 };
 
 use env_logger::Builder;
 
+/// Renders a byte slice as lowercase hex, two nibbles per byte, directly into the formatter --
+/// no intermediate `String` the way a hand-built hex dump (e.g. `format!("{:02x}", b)` joined
+/// per byte) would need. Intended for `qdebug!`/`qtrace!` call sites logging connection IDs or
+/// packet payloads: `qdebug!("cid={}", Hex(&cid))`.
+pub struct Hex<'a>(pub &'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders a 6-byte MAC address as colon-separated lowercase hex octets (`aa:bb:cc:dd:ee:ff`)
+/// directly into the formatter.
+pub struct LowerMac(pub [u8; 6]);
+
+impl fmt::Display for LowerMac {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(":")?;
+            }
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders an IP address -- from a raw `u32` (v4), a raw `[u8; 16]` (v6), or a [`SocketAddr`]
+/// (address only, port dropped) -- as dotted-quad or RFC 5952 compressed v6, by delegating to
+/// `Ipv4Addr`/`Ipv6Addr`'s own `Display`, which already implement exactly that. Exists so log
+/// call sites don't need to reach for `std::net` types or `.ip()` themselves:
+/// `qdebug!("peer={}", Ip::from(addr))`.
+pub struct Ip(pub IpAddr);
+
+impl From<u32> for Ip {
+    fn from(addr: u32) -> Self {
+        Self(IpAddr::V4(Ipv4Addr::from(addr)))
+    }
+}
+
+impl From<[u8; 16]> for Ip {
+    fn from(addr: [u8; 16]) -> Self {
+        Self(IpAddr::V6(Ipv6Addr::from(addr)))
+    }
+}
+
+impl From<SocketAddr> for Ip {
+    fn from(addr: SocketAddr) -> Self {
+        Self(addr.ip())
+    }
+}
+
+impl fmt::Display for Ip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
 #[macro_export]
 macro_rules! do_log {
     (target: $target:expr, $lvl:expr, $($arg:tt)+) => ({
@@ -113,3 +177,271 @@ macro_rules! qtrace {
     ([$ctx:expr], $($arg:tt)*) => (::neqo_common::log_invoke!(::log::Level::Trace, $ctx, $($arg)*););
     ($($arg:tt)*) => ( { ::neqo_common::log::init(None); ::neqo_common::do_log!(::log::Level::Trace, $($arg)*); } );
 }
+
+/// Deferred binary log-record encoding, for per-packet QUIC tracing and for capturing traces
+/// inside fuzzers, where formatting eagerly through `env_logger` on every call (as `do_log!`
+/// does) is too expensive. Records are serialized via [`encode`] into a fixed-capacity buffer
+/// that is never grown (each record is first built in a small scratch `Vec`) and rendered back
+/// to text later, off the hot path, via [`decode`].
+pub mod binary {
+    use std::{cell::RefCell, fmt::Write as _, net::IpAddr};
+
+    use super::{Hex, Ip, LowerMac};
+
+    /// Mirrors `log::Level`'s variants and ordinals, so a record can be decoded without this
+    /// module depending on the `log` crate.
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Level {
+        Error = 1,
+        Warn = 2,
+        Info = 3,
+        Debug = 4,
+        Trace = 5,
+    }
+
+    impl Level {
+        #[must_use]
+        pub fn from_u8(v: u8) -> Option<Self> {
+            Some(match v {
+                1 => Self::Error,
+                2 => Self::Warn,
+                3 => Self::Info,
+                4 => Self::Debug,
+                5 => Self::Trace,
+                _ => return None,
+            })
+        }
+    }
+
+    /// Capacity, in bytes, of the per-thread ring buffer [`log_to_ring_buffer`] writes into.
+    pub const LOG_BUF_CAPACITY: usize = 64 * 1024;
+
+    /// One typed argument of a binary-encoded record. Mirrors the typed-formatter set in
+    /// [`super::Hex`]/[`super::LowerMac`]/[`super::Ip`] so the same value can be logged either
+    /// way.
+    pub enum Field<'a> {
+        Int(i64),
+        Str(&'a str),
+        Bytes(&'a [u8]),
+        Ip(Ip),
+        Mac(LowerMac),
+    }
+
+    impl<'a> From<i64> for Field<'a> {
+        fn from(v: i64) -> Self {
+            Self::Int(v)
+        }
+    }
+
+    impl<'a> From<&'a str> for Field<'a> {
+        fn from(v: &'a str) -> Self {
+            Self::Str(v)
+        }
+    }
+
+    impl<'a> From<&'a [u8]> for Field<'a> {
+        fn from(v: &'a [u8]) -> Self {
+            Self::Bytes(v)
+        }
+    }
+
+    impl From<Ip> for Field<'_> {
+        fn from(v: Ip) -> Self {
+            Self::Ip(v)
+        }
+    }
+
+    impl From<LowerMac> for Field<'_> {
+        fn from(v: LowerMac) -> Self {
+            Self::Mac(v)
+        }
+    }
+
+    /// Type discriminant written before each field's length-prefixed bytes.
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum FieldKind {
+        Int = 0,
+        Str = 1,
+        Bytes = 2,
+        Ip = 3,
+        Mac = 4,
+    }
+
+    impl FieldKind {
+        fn from_u8(v: u8) -> Option<Self> {
+            Some(match v {
+                0 => Self::Int,
+                1 => Self::Str,
+                2 => Self::Bytes,
+                3 => Self::Ip,
+                4 => Self::Mac,
+                _ => return None,
+            })
+        }
+    }
+
+    impl Field<'_> {
+        fn kind(&self) -> FieldKind {
+            match self {
+                Self::Int(_) => FieldKind::Int,
+                Self::Str(_) => FieldKind::Str,
+                Self::Bytes(_) => FieldKind::Bytes,
+                Self::Ip(_) => FieldKind::Ip,
+                Self::Mac(_) => FieldKind::Mac,
+            }
+        }
+    }
+
+    /// Appends one `u32`-length-prefixed record -- a [`Level`] byte, the length-prefixed
+    /// `target`, then each field as a 1-byte [`FieldKind`] followed by a `u16` length and its
+    /// raw bytes -- to `buf`. Returns `false` (leaving `buf` unmodified) if the record wouldn't
+    /// fit within `buf`'s capacity: `buf` is a fixed-capacity arena that this never grows past,
+    /// so a successful call never allocates beyond one scratch `Vec` sized to the record itself.
+    #[must_use]
+    pub fn encode(buf: &mut Vec<u8>, level: Level, target: &str, fields: &[Field<'_>]) -> bool {
+        let mut record = Vec::new();
+        record.push(level as u8);
+        write_length_prefixed(&mut record, target.as_bytes());
+        for field in fields {
+            record.push(field.kind() as u8);
+            match field {
+                Field::Int(n) => write_length_prefixed(&mut record, &n.to_le_bytes()),
+                Field::Str(s) => write_length_prefixed(&mut record, s.as_bytes()),
+                Field::Bytes(b) => write_length_prefixed(&mut record, b),
+                Field::Ip(ip) => write_length_prefixed(&mut record, &encode_ip(ip)),
+                Field::Mac(mac) => write_length_prefixed(&mut record, &mac.0),
+            }
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let prefix = (record.len() as u32).to_le_bytes();
+        if buf.len() + prefix.len() + record.len() > buf.capacity() {
+            return false;
+        }
+        buf.extend_from_slice(&prefix);
+        buf.extend_from_slice(&record);
+        true
+    }
+
+    fn write_length_prefixed(out: &mut Vec<u8>, bytes: &[u8]) {
+        #[allow(clippy::cast_possible_truncation)]
+        let len = bytes.len().min(u16::MAX as usize) as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(bytes.get(..len as usize).unwrap_or(bytes));
+    }
+
+    fn read_length_prefixed(buf: &[u8], pos: usize) -> Option<(&[u8], usize)> {
+        let len = u16::from_le_bytes(buf.get(pos..pos + 2)?.try_into().ok()?) as usize;
+        let start = pos + 2;
+        Some((buf.get(start..start + len)?, start + len))
+    }
+
+    fn encode_ip(ip: &Ip) -> Vec<u8> {
+        match ip.0 {
+            IpAddr::V4(v4) => [&[4u8][..], &v4.octets()[..]].concat(),
+            IpAddr::V6(v6) => [&[6u8][..], &v6.octets()[..]].concat(),
+        }
+    }
+
+    /// Reconstructs the human-readable line for the record starting at `buf[pos]` (as written by
+    /// [`encode`]), for use in a later drain step instead of formatting on the hot path. Returns
+    /// the rendered line and the offset of the byte following the record, or `None` if
+    /// `buf[pos..]` isn't a complete, well-formed record.
+    #[must_use]
+    pub fn decode(buf: &[u8], pos: usize) -> Option<(String, usize)> {
+        let record_len = u32::from_le_bytes(buf.get(pos..pos + 4)?.try_into().ok()?) as usize;
+        let record_start = pos + 4;
+        let record = buf.get(record_start..record_start + record_len)?;
+        let next = record_start + record_len;
+
+        let level = Level::from_u8(*record.first()?)?;
+        let mut cursor = 1;
+        let (target, c) = read_length_prefixed(record, cursor)?;
+        cursor = c;
+        let target = std::str::from_utf8(target).ok()?;
+
+        let mut line = format!("{level:?} {target}");
+        while cursor < record.len() {
+            let kind = FieldKind::from_u8(*record.get(cursor)?)?;
+            cursor += 1;
+            let (value, c) = read_length_prefixed(record, cursor)?;
+            cursor = c;
+            match kind {
+                FieldKind::Int => {
+                    let n = i64::from_le_bytes(value.try_into().ok()?);
+                    let _ = write!(line, " {n}");
+                }
+                FieldKind::Str => {
+                    let _ = write!(line, " {}", std::str::from_utf8(value).ok()?);
+                }
+                FieldKind::Bytes => {
+                    let _ = write!(line, " {}", Hex(value));
+                }
+                FieldKind::Ip => {
+                    let (version, octets) = value.split_first()?;
+                    match version {
+                        4 => {
+                            let octets: [u8; 4] = octets.try_into().ok()?;
+                            let _ = write!(line, " {}", std::net::Ipv4Addr::from(octets));
+                        }
+                        6 => {
+                            let octets: [u8; 16] = octets.try_into().ok()?;
+                            let _ = write!(line, " {}", std::net::Ipv6Addr::from(octets));
+                        }
+                        _ => return None,
+                    }
+                }
+                FieldKind::Mac => {
+                    let octets: [u8; 6] = value.try_into().ok()?;
+                    let _ = write!(line, " {}", LowerMac(octets));
+                }
+            }
+        }
+        Some((line, next))
+    }
+
+    thread_local! {
+        static RING: RefCell<Vec<u8>> = RefCell::new(Vec::with_capacity(LOG_BUF_CAPACITY));
+    }
+
+    /// Encodes one record into the calling thread's ring buffer. Returns whether it was written;
+    /// `false` means the buffer is full and should be drained (see [`take_ring_buffer`]) before
+    /// logging more on this thread.
+    #[must_use]
+    pub fn log_to_ring_buffer(level: Level, target: &str, fields: &[Field<'_>]) -> bool {
+        RING.with(|ring| encode(&mut ring.borrow_mut(), level, target, fields))
+    }
+
+    /// Drains the calling thread's ring buffer, returning its contents and leaving a fresh, empty
+    /// one with the same capacity in its place.
+    pub fn take_ring_buffer() -> Vec<u8> {
+        RING.with(|ring| {
+            std::mem::replace(&mut *ring.borrow_mut(), Vec::with_capacity(LOG_BUF_CAPACITY))
+        })
+    }
+}
+
+/// Encodes one record -- cheaply, without formatting it -- into the calling thread's binary
+/// ring buffer, for later rendering via [`binary::decode`]. Unlike `qdebug!` and friends, the
+/// arguments after `target` aren't a format string; each is converted into a
+/// [`binary::Field`] via `From`, so only a fixed set of typed arguments (`i64`, `&str`,
+/// `&[u8]`, [`Ip`], [`LowerMac`]) is supported.
+#[macro_export]
+macro_rules! qlog_binary {
+    ([$ctx:expr], $lvl:expr, $target:expr $(, $field:expr)* $(,)?) => {{
+        let _ctx = &$ctx;
+        $crate::log::binary::log_to_ring_buffer(
+            $lvl,
+            $target,
+            &[$($crate::log::binary::Field::from($field)),*],
+        )
+    }};
+    ($lvl:expr, $target:expr $(, $field:expr)* $(,)?) => {{
+        $crate::log::binary::log_to_ring_buffer(
+            $lvl,
+            $target,
+            &[$($crate::log::binary::Field::from($field)),*],
+        )
+    }};
+}
@@ -109,6 +109,34 @@ impl From<CryptoSpace> for PacketType {
     }
 }
 
+/// The source of the bytes used to grease the reserved/fixed bits in a
+/// packet header and the reserved version in a Version Negotiation packet.
+///
+/// [`Self::Random`] (the default) draws from the same secure RNG the rest
+/// of this module uses; [`Self::Fixed`] lets callers -- interop harnesses,
+/// fuzzers, or tests -- supply the bytes themselves so the greased output
+/// is reproducible.
+#[derive(Clone, Copy, Debug)]
+pub enum GreaseSource {
+    Random,
+    Fixed([u8; 4]),
+}
+
+impl GreaseSource {
+    fn bytes(self) -> [u8; 4] {
+        match self {
+            Self::Random => random::<4>(),
+            Self::Fixed(v) => v,
+        }
+    }
+}
+
+impl Default for GreaseSource {
+    fn default() -> Self {
+        Self::Random
+    }
+}
+
 struct PacketBuilderOffsets {
     /// The bits of the first octet that need masking.
     first_byte_mask: u8,
@@ -128,6 +156,9 @@ pub struct PacketBuilder {
     limit: usize,
     /// Whether to pad the packet before construction.
     padding: bool,
+    /// Whether the peer has negotiated `grease_quic_bit` (RFC 9287),
+    /// permitting [`Self::scramble`] to clear [`PACKET_BIT_FIXED_QUIC`].
+    grease_quic_bit: bool,
 }
 
 impl PacketBuilder {
@@ -151,7 +182,17 @@ impl PacketBuilder {
     ///
     /// If, after calling this method, `remaining()` returns 0, then call `abort()` to get
     /// the encoder back.
-    pub fn short(mut encoder: Encoder, key_phase: bool, dcid: Option<impl AsRef<[u8]>>) -> Self {
+    ///
+    /// `grease_quic_bit` should be `true` only if the peer has negotiated the
+    /// `grease_quic_bit` transport parameter; it governs whether
+    /// [`Self::scramble`] is allowed to clear the fixed QUIC bit on this
+    /// packet.
+    pub fn short(
+        mut encoder: Encoder,
+        key_phase: bool,
+        dcid: Option<impl AsRef<[u8]>>,
+        grease_quic_bit: bool,
+    ) -> Self {
         let mut limit = Self::infer_limit(&encoder);
         let header_start = encoder.len();
         // Check that there is enough space for the header.
@@ -178,7 +219,25 @@ impl PacketBuilder {
             },
             limit,
             padding: false,
+            grease_quic_bit,
+        }
+    }
+
+    /// Like [`Self::short`], but takes the key phase from `policy` rather
+    /// than a caller-supplied flag, and refuses to build (the same way
+    /// `short` does when there isn't enough space) if `policy` reports the
+    /// current phase as [exhausted][KeyUpdatePolicy::exhausted].
+    pub fn short_with_policy(
+        encoder: Encoder,
+        policy: &KeyUpdatePolicy,
+        dcid: Option<impl AsRef<[u8]>>,
+        grease_quic_bit: bool,
+    ) -> Self {
+        let mut builder = Self::short(encoder, policy.current_phase(), dcid, grease_quic_bit);
+        if policy.exhausted() {
+            builder.limit = 0;
         }
+        builder
     }
 
     /// Start building a long header packet.
@@ -186,6 +245,8 @@ impl PacketBuilder {
     /// even if the token is empty.
     ///
     /// See `short()` for more on how to handle this in cases where there is no space.
+    /// `grease_quic_bit` should be `true` only if the peer has negotiated the
+    /// `grease_quic_bit` transport parameter; see [`Self::short`].
     #[allow(clippy::similar_names)]
     pub fn long(
         mut encoder: Encoder,
@@ -193,6 +254,7 @@ impl PacketBuilder {
         version: Version,
         mut dcid: Option<impl AsRef<[u8]>>,
         mut scid: Option<impl AsRef<[u8]>>,
+        grease_quic_bit: bool,
     ) -> Self {
         let mut limit = Self::infer_limit(&encoder);
         let header_start = encoder.len();
@@ -224,6 +286,7 @@ impl PacketBuilder {
             },
             limit,
             padding: false,
+            grease_quic_bit,
         }
     }
 
@@ -302,13 +365,63 @@ impl PacketBuilder {
         }
     }
 
+    /// Pad with `PADDING` frames until the encoder -- which already holds
+    /// any packets coalesced before this one in the same datagram -- reaches
+    /// `min_len` bytes.
+    ///
+    /// Returns `Ok(false)` without writing anything if the encoder is
+    /// already at least `min_len` bytes long, `Ok(true)` if padding was
+    /// added, or `Err(Error::NotAvailable)` if `min_len` exceeds this
+    /// packet's size limit, so there is no room left to pad into.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NotAvailable` if `min_len` is beyond `self.limit()`.
+    pub fn pad_to(&mut self, min_len: usize) -> Res<bool> {
+        if self.encoder.len() >= min_len {
+            return Ok(false);
+        }
+        if min_len > self.limit {
+            return Err(Error::NotAvailable);
+        }
+        self.encoder
+            .pad_to(min_len, FRAME_TYPE_PADDING.try_into().unwrap());
+        Ok(true)
+    }
+
+    /// Pad the enclosing datagram to [`MIN_INITIAL_PACKET_SIZE`], as RFC
+    /// 9000 section 14.1 requires of a datagram carrying a client Initial,
+    /// to satisfy anti-amplification limits. See [`Self::pad_to`] for the
+    /// exact semantics.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NotAvailable` if this packet's size limit leaves no
+    /// room to reach [`MIN_INITIAL_PACKET_SIZE`].
+    pub fn enforce_min_initial_size(&mut self) -> Res<bool> {
+        self.pad_to(MIN_INITIAL_PACKET_SIZE)
+    }
+
     /// Add unpredictable values for unprotected parts of the packet.
-    pub fn scramble(&mut self, quic_bit: bool) {
+    ///
+    /// The fixed QUIC bit is only scrambled if the peer negotiated
+    /// `grease_quic_bit`, per RFC 9287; see [`Self::short`]/[`Self::long`].
+    pub fn scramble(&mut self) {
+        self.scramble_with_source(GreaseSource::default());
+    }
+
+    /// Like [`Self::scramble`], but draws the scrambling byte from `source`
+    /// rather than the secure RNG, so interop harnesses, fuzzers, and tests
+    /// can reproduce a specific greasing outcome.
+    pub fn scramble_with_source(&mut self, source: GreaseSource) {
         debug_assert!(self.len() > self.header.start);
-        let mask = if quic_bit { PACKET_BIT_FIXED_QUIC } else { 0 }
-            | if self.is_long() { 0 } else { PACKET_BIT_SPIN };
+        let mask = if self.grease_quic_bit {
+            PACKET_BIT_FIXED_QUIC
+        } else {
+            0
+        } | if self.is_long() { 0 } else { PACKET_BIT_SPIN };
         let first = self.header.start;
-        self.encoder.as_mut()[first] ^= random::<1>()[0] & mask;
+        self.encoder.as_mut()[first] ^= source.bytes()[0] & mask;
     }
 
     /// For an Initial packet, encode the token.
@@ -321,6 +434,25 @@ impl PacketBuilder {
         }
     }
 
+    /// Choose the truncated value and encoding length (in 1..=4 bytes) to
+    /// use for `full_pn` in an outgoing packet, given the largest packet
+    /// number the peer has acknowledged so far (`None` if nothing has been
+    /// acknowledged yet).
+    ///
+    /// Per RFC 9000 Appendix A.2, the encoding must be wide enough to cover
+    /// twice the range between `full_pn` and `largest_acked`, so that
+    /// [`PublicPacket::decode_packet_number`] can unambiguously recover
+    /// `full_pn` from the truncated value on the wire.
+    #[must_use]
+    pub fn encode_pn(full_pn: PacketNumber, largest_acked: Option<PacketNumber>) -> (u64, usize) {
+        let num_unacked = largest_acked.map_or(full_pn + 1, |la| full_pn - la);
+        let min_bits = u64::BITS - (num_unacked.max(1).saturating_mul(2) - 1).leading_zeros();
+        let len = usize::try_from((min_bits + 7) / 8)
+            .unwrap()
+            .clamp(1, MAX_PACKET_NUMBER_LEN);
+        (full_pn & ((1_u64 << (len * 8)) - 1), len)
+    }
+
     /// Add a packet number of the given size.
     /// For a long header packet, this also inserts a dummy length.
     /// The length is filled in after calling `build`.
@@ -495,15 +627,37 @@ impl PacketBuilder {
 
     /// Make a Version Negotiation packet.
     #[must_use]
-    #[allow(clippy::similar_names)]
     pub fn version_negotiation(
         dcid: &[u8],
         scid: &[u8],
         client_version: u32,
         versions: &[Version],
+    ) -> Vec<u8> {
+        Self::version_negotiation_with_grease(
+            dcid,
+            scid,
+            client_version,
+            versions,
+            GreaseSource::default(),
+        )
+    }
+
+    /// Like [`Self::version_negotiation`], but draws the greased reserved
+    /// bits and greased version from `source` rather than the secure RNG,
+    /// so interop harnesses, fuzzers, and tests can reproduce a specific
+    /// output and confirm the greased version still avoids colliding with
+    /// `client_version`.
+    #[must_use]
+    #[allow(clippy::similar_names)]
+    pub fn version_negotiation_with_grease(
+        dcid: &[u8],
+        scid: &[u8],
+        client_version: u32,
+        versions: &[Version],
+        source: GreaseSource,
     ) -> Vec<u8> {
         let mut encoder = Encoder::default();
-        let mut grease = random::<4>();
+        let mut grease = source.bytes();
         // This will not include the "QUIC bit" sometimes.  Intentionally.
         encoder.encode_byte(PACKET_BIT_LONG | (grease[3] & 0x7f));
         encoder.encode(&[0; 4]); // Zero version == VN.
@@ -547,6 +701,107 @@ impl From<PacketBuilder> for Encoder {
     }
 }
 
+/// Tracks per-phase usage of a short header packet's protection keys and
+/// decides when a key update should be initiated, per RFC 9001 §6.
+///
+/// This only decides *when* to flip [`PACKET_BIT_KEY_PHASE`] on outgoing
+/// packets and exposes the counters that drove that decision; installing
+/// the new keys and retiring the old ones (so that reordered packets sent
+/// under the previous phase can still be decrypted) remains
+/// `CryptoStates`' responsibility.
+#[derive(Debug, Clone)]
+pub struct KeyUpdatePolicy {
+    current_phase: bool,
+    /// The hard AEAD confidentiality/integrity limit: the current phase
+    /// must not protect any more packets once this many have been sent.
+    packet_limit: u64,
+    /// A soft limit, comfortably below `packet_limit`, past which a key
+    /// update should be proactively initiated.
+    update_threshold: u64,
+    packets_sent: u64,
+    bytes_protected: u64,
+    highest_acked: Option<PacketNumber>,
+}
+
+impl KeyUpdatePolicy {
+    #[must_use]
+    pub const fn new(packet_limit: u64, update_threshold: u64) -> Self {
+        Self {
+            current_phase: false,
+            packet_limit,
+            update_threshold,
+            packets_sent: 0,
+            bytes_protected: 0,
+            highest_acked: None,
+        }
+    }
+
+    /// The key phase that should be set on the next packet sent.
+    #[must_use]
+    pub const fn current_phase(&self) -> bool {
+        self.current_phase
+    }
+
+    #[must_use]
+    pub const fn packet_limit(&self) -> u64 {
+        self.packet_limit
+    }
+
+    #[must_use]
+    pub const fn update_threshold(&self) -> u64 {
+        self.update_threshold
+    }
+
+    #[must_use]
+    pub const fn packets_sent(&self) -> u64 {
+        self.packets_sent
+    }
+
+    #[must_use]
+    pub const fn bytes_protected(&self) -> u64 {
+        self.bytes_protected
+    }
+
+    /// Records that a packet of `len` bytes was just protected under the
+    /// current phase.
+    pub fn packet_sent(&mut self, len: usize) {
+        self.packets_sent += 1;
+        self.bytes_protected += u64::try_from(len).unwrap_or(u64::MAX);
+    }
+
+    /// Records the highest packet number the peer has acknowledged under
+    /// the current phase.
+    pub fn packet_acked(&mut self, pn: PacketNumber) {
+        self.highest_acked = Some(self.highest_acked.map_or(pn, |h| h.max(pn)));
+    }
+
+    /// Whether the current phase has reached its hard limit and must not
+    /// protect any more packets.
+    #[must_use]
+    pub const fn exhausted(&self) -> bool {
+        self.packets_sent >= self.packet_limit
+    }
+
+    /// Whether a key update should be proactively initiated: the soft
+    /// packet-count threshold has been passed, and at least one packet
+    /// sent under the current phase has already been acknowledged (so the
+    /// peer is known to have installed keys for it, and won't need the
+    /// still-in-flight old phase to decode anything it hasn't seen yet).
+    #[must_use]
+    pub const fn update_needed(&self) -> bool {
+        self.packets_sent >= self.update_threshold && self.highest_acked.is_some()
+    }
+
+    /// Marks that a key update has completed: flips the phase that will be
+    /// set on subsequent packets and resets this phase's counters.
+    pub fn rotate(&mut self) {
+        self.current_phase = !self.current_phase;
+        self.packets_sent = 0;
+        self.bytes_protected = 0;
+        self.highest_acked = None;
+    }
+}
+
 /// `PublicPacket` holds information from packets that is public only.  This allows for
 /// processing of packets prior to decryption.
 pub struct PublicPacket<'a> {
@@ -563,57 +818,49 @@ pub struct PublicPacket<'a> {
     header_len: usize,
     /// Protocol version, if present in header.
     version: Option<WireVersion>,
+    /// The observed value of the fixed QUIC bit (`PACKET_BIT_FIXED_QUIC`).
+    /// A `false` here is only valid if `grease_quic_bit` was negotiated;
+    /// see [`Self::decode`].
+    quic_bit: bool,
     /// A reference to the entire packet, including the header.
     data: &'a [u8],
 }
 
-impl<'a> PublicPacket<'a> {
-    fn opt<T>(v: Option<T>) -> Res<T> {
-        v.map_or_else(|| Err(Error::NoMoreData), |v| Ok(v))
-    }
-
-    /// Decode the type-specific portions of a long header.
-    /// This includes reading the length and the remainder of the packet.
-    /// Returns a tuple of any token and the length of the header.
-    fn decode_long(
-        decoder: &mut Decoder<'a>,
-        packet_type: PacketType,
-        version: Version,
-    ) -> Res<(&'a [u8], usize)> {
-        if packet_type == PacketType::Retry {
-            let header_len = decoder.offset();
-            let expansion = retry::expansion(version);
-            let token = decoder
-                .remaining()
-                .checked_sub(expansion)
-                .map_or(Err(Error::InvalidPacket), |v| Self::opt(decoder.decode(v)))?;
-            if token.is_empty() {
-                return Err(Error::InvalidPacket);
-            }
-            Self::opt(decoder.decode(expansion))?;
-            return Ok((token, header_len));
-        }
-        let token = if packet_type == PacketType::Initial {
-            Self::opt(decoder.decode_vvec())?
-        } else {
-            &[]
-        };
-        let len = Self::opt(decoder.decode_varint())?;
-        let header_len = decoder.offset();
-        let _body = Self::opt(decoder.decode(usize::try_from(len)?))?;
-        Ok((token, header_len))
-    }
+/// The version-invariant prefix of a packet: the first byte, the
+/// destination connection ID, and -- for long headers -- the source
+/// connection ID and version.  These are the only parts of a QUIC packet
+/// guaranteed to be in the same place regardless of version, so a
+/// demultiplexing server can decode this much, decide which connection (and
+/// so which version and keys) the packet belongs to, and only then pay for
+/// [`Self::finish`] to parse the version- and type-specific remainder.
+///
+/// This mirrors the `first, dcid` prefix quinn-proto's `PartialDecode`
+/// exposes before committing to the rest of the header.
+pub struct PartialDecode<'a> {
+    data: &'a [u8],
+    first: u8,
+    dcid: ConnectionIdRef<'a>,
+    scid: Option<ConnectionIdRef<'a>>,
+    /// The wire-format version field, for long headers only.
+    wire_version: Option<WireVersion>,
+    /// Where the version-invariant prefix ends, so `finish` can resume
+    /// decoding from here.
+    offset: usize,
+}
 
-    /// Decode the common parts of a packet.  This provides minimal parsing and validation.
-    /// Returns a tuple of a `PublicPacket` and a slice with any remainder from the datagram.
+impl<'a> PartialDecode<'a> {
+    /// Decode the version-invariant prefix of a single packet at the start
+    /// of `data`.
     ///
     /// # Errors
     ///
-    /// This will return an error if the packet could not be decoded.
+    /// This will return an error if even this much of the packet could not
+    /// be decoded.  It does not validate anything beyond the prefix; call
+    /// [`Self::finish`] for that.
     #[allow(clippy::similar_names)]
-    pub fn decode(data: &'a [u8], dcid_decoder: &dyn ConnectionIdDecoder) -> Res<(Self, &'a [u8])> {
+    pub fn decode(data: &'a [u8], dcid_decoder: &dyn ConnectionIdDecoder) -> Res<Self> {
         let mut decoder = Decoder::new(data);
-        let first = Self::opt(decoder.decode_uint::<u8>())?;
+        let first = PublicPacket::opt(decoder.decode_uint::<u8>())?;
 
         if first & 0x80 == PACKET_BIT_SHORT {
             // Conveniently, this also guarantees that there is enough space
@@ -621,84 +868,243 @@ impl<'a> PublicPacket<'a> {
             if decoder.remaining() < SAMPLE_OFFSET + SAMPLE_SIZE {
                 return Err(Error::InvalidPacket);
             }
-            let dcid = Self::opt(dcid_decoder.decode_cid(&mut decoder))?;
+            let dcid = PublicPacket::opt(dcid_decoder.decode_cid(&mut decoder))?;
             if decoder.remaining() < SAMPLE_OFFSET + SAMPLE_SIZE {
                 return Err(Error::InvalidPacket);
             }
-            let header_len = decoder.offset();
+            return Ok(Self {
+                data,
+                first,
+                dcid,
+                scid: None,
+                wire_version: None,
+                offset: decoder.offset(),
+            });
+        }
+
+        // Generic long header: version plus explicit, length-prefixed
+        // DCID/SCID are stable across every version we know about.
+        let wire_version = PublicPacket::opt(decoder.decode_uint())?;
+        let dcid = ConnectionIdRef::from(PublicPacket::opt(decoder.decode_vec(1))?);
+        let scid = ConnectionIdRef::from(PublicPacket::opt(decoder.decode_vec(1))?);
+
+        Ok(Self {
+            data,
+            first,
+            dcid,
+            scid: Some(scid),
+            wire_version: Some(wire_version),
+            offset: decoder.offset(),
+        })
+    }
+
+    #[must_use]
+    pub const fn dcid(&self) -> ConnectionIdRef<'a> {
+        self.dcid
+    }
+
+    #[must_use]
+    const fn is_long(&self) -> bool {
+        self.first & 0x80 == PACKET_BIT_LONG
+    }
+
+    /// The version field, for long headers.  `None` for short headers,
+    /// which don't carry one.
+    #[must_use]
+    pub const fn wire_version(&self) -> Option<WireVersion> {
+        self.wire_version
+    }
+
+    /// Whether the version this packet was sent with, if any, is one of
+    /// `versions`.  Short header packets are implicitly using an
+    /// already-negotiated version, so this is always `true` for those; a
+    /// Version Negotiation packet (wire version `0`) is always `false`.
+    ///
+    /// This lets a server decide to respond with a Version Negotiation
+    /// packet (see [`PacketBuilder::version_negotiation`]) without
+    /// finishing the parse of a packet whose version it doesn't support.
+    #[must_use]
+    pub fn is_supported_version(&self, versions: &[Version]) -> bool {
+        match self.wire_version {
+            None => true,
+            Some(0) => false,
+            Some(wire) => Version::try_from(wire).is_ok_and(|v| versions.contains(&v)),
+        }
+    }
+
+    /// Finish decoding the packet, parsing the type-specific remainder:
+    /// the token (for Initial and Retry), the length, and the body.
+    ///
+    /// # Errors
+    ///
+    /// This will return an error if the packet could not be decoded, or if
+    /// the fixed QUIC bit is cleared on a packet other than Version
+    /// Negotiation while `grease_quic_bit` is `false` (i.e. the local
+    /// endpoint never advertised the `grease_quic_bit` transport
+    /// parameter to its peer, so a cleared bit cannot be an intentional
+    /// grease and must be treated as RFC 9287 requires).
+    #[allow(clippy::similar_names)]
+    pub fn finish(self, grease_quic_bit: bool) -> Res<(PublicPacket<'a>, &'a [u8])> {
+        let quic_bit = self.first & PACKET_BIT_FIXED_QUIC == PACKET_BIT_FIXED_QUIC;
+
+        let mut decoder = Decoder::new(self.data);
+        decoder.skip(self.offset);
+
+        if !self.is_long() {
+            if !quic_bit && !grease_quic_bit {
+                return Err(Error::InvalidPacket);
+            }
             return Ok((
-                Self {
+                PublicPacket {
                     packet_type: PacketType::Short,
-                    dcid,
+                    dcid: self.dcid,
                     scid: None,
                     token: &[],
-                    header_len,
+                    header_len: self.offset,
                     version: None,
-                    data,
+                    quic_bit,
+                    data: self.data,
                 },
                 &[],
             ));
         }
 
-        // Generic long header.
-        let version = Self::opt(decoder.decode_uint())?;
-        let dcid = ConnectionIdRef::from(Self::opt(decoder.decode_vec(1))?);
-        let scid = ConnectionIdRef::from(Self::opt(decoder.decode_vec(1))?);
+        let scid = self.scid.expect("long header always carries a scid");
+        let wire_version = self
+            .wire_version
+            .expect("long header always carries a version");
 
-        // Version negotiation.
-        if version == 0 {
+        // Version negotiation packets grease the fixed bit by design, so a
+        // cleared bit here is never a validation failure.
+        if wire_version == 0 {
             return Ok((
-                Self {
+                PublicPacket {
                     packet_type: PacketType::VersionNegotiation,
-                    dcid,
+                    dcid: self.dcid,
                     scid: Some(scid),
                     token: &[],
                     header_len: decoder.offset(),
                     version: None,
-                    data,
+                    quic_bit,
+                    data: self.data,
                 },
                 &[],
             ));
         }
 
+        if !quic_bit && !grease_quic_bit {
+            return Err(Error::InvalidPacket);
+        }
+
         // Check that this is a long header from a supported version.
-        let Ok(version) = Version::try_from(version) else {
+        let Ok(version) = Version::try_from(wire_version) else {
             return Ok((
-                Self {
+                PublicPacket {
                     packet_type: PacketType::OtherVersion,
-                    dcid,
+                    dcid: self.dcid,
                     scid: Some(scid),
                     token: &[],
                     header_len: decoder.offset(),
-                    version: Some(version),
-                    data,
+                    version: Some(wire_version),
+                    quic_bit,
+                    data: self.data,
                 },
                 &[],
             ));
         };
 
-        if dcid.len() > MAX_CONNECTION_ID_LEN || scid.len() > MAX_CONNECTION_ID_LEN {
+        if self.dcid.len() > MAX_CONNECTION_ID_LEN || scid.len() > MAX_CONNECTION_ID_LEN {
             return Err(Error::InvalidPacket);
         }
-        let packet_type = PacketType::from_byte((first >> 4) & 3, version);
+        let packet_type = PacketType::from_byte((self.first >> 4) & 3, version);
 
         // The type-specific code includes a token.  This consumes the remainder of the packet.
-        let (token, header_len) = Self::decode_long(&mut decoder, packet_type, version)?;
-        let end = data.len() - decoder.remaining();
-        let (data, remainder) = data.split_at(end);
+        let (token, header_len) = PublicPacket::decode_long(&mut decoder, packet_type, version)?;
+        let end = self.data.len() - decoder.remaining();
+        let (data, remainder) = self.data.split_at(end);
         Ok((
-            Self {
+            PublicPacket {
                 packet_type,
-                dcid,
+                dcid: self.dcid,
                 scid: Some(scid),
                 token,
                 header_len,
                 version: Some(version.wire_version()),
+                quic_bit,
                 data,
             },
             remainder,
         ))
     }
+}
+
+impl<'a> PublicPacket<'a> {
+    fn opt<T>(v: Option<T>) -> Res<T> {
+        v.map_or_else(|| Err(Error::NoMoreData), |v| Ok(v))
+    }
+
+    /// Decode the type-specific portions of a long header.
+    /// This includes reading the length and the remainder of the packet.
+    /// Returns a tuple of any token and the length of the header.
+    fn decode_long(
+        decoder: &mut Decoder<'a>,
+        packet_type: PacketType,
+        version: Version,
+    ) -> Res<(&'a [u8], usize)> {
+        if packet_type == PacketType::Retry {
+            let header_len = decoder.offset();
+            let expansion = retry::expansion(version);
+            let token = decoder
+                .remaining()
+                .checked_sub(expansion)
+                .map_or(Err(Error::InvalidPacket), |v| Self::opt(decoder.decode(v)))?;
+            if token.is_empty() {
+                return Err(Error::InvalidPacket);
+            }
+            Self::opt(decoder.decode(expansion))?;
+            return Ok((token, header_len));
+        }
+        let token = if packet_type == PacketType::Initial {
+            Self::opt(decoder.decode_vvec())?
+        } else {
+            &[]
+        };
+        let len = Self::opt(decoder.decode_varint())?;
+        let header_len = decoder.offset();
+        let _body = Self::opt(decoder.decode(usize::try_from(len)?))?;
+        Ok((token, header_len))
+    }
+
+    /// Decode the common parts of a packet.  This provides minimal parsing and validation.
+    /// Returns a tuple of a `PublicPacket` and a slice with any remainder from the datagram.
+    ///
+    /// This is a convenience wrapper around [`PartialDecode::decode`] and
+    /// [`PartialDecode::finish`] for callers that don't need to inspect the
+    /// version-invariant prefix before committing to a full parse.
+    ///
+    /// `grease_quic_bit` should be `true` only if the local endpoint
+    /// advertised the `grease_quic_bit` transport parameter to its peer;
+    /// see [`PartialDecode::finish`].
+    ///
+    /// # Errors
+    ///
+    /// This will return an error if the packet could not be decoded.
+    pub fn decode(
+        data: &'a [u8],
+        dcid_decoder: &dyn ConnectionIdDecoder,
+        grease_quic_bit: bool,
+    ) -> Res<(Self, &'a [u8])> {
+        PartialDecode::decode(data, dcid_decoder)?.finish(grease_quic_bit)
+    }
+
+    /// The observed value of the fixed QUIC bit (`PACKET_BIT_FIXED_QUIC`)
+    /// on this packet, so connection-level code can enforce
+    /// `grease_quic_bit` policy (e.g. reject a cleared bit once the
+    /// handshake confirms the peer never advertised the parameter).
+    #[must_use]
+    pub const fn quic_bit(&self) -> bool {
+        self.quic_bit
+    }
 
     /// Validate the given packet as though it were a retry.
     #[must_use]
@@ -788,6 +1194,21 @@ impl<'a> PublicPacket<'a> {
         }
     }
 
+    /// Public wrapper over [`Self::decode_pn`], the packet-number
+    /// reconstruction algorithm this module uses while decrypting a packet
+    /// (RFC 9000 Appendix A.3), for loss-recovery and test code that needs
+    /// to reconstruct a full packet number from a truncated one without
+    /// decrypting an actual packet. The inverse of
+    /// [`PacketBuilder::encode_pn`].
+    #[must_use]
+    pub const fn decode_packet_number(
+        expected: PacketNumber,
+        truncated: u64,
+        len: usize,
+    ) -> PacketNumber {
+        Self::decode_pn(expected, truncated, len)
+    }
+
     /// Decrypt the header of the packet.
     fn decrypt_header(
         &self,
@@ -885,6 +1306,27 @@ impl<'a> PublicPacket<'a> {
         }
     }
 
+    /// The trailing 16 bytes of this packet, if it is shaped like a
+    /// stateless reset: a short header packet, which is end-host
+    /// indistinguishable from an ordinary short packet until its last 16
+    /// bytes are compared against an issued reset token.
+    ///
+    /// This doesn't confirm that `self` *is* a stateless reset -- only the
+    /// connection layer knows the tokens it issued per connection ID, so
+    /// callers whose `decrypt()` call on this packet failed should compare
+    /// the returned bytes, in constant time, against those tokens before
+    /// treating the packet as a reset.
+    #[must_use]
+    pub fn possible_stateless_reset(&self) -> Option<&'a [u8; 16]> {
+        if self.packet_type != PacketType::Short {
+            return None;
+        }
+        self.data
+            .len()
+            .checked_sub(16)
+            .map(|start| <&[u8; 16]>::try_from(&self.data[start..]).unwrap())
+    }
+
     /// # Errors
     ///
     /// This will return an error if the packet is not a version negotiation packet
@@ -901,6 +1343,85 @@ impl<'a> PublicPacket<'a> {
         }
         Ok(res)
     }
+
+    /// Iterates over every packet coalesced into the datagram `data`,
+    /// decoding each one in turn.
+    ///
+    /// A short header packet has no length field and so consumes the rest
+    /// of the datagram; once one is yielded, iteration ends.  Version
+    /// Negotiation and Retry are always terminal too, as neither can be
+    /// coalesced with a following packet. A decode error on a later packet
+    /// is yielded once, without discarding the packets already decoded,
+    /// and ends iteration.
+    ///
+    /// Call [`PublicPacketIter::offset`] to find the byte offset, within
+    /// `data`, of the packet the next call to `next()` will decode.
+    #[must_use]
+    pub fn iter(
+        data: &'a [u8],
+        dcid_decoder: &'a dyn ConnectionIdDecoder,
+        grease_quic_bit: bool,
+    ) -> PublicPacketIter<'a> {
+        PublicPacketIter {
+            datagram_len: data.len(),
+            remainder: Some(data),
+            dcid_decoder,
+            grease_quic_bit,
+        }
+    }
+
+    /// Alias for [`Self::iter`], named for callers looking for a decode loop
+    /// over every packet coalesced into a datagram rather than an iterator
+    /// combinator.
+    pub fn decode_all(
+        data: &'a [u8],
+        dcid_decoder: &'a dyn ConnectionIdDecoder,
+        grease_quic_bit: bool,
+    ) -> PublicPacketIter<'a> {
+        Self::iter(data, dcid_decoder, grease_quic_bit)
+    }
+}
+
+/// An iterator over the packets coalesced into a single datagram, returned
+/// by [`PublicPacket::iter`].
+pub struct PublicPacketIter<'a> {
+    datagram_len: usize,
+    remainder: Option<&'a [u8]>,
+    dcid_decoder: &'a dyn ConnectionIdDecoder,
+    grease_quic_bit: bool,
+}
+
+impl PublicPacketIter<'_> {
+    /// The byte offset, within the original datagram, of the next packet
+    /// [`next`][Iterator::next] will attempt to decode.
+    ///
+    /// Combined with a packet's `data.len()`, this lets a caller correlate
+    /// each yielded packet with its position in the original buffer.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.datagram_len - self.remainder.map_or(0, <[u8]>::len)
+    }
+}
+
+impl<'a> Iterator for PublicPacketIter<'a> {
+    type Item = Res<PublicPacket<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.remainder.take()?;
+        if data.is_empty() {
+            return None;
+        }
+
+        match PublicPacket::decode(data, self.dcid_decoder, self.grease_quic_bit) {
+            Ok((packet, remainder)) => {
+                if !remainder.is_empty() {
+                    self.remainder = Some(remainder);
+                }
+                Some(Ok(packet))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
 }
 
 impl fmt::Debug for PublicPacket<'_> {
@@ -956,10 +1477,12 @@ mod tests {
         cid::MAX_CONNECTION_ID_LEN,
         crypto::{CryptoDxState, CryptoStates},
         packet::{
-            PacketBuilder, PacketType, PublicPacket, PACKET_BIT_FIXED_QUIC, PACKET_BIT_LONG,
-            PACKET_BIT_SPIN,
+            GreaseSource, KeyUpdatePolicy, PacketBuilder, PacketType, PartialDecode, PublicPacket,
+            MAX_PACKET_NUMBER_LEN, MIN_INITIAL_PACKET_SIZE, PACKET_BIT_FIXED_QUIC,
+            PACKET_BIT_KEY_PHASE, PACKET_BIT_LONG, PACKET_BIT_SPIN,
         },
-        ConnectionId, EmptyConnectionIdGenerator, RandomConnectionIdGenerator, Version,
+        ConnectionId, EmptyConnectionIdGenerator, Error, RandomConnectionIdGenerator, Res,
+        Version,
     };
 
     const CLIENT_CID: &[u8] = &[0x83, 0x94, 0xc8, 0xf0, 0x3e, 0x51, 0x57, 0x08];
@@ -1007,6 +1530,7 @@ mod tests {
             Version::default(),
             None::<&[u8]>,
             Some(ConnectionId::from(SERVER_CID)),
+            true,
         );
         builder.initial_token(&[]);
         builder.pn(1, 2);
@@ -1022,7 +1546,7 @@ mod tests {
         fixture_init();
         let mut padded = SAMPLE_INITIAL.to_vec();
         padded.extend_from_slice(EXTRA);
-        let (packet, remainder) = PublicPacket::decode(&padded, &cid_mgr()).unwrap();
+        let (packet, remainder) = PublicPacket::decode(&padded, &cid_mgr(), true).unwrap();
         assert_eq!(packet.packet_type(), PacketType::Initial);
         assert_eq!(&packet.dcid()[..], &[] as &[u8]);
         assert_eq!(&packet.scid()[..], SERVER_CID);
@@ -1044,7 +1568,7 @@ mod tests {
         enc.encode_vec(1, &[]);
         enc.encode(&[0xff; 40]); // junk
 
-        assert!(PublicPacket::decode(enc.as_ref(), &cid_mgr()).is_err());
+        assert!(PublicPacket::decode(enc.as_ref(), &cid_mgr(), true).is_err());
     }
 
     #[test]
@@ -1056,7 +1580,7 @@ mod tests {
         enc.encode_vec(1, &[0x00; MAX_CONNECTION_ID_LEN + 2]);
         enc.encode(&[0xff; 40]); // junk
 
-        assert!(PublicPacket::decode(enc.as_ref(), &cid_mgr()).is_err());
+        assert!(PublicPacket::decode(enc.as_ref(), &cid_mgr(), true).is_err());
     }
 
     const SAMPLE_SHORT: &[u8] = &[
@@ -1069,7 +1593,7 @@ mod tests {
     fn build_short() {
         fixture_init();
         let mut builder =
-            PacketBuilder::short(Encoder::new(), true, Some(ConnectionId::from(SERVER_CID)));
+            PacketBuilder::short(Encoder::new(), true, Some(ConnectionId::from(SERVER_CID)), true);
         builder.pn(0, 1);
         builder.encode(SAMPLE_SHORT_PAYLOAD); // Enough payload for sampling.
         let packet = builder
@@ -1084,8 +1608,8 @@ mod tests {
         let mut firsts = Vec::new();
         for _ in 0..64 {
             let mut builder =
-                PacketBuilder::short(Encoder::new(), true, Some(ConnectionId::from(SERVER_CID)));
-            builder.scramble(true);
+                PacketBuilder::short(Encoder::new(), true, Some(ConnectionId::from(SERVER_CID)), true);
+            builder.scramble();
             builder.pn(0, 1);
             firsts.push(builder.as_ref()[0]);
         }
@@ -1100,10 +1624,23 @@ mod tests {
         assert!(!firsts.iter().all(is_set(PACKET_BIT_SPIN)));
     }
 
+    #[test]
+    fn scramble_with_fixed_source_is_deterministic() {
+        fixture_init();
+        let build = || {
+            let mut builder =
+                PacketBuilder::short(Encoder::new(), true, Some(ConnectionId::from(SERVER_CID)), true);
+            builder.scramble_with_source(GreaseSource::Fixed([0xff; 4]));
+            builder.pn(0, 1);
+            builder.as_ref()[0]
+        };
+        assert_eq!(build(), build());
+    }
+
     #[test]
     fn decode_short() {
         fixture_init();
-        let (packet, remainder) = PublicPacket::decode(SAMPLE_SHORT, &cid_mgr()).unwrap();
+        let (packet, remainder) = PublicPacket::decode(SAMPLE_SHORT, &cid_mgr(), true).unwrap();
         assert_eq!(packet.packet_type(), PacketType::Short);
         assert!(remainder.is_empty());
         let decrypted = packet
@@ -1120,6 +1657,7 @@ mod tests {
         let (packet, remainder) = PublicPacket::decode(
             SAMPLE_SHORT,
             &RandomConnectionIdGenerator::new(SERVER_CID.len() - 1),
+            true,
         )
         .unwrap();
         assert_eq!(packet.packet_type(), PacketType::Short);
@@ -1134,11 +1672,31 @@ mod tests {
     fn decode_short_long_cid() {
         assert!(PublicPacket::decode(
             SAMPLE_SHORT,
-            &RandomConnectionIdGenerator::new(SERVER_CID.len() + 1)
+            &RandomConnectionIdGenerator::new(SERVER_CID.len() + 1),
+            true,
         )
         .is_err());
     }
 
+    #[test]
+    fn possible_stateless_reset_short() {
+        fixture_init();
+        let (packet, _) = PublicPacket::decode(SAMPLE_SHORT, &cid_mgr(), true).unwrap();
+        assert_eq!(
+            packet.possible_stateless_reset(),
+            Some(&SAMPLE_SHORT[SAMPLE_SHORT.len() - 16..].try_into().unwrap())
+        );
+    }
+
+    #[test]
+    fn possible_stateless_reset_long_is_none() {
+        fixture_init();
+        let mut padded = SAMPLE_INITIAL.to_vec();
+        padded.extend_from_slice(&[0xce; 33]);
+        let (packet, _) = PublicPacket::decode(&padded, &cid_mgr(), true).unwrap();
+        assert_eq!(packet.possible_stateless_reset(), None);
+    }
+
     #[test]
     fn build_two() {
         fixture_init();
@@ -1149,6 +1707,7 @@ mod tests {
             Version::default(),
             Some(ConnectionId::from(SERVER_CID)),
             Some(ConnectionId::from(CLIENT_CID)),
+            true,
         );
         builder.pn(0, 1);
         builder.encode(&[0; 3]);
@@ -1157,7 +1716,7 @@ mod tests {
         let first = encoder.clone();
 
         let mut builder =
-            PacketBuilder::short(encoder, false, Some(ConnectionId::from(SERVER_CID)));
+            PacketBuilder::short(encoder, false, Some(ConnectionId::from(SERVER_CID)), true);
         builder.pn(1, 3);
         builder.encode(&[0]); // Minimal size (packet number is big enough).
         let encoder = builder.build(&mut prot).expect("build");
@@ -1184,6 +1743,7 @@ mod tests {
             Version::default(),
             None::<&[u8]>,
             None::<&[u8]>,
+            true,
         );
         builder.pn(0, 1);
         builder.encode(&[1, 2, 3]);
@@ -1203,9 +1763,10 @@ mod tests {
                 Version::default(),
                 None::<&[u8]>,
                 None::<&[u8]>,
+                true,
             );
             builder.pn(0, 1);
-            builder.scramble(true);
+            builder.scramble();
             if (builder.as_ref()[0] & PACKET_BIT_FIXED_QUIC) == 0 {
                 found_unset = true;
             } else {
@@ -1224,6 +1785,7 @@ mod tests {
             Version::default(),
             None::<&[u8]>,
             Some(ConnectionId::from(SERVER_CID)),
+            true,
         );
         assert_ne!(builder.remaining(), 0);
         builder.initial_token(&[]);
@@ -1242,6 +1804,7 @@ mod tests {
             Encoder::with_capacity(100),
             true,
             Some(ConnectionId::from(SERVER_CID)),
+            true,
         );
         builder.pn(0, 1);
         // Pad, but not up to the full capacity. Leave enough space for the
@@ -1258,11 +1821,80 @@ mod tests {
             Version::default(),
             Some(ConnectionId::from(SERVER_CID)),
             Some(ConnectionId::from(SERVER_CID)),
+            true,
         );
         assert_eq!(builder.remaining(), 0);
         assert_eq!(builder.abort(), encoder_copy);
     }
 
+    #[test]
+    fn pad_to_already_large_enough() {
+        fixture_init();
+        let mut builder = PacketBuilder::long(
+            Encoder::new(),
+            PacketType::Initial,
+            Version::default(),
+            None::<&[u8]>,
+            Some(ConnectionId::from(SERVER_CID)),
+            true,
+        );
+        builder.pn(1, 2);
+        let len_before = builder.len();
+        assert_eq!(builder.pad_to(len_before), Ok(false));
+        assert_eq!(builder.len(), len_before);
+    }
+
+    #[test]
+    fn pad_to_no_room() {
+        fixture_init();
+        let mut builder = PacketBuilder::long(
+            Encoder::new(),
+            PacketType::Initial,
+            Version::default(),
+            None::<&[u8]>,
+            Some(ConnectionId::from(SERVER_CID)),
+            true,
+        );
+        builder.pn(1, 2);
+        builder.set_limit(builder.len());
+        assert_eq!(
+            builder.pad_to(MIN_INITIAL_PACKET_SIZE),
+            Err(Error::NotAvailable)
+        );
+    }
+
+    #[test]
+    fn enforce_min_initial_size_round_trips() {
+        fixture_init();
+        let mut prot = CryptoDxState::test_default();
+        let mut builder = PacketBuilder::long(
+            Encoder::new(),
+            PacketType::Initial,
+            Version::default(),
+            None::<&[u8]>,
+            Some(ConnectionId::from(SERVER_CID)),
+            true,
+        );
+        builder.initial_token(&[]);
+        builder.pn(0, 2);
+        let header_len = builder.len();
+        builder.encode(&[0; 3]);
+        assert_eq!(builder.enforce_min_initial_size(), Ok(true));
+        assert!(builder.len() >= MIN_INITIAL_PACKET_SIZE);
+
+        let packet = builder.build(&mut prot).expect("build");
+        assert!(packet.len() >= MIN_INITIAL_PACKET_SIZE);
+
+        let (decoded, remainder) = PublicPacket::decode(packet.as_ref(), &cid_mgr(), true).unwrap();
+        assert_eq!(decoded.packet_type(), PacketType::Initial);
+        assert!(remainder.is_empty());
+        let decrypted = decoded
+            .decrypt(&mut CryptoStates::test_default(), now())
+            .unwrap();
+        // The payload is the 3 zero bytes followed by the PADDING (0x00) frames.
+        assert_eq!(&decrypted[..], &vec![0; MIN_INITIAL_PACKET_SIZE - header_len][..]);
+    }
+
     const SAMPLE_RETRY_V2: &[u8] = &[
         0xcf, 0x6b, 0x33, 0x43, 0xcf, 0x00, 0x08, 0xf0, 0x67, 0xa5, 0x50, 0x2a, 0x42, 0x62, 0xb5,
         0x74, 0x6f, 0x6b, 0x65, 0x6e, 0xc8, 0x64, 0x6c, 0xe8, 0xbf, 0xe3, 0x39, 0x52, 0xd9, 0x55,
@@ -1288,7 +1920,7 @@ mod tests {
         let retry =
             PacketBuilder::retry(version, &[], SERVER_CID, RETRY_TOKEN, CLIENT_CID).unwrap();
 
-        let (packet, remainder) = PublicPacket::decode(&retry, &cid_mgr()).unwrap();
+        let (packet, remainder) = PublicPacket::decode(&retry, &cid_mgr(), true).unwrap();
         assert!(packet.is_valid_retry(&ConnectionId::from(CLIENT_CID)));
         assert!(remainder.is_empty());
 
@@ -1337,7 +1969,7 @@ mod tests {
     fn decode_retry(version: Version, sample_retry: &[u8]) {
         fixture_init();
         let (packet, remainder) =
-            PublicPacket::decode(sample_retry, &RandomConnectionIdGenerator::new(5)).unwrap();
+            PublicPacket::decode(sample_retry, &RandomConnectionIdGenerator::new(5), true).unwrap();
         assert!(packet.is_valid_retry(&ConnectionId::from(CLIENT_CID)));
         assert_eq!(Some(version), packet.version());
         assert!(packet.dcid().is_empty());
@@ -1368,30 +2000,30 @@ mod tests {
         let cid_mgr = RandomConnectionIdGenerator::new(5);
         let odcid = ConnectionId::from(CLIENT_CID);
 
-        assert!(PublicPacket::decode(&[], &cid_mgr).is_err());
+        assert!(PublicPacket::decode(&[], &cid_mgr, true).is_err());
 
-        let (packet, remainder) = PublicPacket::decode(SAMPLE_RETRY_V1, &cid_mgr).unwrap();
+        let (packet, remainder) = PublicPacket::decode(SAMPLE_RETRY_V1, &cid_mgr, true).unwrap();
         assert!(remainder.is_empty());
         assert!(packet.is_valid_retry(&odcid));
 
         let mut damaged_retry = SAMPLE_RETRY_V1.to_vec();
         let last = damaged_retry.len() - 1;
         damaged_retry[last] ^= 66;
-        let (packet, remainder) = PublicPacket::decode(&damaged_retry, &cid_mgr).unwrap();
+        let (packet, remainder) = PublicPacket::decode(&damaged_retry, &cid_mgr, true).unwrap();
         assert!(remainder.is_empty());
         assert!(!packet.is_valid_retry(&odcid));
 
         damaged_retry.truncate(last);
-        let (packet, remainder) = PublicPacket::decode(&damaged_retry, &cid_mgr).unwrap();
+        let (packet, remainder) = PublicPacket::decode(&damaged_retry, &cid_mgr, true).unwrap();
         assert!(remainder.is_empty());
         assert!(!packet.is_valid_retry(&odcid));
 
         // An invalid token should be rejected sooner.
         damaged_retry.truncate(last - 4);
-        assert!(PublicPacket::decode(&damaged_retry, &cid_mgr).is_err());
+        assert!(PublicPacket::decode(&damaged_retry, &cid_mgr, true).is_err());
 
         damaged_retry.truncate(last - 1);
-        assert!(PublicPacket::decode(&damaged_retry, &cid_mgr).is_err());
+        assert!(PublicPacket::decode(&damaged_retry, &cid_mgr, true).is_err());
     }
 
     const SAMPLE_VN: &[u8] = &[
@@ -1430,10 +2062,46 @@ mod tests {
         assert_ne!(&vn[SAMPLE_VN.len() - 4..], &[0x0a, 0x0a, 0x0a, 0x0a]);
     }
 
+    /// With a fixed grease source the output is fully deterministic, with no
+    /// need to mask away randomness before comparing.
+    #[test]
+    fn build_vn_with_fixed_grease() {
+        fixture_init();
+        let vn = PacketBuilder::version_negotiation_with_grease(
+            SERVER_CID,
+            CLIENT_CID,
+            0x0a0a_0a0a,
+            &Version::all(),
+            GreaseSource::Fixed([0x12, 0x34, 0x56, 0x78]),
+        );
+        let vn_again = PacketBuilder::version_negotiation_with_grease(
+            SERVER_CID,
+            CLIENT_CID,
+            0x0a0a_0a0a,
+            &Version::all(),
+            GreaseSource::Fixed([0x12, 0x34, 0x56, 0x78]),
+        );
+        assert_eq!(vn, vn_again);
+    }
+
+    /// A fixed grease source still avoids colliding with the client version.
+    #[test]
+    fn vn_with_fixed_grease_avoids_client_version() {
+        fixture_init();
+        let vn = PacketBuilder::version_negotiation_with_grease(
+            SERVER_CID,
+            CLIENT_CID,
+            0x0a0a_0a0a,
+            &Version::all(),
+            GreaseSource::Fixed([0x0a, 0x0a, 0x0a, 0x0a]),
+        );
+        assert_ne!(&vn[vn.len() - 4..], &[0x0a, 0x0a, 0x0a, 0x0a]);
+    }
+
     #[test]
     fn parse_vn() {
         let (packet, remainder) =
-            PublicPacket::decode(SAMPLE_VN, &EmptyConnectionIdGenerator::default()).unwrap();
+            PublicPacket::decode(SAMPLE_VN, &EmptyConnectionIdGenerator::default(), true).unwrap();
         assert!(remainder.is_empty());
         assert_eq!(&packet.dcid[..], SERVER_CID);
         assert!(packet.scid.is_some());
@@ -1454,7 +2122,7 @@ mod tests {
         enc.encode_uint(4, 0x5a6a_7a8a_u64);
 
         let (packet, remainder) =
-            PublicPacket::decode(enc.as_ref(), &EmptyConnectionIdGenerator::default()).unwrap();
+            PublicPacket::decode(enc.as_ref(), &EmptyConnectionIdGenerator::default(), true).unwrap();
         assert!(remainder.is_empty());
         assert_eq!(&packet.dcid[..], BIG_DCID);
         assert!(packet.scid.is_some());
@@ -1482,6 +2150,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_packet_number_matches_decode_pn() {
+        assert_eq!(
+            PublicPacket::decode_packet_number(0x80, 2, 1),
+            PublicPacket::decode_pn(0x80, 2, 1),
+        );
+    }
+
+    #[test]
+    fn encode_pn_picks_minimal_unambiguous_length() {
+        // Nothing acked yet: the whole range up to full_pn must be covered.
+        assert_eq!(PacketBuilder::encode_pn(0, None), (0, 1));
+        assert_eq!(PacketBuilder::encode_pn(0x7f, None), (0x7f, 1));
+        assert_eq!(PacketBuilder::encode_pn(0xff, None), (0xff, 2));
+
+        // With an ack, only the gap since largest_acked needs to be covered.
+        assert_eq!(PacketBuilder::encode_pn(10, Some(9)), (10, 1));
+        assert_eq!(PacketBuilder::encode_pn(1000, Some(990)), (1000 & 0xff, 1));
+        assert_eq!(
+            PacketBuilder::encode_pn(100_000, Some(0)),
+            (100_000 & 0xff_ffff, 3)
+        );
+
+        // The encoding never grows past the maximum packet number length.
+        assert_eq!(
+            PacketBuilder::encode_pn(0x3fff_ffff_ffff_ffff, None),
+            (0x3fff_ffff_ffff_ffff & 0xffff_ffff, MAX_PACKET_NUMBER_LEN)
+        );
+    }
+
+    #[test]
+    fn encode_pn_round_trips_through_decode_packet_number() {
+        for (full_pn, largest_acked) in [(0, None), (1, Some(0)), (0x1234, Some(0x1200)), (0x3fff_ffff, Some(0))] {
+            let (truncated, len) = PacketBuilder::encode_pn(full_pn, largest_acked);
+            let expected = largest_acked.map_or(0, |la| la + 1);
+            assert_eq!(
+                PublicPacket::decode_packet_number(expected, truncated, len),
+                full_pn
+            );
+        }
+    }
+
     #[test]
     fn chacha20_sample() {
         const PACKET: &[u8] = &[
@@ -1490,7 +2200,7 @@ mod tests {
         ];
         fixture_init();
         let (packet, slice) =
-            PublicPacket::decode(PACKET, &EmptyConnectionIdGenerator::default()).unwrap();
+            PublicPacket::decode(PACKET, &EmptyConnectionIdGenerator::default(), true).unwrap();
         assert!(slice.is_empty());
         let decrypted = packet
             .decrypt(&mut CryptoStates::test_chacha(), now())
@@ -1503,7 +2213,7 @@ mod tests {
     #[test]
     fn decode_empty() {
         neqo_crypto::init().unwrap();
-        let res = PublicPacket::decode(&[], &EmptyConnectionIdGenerator::default());
+        let res = PublicPacket::decode(&[], &EmptyConnectionIdGenerator::default(), true);
         assert!(res.is_err());
     }
 
@@ -1513,7 +2223,256 @@ mod tests {
         let res = PublicPacket::decode(
             &[179, 255, 0, 0, 29, 0, 0],
             &EmptyConnectionIdGenerator::default(),
+            true,
         );
         assert!(res.is_err());
     }
+
+    #[test]
+    fn partial_decode_short() {
+        fixture_init();
+        let partial = PartialDecode::decode(SAMPLE_SHORT, &cid_mgr()).unwrap();
+        assert_eq!(&partial.dcid()[..], SERVER_CID);
+        assert_eq!(partial.wire_version(), None);
+        assert!(partial.is_supported_version(&[]));
+
+        let (packet, remainder) = partial.finish(true).unwrap();
+        assert_eq!(packet.packet_type(), PacketType::Short);
+        assert!(remainder.is_empty());
+    }
+
+    #[test]
+    fn partial_decode_long() {
+        fixture_init();
+        let mut padded = SAMPLE_INITIAL.to_vec();
+        padded.extend_from_slice(&[0xce; 33]);
+        let partial = PartialDecode::decode(&padded, &cid_mgr()).unwrap();
+        assert_eq!(&partial.dcid()[..], &[] as &[u8]);
+        assert_eq!(partial.wire_version(), Some(Version::default().wire_version()));
+        assert!(partial.is_supported_version(&Version::all()));
+        assert!(!partial.is_supported_version(&[]));
+
+        let (packet, remainder) = partial.finish(true).unwrap();
+        assert_eq!(packet.packet_type(), PacketType::Initial);
+        assert_eq!(&packet.scid()[..], SERVER_CID);
+        assert_eq!(remainder, &[0xce; 33]);
+    }
+
+    #[test]
+    fn partial_decode_unsupported_version() {
+        let mut enc = Encoder::new();
+        enc.encode_byte(PACKET_BIT_LONG | PACKET_BIT_FIXED_QUIC);
+        enc.encode_uint(4, 0x1a2a_3a4a_u32);
+        enc.encode_vec(1, SERVER_CID);
+        enc.encode_vec(1, CLIENT_CID);
+        enc.encode(&[0xff; 16]); // junk
+
+        let partial = PartialDecode::decode(enc.as_ref(), &cid_mgr()).unwrap();
+        assert_eq!(partial.wire_version(), Some(0x1a2a_3a4a));
+        assert!(!partial.is_supported_version(&Version::all()));
+
+        let (packet, remainder) = partial.finish(true).unwrap();
+        assert_eq!(packet.packet_type(), PacketType::OtherVersion);
+        assert!(remainder.is_empty());
+    }
+
+    #[test]
+    fn partial_decode_version_negotiation() {
+        let partial = PartialDecode::decode(SAMPLE_VN, &EmptyConnectionIdGenerator::default())
+            .unwrap();
+        assert_eq!(partial.wire_version(), Some(0));
+        assert!(!partial.is_supported_version(&Version::all()));
+
+        let (packet, remainder) = partial.finish(true).unwrap();
+        assert_eq!(packet.packet_type(), PacketType::VersionNegotiation);
+        assert!(remainder.is_empty());
+    }
+
+    /// Version Negotiation packets grease the fixed bit, so it may be unset; they decode
+    /// regardless of whether `grease_quic_bit` was negotiated.
+    #[test]
+    fn partial_decode_version_negotiation_quic_bit_exempt() {
+        let partial = PartialDecode::decode(SAMPLE_VN, &EmptyConnectionIdGenerator::default())
+            .unwrap();
+
+        let (packet, _) = partial.finish(false).unwrap();
+        assert_eq!(packet.packet_type(), PacketType::VersionNegotiation);
+        assert!(!packet.quic_bit());
+    }
+
+    #[test]
+    fn decode_short_quic_bit_unset_requires_grease() {
+        fixture_init();
+        let mut unset = SAMPLE_SHORT.to_vec();
+        unset[0] &= !PACKET_BIT_FIXED_QUIC;
+
+        assert!(PublicPacket::decode(&unset, &cid_mgr(), false).is_err());
+
+        let (packet, _) = PublicPacket::decode(&unset, &cid_mgr(), true).unwrap();
+        assert_eq!(packet.packet_type(), PacketType::Short);
+        assert!(!packet.quic_bit());
+    }
+
+    #[test]
+    fn decode_long_quic_bit_unset_requires_grease() {
+        fixture_init();
+        let mut padded = SAMPLE_INITIAL.to_vec();
+        padded[0] &= !PACKET_BIT_FIXED_QUIC;
+
+        assert!(PublicPacket::decode(&padded, &cid_mgr(), false).is_err());
+
+        let (packet, _) = PublicPacket::decode(&padded, &cid_mgr(), true).unwrap();
+        assert_eq!(packet.packet_type(), PacketType::Initial);
+        assert!(!packet.quic_bit());
+    }
+
+    #[test]
+    fn iter_single() {
+        fixture_init();
+        let packets = PublicPacket::iter(SAMPLE_SHORT, &cid_mgr(), true)
+            .collect::<Res<Vec<_>>>()
+            .unwrap();
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packets[0].packet_type(), PacketType::Short);
+    }
+
+    #[test]
+    fn decode_all_is_iter() {
+        fixture_init();
+        let packets = PublicPacket::decode_all(SAMPLE_SHORT, &cid_mgr(), true)
+            .collect::<Res<Vec<_>>>()
+            .unwrap();
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packets[0].packet_type(), PacketType::Short);
+    }
+
+    #[test]
+    fn iter_coalesced() {
+        fixture_init();
+        let mut datagram = SAMPLE_INITIAL.to_vec();
+        datagram.extend_from_slice(SAMPLE_SHORT);
+        let packets = PublicPacket::iter(&datagram, &cid_mgr(), true)
+            .collect::<Res<Vec<_>>>()
+            .unwrap();
+        assert_eq!(packets.len(), 2);
+        assert_eq!(packets[0].packet_type(), PacketType::Initial);
+        assert_eq!(packets[1].packet_type(), PacketType::Short);
+    }
+
+    #[test]
+    fn iter_offset_tracks_consumed_bytes() {
+        fixture_init();
+        let mut datagram = SAMPLE_INITIAL.to_vec();
+        datagram.extend_from_slice(SAMPLE_SHORT);
+        let mut iter = PublicPacket::iter(&datagram, &cid_mgr(), true);
+        assert_eq!(iter.offset(), 0);
+
+        let first = iter.next().unwrap().unwrap();
+        assert_eq!(first.packet_type(), PacketType::Initial);
+        assert_eq!(iter.offset(), SAMPLE_INITIAL.len());
+
+        let second = iter.next().unwrap().unwrap();
+        assert_eq!(second.packet_type(), PacketType::Short);
+        assert_eq!(iter.offset(), datagram.len());
+
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn iter_stops_after_short() {
+        fixture_init();
+        // Anything following a short header packet is part of it, not a
+        // separate coalesced packet, so it must not be yielded again.
+        let mut datagram = SAMPLE_SHORT.to_vec();
+        datagram.extend_from_slice(SAMPLE_SHORT);
+        let packets = PublicPacket::iter(&datagram, &cid_mgr(), true)
+            .collect::<Res<Vec<_>>>()
+            .unwrap();
+        assert_eq!(packets.len(), 1);
+    }
+
+    #[test]
+    fn iter_surfaces_error_on_interior_packet() {
+        fixture_init();
+        let mut datagram = SAMPLE_INITIAL.to_vec();
+        datagram.push(0xff); // Not enough for another packet to decode.
+        let mut iter = PublicPacket::iter(&datagram, &cid_mgr(), true);
+        assert_eq!(
+            iter.next().unwrap().unwrap().packet_type(),
+            PacketType::Initial
+        );
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn key_update_policy_thresholds() {
+        let mut policy = KeyUpdatePolicy::new(100, 10);
+        assert!(!policy.exhausted());
+        assert!(!policy.update_needed());
+
+        for _ in 0..10 {
+            policy.packet_sent(50);
+        }
+        assert_eq!(policy.packets_sent(), 10);
+        assert_eq!(policy.bytes_protected(), 500);
+        // No acknowledgement yet, so an update isn't needed even past the
+        // soft threshold.
+        assert!(!policy.update_needed());
+
+        policy.packet_acked(3);
+        assert!(policy.update_needed());
+        assert!(!policy.exhausted());
+
+        for _ in 10..100 {
+            policy.packet_sent(50);
+        }
+        assert!(policy.exhausted());
+    }
+
+    #[test]
+    fn key_update_policy_rotate_resets_counters() {
+        let mut policy = KeyUpdatePolicy::new(100, 10);
+        assert!(!policy.current_phase());
+        policy.packet_sent(10);
+        policy.packet_acked(0);
+
+        policy.rotate();
+        assert!(policy.current_phase());
+        assert_eq!(policy.packets_sent(), 0);
+        assert_eq!(policy.bytes_protected(), 0);
+        assert!(!policy.update_needed());
+    }
+
+    #[test]
+    fn short_with_policy_uses_current_phase() {
+        fixture_init();
+        let mut policy = KeyUpdatePolicy::new(100, 10);
+        policy.rotate();
+
+        let builder = PacketBuilder::short_with_policy(
+            Encoder::new(),
+            &policy,
+            Some(ConnectionId::from(SERVER_CID)),
+            true,
+        );
+        assert_ne!(builder.remaining(), 0);
+        assert_eq!(builder.as_ref()[0] & PACKET_BIT_KEY_PHASE, PACKET_BIT_KEY_PHASE);
+    }
+
+    #[test]
+    fn short_with_policy_refuses_when_exhausted() {
+        fixture_init();
+        let mut policy = KeyUpdatePolicy::new(1, 1);
+        policy.packet_sent(10);
+        assert!(policy.exhausted());
+
+        let builder = PacketBuilder::short_with_policy(
+            Encoder::new(),
+            &policy,
+            Some(ConnectionId::from(SERVER_CID)),
+            true,
+        );
+        assert_eq!(builder.remaining(), 0);
+    }
 }
@@ -0,0 +1,221 @@
+//! Linux `epoll` support.
+//!
+//! # References
+//!  - [Linux]
+//!
+//! [Linux]: https://man7.org/linux/man-pages/man7/epoll.7.html
+#![allow(unsafe_code)]
+
+use crate::backend::c;
+use crate::backend::event::syscalls;
+use crate::fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd};
+use crate::io;
+use core::time::Duration;
+
+bitflags::bitflags! {
+    /// `EPOLL_CLOEXEC` for use with [`create`].
+    #[repr(transparent)]
+    #[derive(Default)]
+    pub struct CreateFlags: u32 {
+        /// `EPOLL_CLOEXEC`
+        const CLOEXEC = c::EPOLL_CLOEXEC as u32;
+    }
+}
+
+bitflags::bitflags! {
+    /// `EPOLL*` flags for use with [`add`] and [`modify`], and returned from
+    /// [`EventVec`] iteration.
+    #[repr(transparent)]
+    #[derive(Default)]
+    pub struct EventFlags: u32 {
+        /// `EPOLLIN`
+        const IN = c::EPOLLIN as u32;
+        /// `EPOLLOUT`
+        const OUT = c::EPOLLOUT as u32;
+        /// `EPOLLPRI`
+        const PRI = c::EPOLLPRI as u32;
+        /// `EPOLLERR`
+        const ERR = c::EPOLLERR as u32;
+        /// `EPOLLHUP`
+        const HUP = c::EPOLLHUP as u32;
+        /// `EPOLLRDHUP`
+        const RDHUP = c::EPOLLRDHUP as u32;
+        /// `EPOLLET`—requests edge-triggered notification.
+        const ET = c::EPOLLET as u32;
+        /// `EPOLLONESHOT`—disables the interest after one event.
+        const ONESHOT = c::EPOLLONESHOT as u32;
+        /// `EPOLLWAKEUP`
+        const WAKEUP = c::EPOLLWAKEUP as u32;
+        /// `EPOLLEXCLUSIVE`—see [`add`]'s documentation.
+        const EXCLUSIVE = c::EPOLLEXCLUSIVE as u32;
+    }
+}
+
+impl EventFlags {
+    /// True if the readiness indicates the file descriptor is readable.
+    #[inline]
+    pub const fn is_readable(self) -> bool {
+        self.intersects(Self::IN.union(Self::PRI))
+    }
+
+    /// True if the readiness indicates the file descriptor is writable.
+    #[inline]
+    pub const fn is_writable(self) -> bool {
+        self.intersects(Self::OUT)
+    }
+
+    /// True if `EPOLLERR` is set.
+    #[inline]
+    pub const fn is_error(self) -> bool {
+        self.intersects(Self::ERR)
+    }
+
+    /// True if `EPOLLHUP` or `EPOLLRDHUP` is set.
+    ///
+    /// Note that `EPOLLHUP` can be reported on a socket even when
+    /// `connect()` succeeded and no error occurred; don't treat this as
+    /// equivalent to [`EventFlags::is_error`].
+    #[inline]
+    pub const fn is_hangup(self) -> bool {
+        self.intersects(Self::HUP.union(Self::RDHUP))
+    }
+
+    /// True if `EPOLLPRI` is set.
+    #[inline]
+    pub const fn is_priority(self) -> bool {
+        self.intersects(Self::PRI)
+    }
+}
+
+/// `union epoll_data`—Opaque data associated with an `epoll` registration.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct EventData(c::epoll_data_t);
+
+impl EventData {
+    /// Construct an `EventData` wrapping a `u64`.
+    #[inline]
+    pub fn new_u64(value: u64) -> Self {
+        Self(c::epoll_data_t { u64_: value })
+    }
+
+    /// Construct an `EventData` wrapping a `u32`.
+    #[inline]
+    pub fn new_u32(value: u32) -> Self {
+        Self(c::epoll_data_t { u32_: value })
+    }
+
+    /// Construct an `EventData` wrapping a pointer.
+    #[inline]
+    pub fn new_ptr(value: *mut core::ffi::c_void) -> Self {
+        Self(c::epoll_data_t { ptr: value })
+    }
+
+    /// Return the value as a `u64`.
+    #[inline]
+    pub fn u64(self) -> u64 {
+        // SAFETY: Reading the union through whichever variant was written is
+        // how `epoll_data_t` is meant to be used.
+        unsafe { self.0.u64_ }
+    }
+
+    /// Return the value as a `u32`.
+    #[inline]
+    pub fn u32(self) -> u32 {
+        unsafe { self.0.u32_ }
+    }
+}
+
+/// An event returned by [`wait`], borrowed from an [`EventVec`].
+#[derive(Clone, Copy)]
+pub struct Event {
+    /// The events that occurred.
+    pub flags: EventFlags,
+    /// The data associated with the registration that produced this event.
+    pub data: EventData,
+}
+
+/// A vector of `epoll` events, re-used across calls to [`wait`] to avoid
+/// reallocating.
+pub struct EventVec {
+    events: alloc::vec::Vec<c::epoll_event>,
+    len: usize,
+}
+
+impl EventVec {
+    /// Construct an `EventVec` with room for `capacity` events.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { events: alloc::vec::Vec::with_capacity(capacity), len: 0 }
+    }
+
+    /// The number of events currently stored.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// True if there are no events currently stored.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// An iterator over the currently stored events.
+    pub fn iter(&self) -> impl Iterator<Item = Event> + '_ {
+        self.events[..self.len].iter().map(|e| Event {
+            flags: EventFlags::from_bits_retain(e.events),
+            data: EventData::new_u64(e.u64),
+        })
+    }
+}
+
+/// `epoll_create1(flags)`—Creates a new `epoll` object.
+#[inline]
+pub fn create(flags: CreateFlags) -> io::Result<OwnedFd> {
+    syscalls::epoll_create(flags)
+}
+
+/// `epoll_ctl(self, EPOLL_CTL_ADD, fd, &epoll_event { events, data })`
+///
+/// Registering the same file descriptor twice is not allowed; use
+/// [`modify`] to change an existing registration. [`EventFlags::EXCLUSIVE`]
+/// may be set to avoid the "thundering herd" problem when multiple epoll
+/// instances share a registration for a single source, at the cost of
+/// restricting which other flags may be combined with it; see `epoll_ctl(2)`.
+#[inline]
+pub fn add(
+    epoll: impl AsFd,
+    source: impl AsFd,
+    data: EventData,
+    flags: EventFlags,
+) -> io::Result<()> {
+    syscalls::epoll_add(epoll.as_fd(), source.as_fd(), data, flags)
+}
+
+/// `epoll_ctl(self, EPOLL_CTL_MOD, fd, &epoll_event { events, data })`
+#[inline]
+pub fn modify(
+    epoll: impl AsFd,
+    source: impl AsFd,
+    data: EventData,
+    flags: EventFlags,
+) -> io::Result<()> {
+    syscalls::epoll_modify(epoll.as_fd(), source.as_fd(), data, flags)
+}
+
+/// `epoll_ctl(self, EPOLL_CTL_DEL, fd, NULL)`
+#[inline]
+pub fn delete(epoll: impl AsFd, source: impl AsFd) -> io::Result<()> {
+    syscalls::epoll_delete(epoll.as_fd(), source.as_fd())
+}
+
+/// `epoll_pwait2(self, events, timeout, NULL)`—Waits for events, with
+/// nanosecond precision.
+///
+/// On kernels old enough to lack `epoll_pwait2` (pre-5.11), `timeout` is
+/// rounded up to the next millisecond and dispatched through `epoll_wait`
+/// instead.
+#[inline]
+pub fn wait(epoll: impl AsFd, events: &mut EventVec, timeout: Option<Duration>) -> io::Result<()> {
+    let len = syscalls::epoll_wait(epoll.as_fd(), &mut events.events, timeout)?;
+    events.len = len;
+    Ok(())
+}
@@ -0,0 +1,122 @@
+//! The `eventfd` function and a higher-level [`Eventfd`] wrapper.
+//!
+//! # References
+//!  - [Linux]
+//!
+//! [Linux]: https://man7.org/linux/man-pages/man2/eventfd.2.html
+#![allow(unsafe_code)]
+
+use crate::backend::c;
+use crate::backend::event::syscalls;
+use crate::fd::{AsFd, BorrowedFd, OwnedFd};
+use crate::io;
+
+bitflags::bitflags! {
+    /// `EFD_*` flags for use with [`eventfd`].
+    #[repr(transparent)]
+    #[derive(Default)]
+    pub struct EventfdFlags: u32 {
+        /// `EFD_CLOEXEC`
+        const CLOEXEC = c::EFD_CLOEXEC as u32;
+        /// `EFD_NONBLOCK`
+        const NONBLOCK = c::EFD_NONBLOCK as u32;
+        /// `EFD_SEMAPHORE`
+        const SEMAPHORE = c::EFD_SEMAPHORE as u32;
+    }
+}
+
+/// `eventfd(initval, flags)`—Creates a new `eventfd` object.
+///
+/// Prefer [`Eventfd::new`] for a safe, typed wrapper around the raw
+/// counter semantics.
+#[inline]
+pub fn eventfd(initval: u32, flags: EventfdFlags) -> io::Result<OwnedFd> {
+    syscalls::eventfd(initval, flags)
+}
+
+/// The mode an [`Eventfd`] counts in, mirroring whether `EFD_SEMAPHORE` was
+/// passed at creation time.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum EventfdMode {
+    /// Plain counter mode: a read returns the entire accumulated count (and
+    /// resets it to zero), and a write adds to the count.
+    Counter,
+    /// Semaphore mode (`EFD_SEMAPHORE`): a read returns `1` and decrements
+    /// the count by one, so each unit written must be consumed by its own
+    /// read; this is useful for waking exactly one waiter per event.
+    Semaphore,
+}
+
+/// A safe, typed wrapper around an `eventfd` object.
+///
+/// An `Eventfd` holds a 64-bit counter in the kernel; writing adds to the
+/// counter (waking anyone blocked reading or polling for readability), and
+/// reading consumes it, either all at once ([`EventfdMode::Counter`]) or one
+/// unit at a time ([`EventfdMode::Semaphore`]).
+pub struct Eventfd {
+    fd: OwnedFd,
+    mode: EventfdMode,
+}
+
+impl Eventfd {
+    /// Create a new `Eventfd` with the given initial value and mode.
+    ///
+    /// The kernel takes a 32-bit initial value, so an `initval` above
+    /// `u32::MAX` is clamped to `u32::MAX`.
+    ///
+    /// `nonblocking` selects whether [`Eventfd::read`] and [`Eventfd::write`]
+    /// return [`io::Errno::AGAIN`] instead of blocking when the operation
+    /// cannot complete immediately (an empty counter for reads, or an
+    /// about-to-overflow counter for writes).
+    pub fn new(initval: u64, mode: EventfdMode, nonblocking: bool) -> io::Result<Self> {
+        let mut flags = EventfdFlags::CLOEXEC;
+        if mode == EventfdMode::Semaphore {
+            flags |= EventfdFlags::SEMAPHORE;
+        }
+        if nonblocking {
+            flags |= EventfdFlags::NONBLOCK;
+        }
+        // The kernel ABI takes a 32-bit initial value.
+        let initval = u32::try_from(initval).unwrap_or(u32::MAX);
+        Ok(Self { fd: eventfd(initval, flags)?, mode })
+    }
+
+    /// The mode this `Eventfd` was created with.
+    pub fn mode(&self) -> EventfdMode {
+        self.mode
+    }
+
+    /// Read the counter.
+    ///
+    /// In [`EventfdMode::Counter`] mode this returns the full accumulated
+    /// value and resets the counter to zero. In [`EventfdMode::Semaphore`]
+    /// mode this always returns `1` and decrements the counter by one.
+    /// Blocks (or returns [`io::Errno::AGAIN`] if non-blocking) while the
+    /// counter is zero.
+    pub fn read(&self) -> io::Result<u64> {
+        let mut buf = [0u8; 8];
+        crate::io::read(&self.fd, &mut buf)?;
+        Ok(u64::from_ne_bytes(buf))
+    }
+
+    /// Add `value` to the counter, waking any waiters.
+    ///
+    /// Blocks (or returns [`io::Errno::AGAIN`] if non-blocking) if the
+    /// addition would make the counter exceed `u64::MAX - 1`.
+    pub fn write(&self, value: u64) -> io::Result<()> {
+        crate::io::write(&self.fd, &value.to_ne_bytes())?;
+        Ok(())
+    }
+}
+
+impl AsFd for Eventfd {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
+
+impl From<Eventfd> for OwnedFd {
+    fn from(value: Eventfd) -> Self {
+        value.fd
+    }
+}
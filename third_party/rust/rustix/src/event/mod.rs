@@ -1,4 +1,10 @@
 //! Event operations.
+//!
+//! `poll` is supported on every platform this crate targets, and `pause` on
+//! all of them except Windows, Redox, and WASI. That includes the
+//! PlayStation Vita (`armv7-sony-vita-newlibeabihf`), whose
+//! newlib-based libc has no `epoll`, `kqueue`, or event ports, but does
+//! provide `select`.
 
 #[cfg(any(linux_kernel, solarish, target_os = "redox"))]
 pub mod epoll;
@@ -14,9 +20,11 @@ pub mod kqueue;
 #[cfg(not(any(windows, target_os = "redox", target_os = "wasi")))]
 mod pause;
 mod poll;
+#[cfg(all(feature = "alloc", not(any(windows, target_os = "redox", target_os = "wasi"))))]
+pub mod poller;
 #[cfg(solarish)]
 pub mod port;
-#[cfg(any(bsd, linux_kernel, windows, target_os = "wasi"))]
+#[cfg(any(bsd, linux_kernel, windows, target_os = "vita", target_os = "wasi"))]
 mod select;
 
 #[cfg(any(
@@ -25,9 +33,9 @@ mod select;
     target_os = "illumos",
     target_os = "espidf"
 ))]
-pub use eventfd::{eventfd, EventfdFlags};
+pub use eventfd::{eventfd, Eventfd, EventfdFlags, EventfdMode};
 #[cfg(not(any(windows, target_os = "redox", target_os = "wasi")))]
 pub use pause::*;
 pub use poll::{poll, PollFd, PollFlags};
-#[cfg(any(bsd, linux_kernel, windows, target_os = "wasi"))]
+#[cfg(any(bsd, linux_kernel, windows, target_os = "vita", target_os = "wasi"))]
 pub use select::*;
@@ -0,0 +1,162 @@
+//! The `poll` function.
+//!
+//! # Safety
+//!
+//! `PollFd` wraps a raw file descriptor.
+#![allow(unsafe_code)]
+
+use crate::backend::c;
+use crate::backend::event::syscalls;
+use crate::fd::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use crate::io;
+use core::time::Duration;
+
+bitflags::bitflags! {
+    /// `POLL*` flags for use with [`poll`].
+    #[repr(transparent)]
+    #[derive(Default)]
+    pub struct PollFlags: u16 {
+        /// `POLLIN`
+        const IN = c::POLLIN as u16;
+        /// `POLLPRI`
+        const PRI = c::POLLPRI as u16;
+        /// `POLLOUT`
+        const OUT = c::POLLOUT as u16;
+        /// `POLLRDNORM`
+        const RDNORM = c::POLLRDNORM as u16;
+        /// `POLLWRNORM`
+        const WRNORM = c::POLLWRNORM as u16;
+        /// `POLLRDBAND`
+        const RDBAND = c::POLLRDBAND as u16;
+        /// `POLLWRBAND`
+        const WRBAND = c::POLLWRBAND as u16;
+        /// `POLLERR`
+        const ERR = c::POLLERR as u16;
+        /// `POLLHUP`
+        const HUP = c::POLLHUP as u16;
+        /// `POLLNVAL`
+        const NVAL = c::POLLNVAL as u16;
+        /// `POLLRDHUP`
+        #[cfg(linux_kernel)]
+        const RDHUP = c::POLLRDHUP as u16;
+    }
+}
+
+impl PollFlags {
+    /// True if this set of readiness flags indicates the file descriptor is
+    /// readable, including out-of-band/priority data.
+    #[inline]
+    pub const fn is_readable(self) -> bool {
+        self.intersects(Self::IN.union(Self::PRI).union(Self::RDNORM).union(Self::RDBAND))
+    }
+
+    /// True if this set of readiness flags indicates the file descriptor is
+    /// writable.
+    #[inline]
+    pub const fn is_writable(self) -> bool {
+        self.intersects(Self::OUT.union(Self::WRNORM).union(Self::WRBAND))
+    }
+
+    /// True if `POLLERR` is set.
+    ///
+    /// Note that on Linux, `POLLHUP` can also be reported for sockets that
+    /// have not experienced an actual error; use [`PollFlags::is_hangup`]
+    /// to test for that case separately.
+    #[inline]
+    pub const fn is_error(self) -> bool {
+        self.intersects(Self::ERR)
+    }
+
+    /// True if `POLLHUP` (or, on Linux, `POLLRDHUP`) is set, meaning the
+    /// peer has hung up, e.g. closed its end of the connection.
+    #[inline]
+    pub const fn is_hangup(self) -> bool {
+        #[cfg(linux_kernel)]
+        {
+            self.intersects(Self::HUP.union(Self::RDHUP))
+        }
+        #[cfg(not(linux_kernel))]
+        {
+            self.intersects(Self::HUP)
+        }
+    }
+
+    /// True if `POLLPRI` or `POLLRDBAND` is set, meaning urgent/priority
+    /// data is available to read.
+    #[inline]
+    pub const fn is_priority(self) -> bool {
+        self.intersects(Self::PRI.union(Self::RDBAND))
+    }
+
+    /// True if `POLLNVAL` is set, meaning the file descriptor was not open.
+    #[inline]
+    pub const fn is_invalid(self) -> bool {
+        self.intersects(Self::NVAL)
+    }
+
+    /// True if any of the error-like bits (`POLLERR`, `POLLHUP`, `POLLNVAL`,
+    /// and on Linux `POLLRDHUP`) are set.
+    ///
+    /// This only inspects `revents`; a `poll` call itself failing with
+    /// `EINTR` is reported through its `Result`, not here. It's exactly
+    /// [`is_error`](Self::is_error) | [`is_hangup`](Self::is_hangup) |
+    /// [`is_invalid`](Self::is_invalid).
+    #[inline]
+    pub const fn is_error_or_hangup(self) -> bool {
+        self.is_error() || self.is_hangup() || self.is_invalid()
+    }
+}
+
+/// `struct pollfd`—A descriptor and flags for use with [`poll`].
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+#[doc(alias = "pollfd")]
+pub struct PollFd<'fd> {
+    pollfd: c::pollfd,
+    _phantom: core::marker::PhantomData<BorrowedFd<'fd>>,
+}
+
+impl<'fd> PollFd<'fd> {
+    /// Constructs a new `PollFd` holding `fd` and `events`.
+    #[inline]
+    pub fn new<Fd: AsFd>(fd: &'fd Fd, events: PollFlags) -> Self {
+        Self::from_borrowed_fd(fd.as_fd(), events)
+    }
+
+    /// Constructs a new `PollFd` holding `fd` and `events`, from a
+    /// `BorrowedFd`.
+    #[inline]
+    pub fn from_borrowed_fd(fd: BorrowedFd<'fd>, events: PollFlags) -> Self {
+        Self {
+            pollfd: c::pollfd { fd: fd.as_raw_fd() as _, events: events.bits() as _, revents: 0 },
+            _phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns the ready events.
+    #[inline]
+    pub fn revents(&self) -> PollFlags {
+        // `from_bits_truncate` since the OS may set flags we don't know
+        // about.
+        PollFlags::from_bits_truncate(self.pollfd.revents as _)
+    }
+
+    /// Clears the ready events.
+    #[inline]
+    pub fn clear_revents(&mut self) {
+        self.pollfd.revents = 0;
+    }
+}
+
+impl<'fd> AsRawFd for PollFd<'fd> {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.pollfd.fd as _
+    }
+}
+
+/// `poll(self.as_ptr(), self.len(), timeout)`
+#[inline]
+pub fn poll(fds: &mut [PollFd<'_>], timeout: Option<Duration>) -> io::Result<usize> {
+    syscalls::poll(fds, timeout)
+}
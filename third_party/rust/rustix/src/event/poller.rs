@@ -0,0 +1,177 @@
+//! A portable readiness-polling abstraction.
+//!
+//! [`Poller`] unifies the platform-specific readiness-notification facilities
+//! exposed elsewhere in [`event`] (`epoll` on Linux, `kqueue` on the BSDs and
+//! macOS, event ports on illumos/Solaris, and a `poll`-based fallback
+//! everywhere else) behind a single small API, so that callers don't need to
+//! reimplement the backend fan-out themselves.
+//!
+//! [`event`]: crate::event
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rustix::event::poller::{Event, PollMode, Poller};
+//! use rustix::net::{AddressFamily, SocketType};
+//! use std::time::Duration;
+//!
+//! let poller = Poller::new()?;
+//! let socket = rustix::net::socket(AddressFamily::INET, SocketType::STREAM, None)?;
+//!
+//! // Register interest in readability, with a single one-shot delivery.
+//! unsafe {
+//!     poller.add(&socket, Event::readable(1), PollMode::Oneshot)?;
+//! }
+//!
+//! let mut events = Vec::new();
+//! poller.wait(&mut events, Some(Duration::from_secs(1)))?;
+//! for event in &events {
+//!     println!("key {} readable={} writable={}", event.key, event.readable, event.writable);
+//! }
+//! # Ok::<(), rustix::io::Errno>(())
+//! ```
+
+use crate::fd::{AsFd, BorrowedFd, OwnedFd};
+use crate::io;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+#[cfg(linux_kernel)]
+mod epoll_backend;
+#[cfg(bsd)]
+mod kqueue_backend;
+#[cfg(solarish)]
+mod port_backend;
+#[cfg(not(any(linux_kernel, bsd, solarish)))]
+mod poll_backend;
+
+#[cfg(linux_kernel)]
+use epoll_backend as sys;
+#[cfg(bsd)]
+use kqueue_backend as sys;
+#[cfg(solarish)]
+use port_backend as sys;
+#[cfg(not(any(linux_kernel, bsd, solarish)))]
+use poll_backend as sys;
+
+mod wakeup;
+
+/// A readiness event returned from [`Poller::wait`].
+///
+/// The `key` is whatever value was passed to [`Poller::add`] or
+/// [`Poller::modify`] for the file descriptor that became ready.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Event {
+    /// The user-chosen key identifying the registration.
+    pub key: u64,
+    /// Whether the file descriptor is readable.
+    pub readable: bool,
+    /// Whether the file descriptor is writable.
+    pub writable: bool,
+    /// Whether the file descriptor hung up (e.g. the peer closed the
+    /// connection). Note that on Linux, `EPOLLHUP` can be reported for a
+    /// socket even when no error has occurred, so this should not be
+    /// confused with [`Event::error`].
+    pub hangup: bool,
+    /// Whether an error condition is pending on the file descriptor.
+    pub error: bool,
+}
+
+impl Event {
+    /// Construct an `Event` requesting readability with the given key.
+    pub fn readable(key: u64) -> Self {
+        Self { key, readable: true, writable: false, hangup: false, error: false }
+    }
+
+    /// Construct an `Event` requesting writability with the given key.
+    pub fn writable(key: u64) -> Self {
+        Self { key, readable: false, writable: true, hangup: false, error: false }
+    }
+
+    /// Construct an `Event` requesting neither readability nor writability;
+    /// useful as a placeholder for [`Poller::delete`]-adjacent bookkeeping.
+    pub fn none(key: u64) -> Self {
+        Self { key, readable: false, writable: false, hangup: false, error: false }
+    }
+}
+
+/// How long an interest registration stays active after it fires.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PollMode {
+    /// The interest is automatically disabled after the first event is
+    /// delivered for it (`EPOLLONESHOT` / `EV_ONESHOT` / a one-shot port
+    /// association). The poll-based fallback emulates this by removing the
+    /// file descriptor from the interest set once it fires.
+    Oneshot,
+    /// The interest stays active and keeps firing as long as the condition
+    /// holds (level-triggered).
+    ///
+    /// On the event-ports backend (illumos/Solaris) every association is
+    /// removed once it fires, so a `Level` registration there is re-armed
+    /// only by calling [`Poller::modify`] again.
+    Level,
+    /// The interest stays active but only fires on a state transition
+    /// (`EPOLLET` / `EV_CLEAR`, edge-triggered).
+    ///
+    /// On the event-ports backend this behaves like `Level`: the association
+    /// is removed once it fires and is re-armed by [`Poller::modify`].
+    Edge,
+}
+
+/// A portable handle for polling the readiness of a set of file descriptors.
+///
+/// See the [module-level documentation](self) for an example.
+pub struct Poller {
+    sys: sys::Poller,
+    wakeup: wakeup::Wakeup,
+}
+
+impl Poller {
+    /// Create a new, empty `Poller`.
+    pub fn new() -> io::Result<Self> {
+        Ok(Self { sys: sys::Poller::new()?, wakeup: wakeup::Wakeup::new()? })
+    }
+
+    /// Begin watching `fd` for the readiness described by `event`, with the
+    /// given [`PollMode`].
+    ///
+    /// # Safety
+    ///
+    /// `fd` must not be dropped or otherwise closed while it remains
+    /// registered with this `Poller`; doing so may cause another file
+    /// descriptor with the same numeric value to be watched instead.
+    pub unsafe fn add<Fd: AsFd>(&self, fd: Fd, event: Event, mode: PollMode) -> io::Result<()> {
+        self.sys.add(fd.as_fd(), event, mode)
+    }
+
+    /// Change the event and mode associated with a file descriptor that was
+    /// previously passed to [`Poller::add`].
+    pub fn modify<Fd: AsFd>(&self, fd: Fd, event: Event, mode: PollMode) -> io::Result<()> {
+        self.sys.modify(fd.as_fd(), event, mode)
+    }
+
+    /// Stop watching `fd`.
+    pub fn delete<Fd: AsFd>(&self, fd: Fd) -> io::Result<()> {
+        self.sys.delete(fd.as_fd())
+    }
+
+    /// Block until at least one watched file descriptor is ready, the
+    /// timeout elapses, or [`Poller::notify`] is called from another thread,
+    /// appending any ready events to `events`.
+    ///
+    /// Returns the number of events appended.
+    pub fn wait(&self, events: &mut Vec<Event>, timeout: Option<Duration>) -> io::Result<usize> {
+        self.sys.wait(events, timeout, self.wakeup.fd())
+    }
+
+    /// Interrupt a concurrent call to [`Poller::wait`] on another thread.
+    pub fn notify(&self) -> io::Result<()> {
+        self.wakeup.notify()
+    }
+}
+
+impl core::fmt::Debug for Poller {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Poller").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,97 @@
+//! The `epoll`-based backend for [`super::Poller`], used on Linux.
+
+use super::{Event, PollMode};
+use crate::event::epoll;
+use crate::fd::{BorrowedFd, OwnedFd};
+use crate::io;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+pub(super) struct Poller {
+    epoll: OwnedFd,
+}
+
+impl Poller {
+    pub(super) fn new() -> io::Result<Self> {
+        Ok(Self { epoll: epoll::create(epoll::CreateFlags::CLOEXEC)? })
+    }
+
+    pub(super) fn add(&self, fd: BorrowedFd<'_>, event: Event, mode: PollMode) -> io::Result<()> {
+        epoll::add(&self.epoll, fd, epoll_data(event), epoll_flags(event, mode))
+    }
+
+    pub(super) fn modify(
+        &self,
+        fd: BorrowedFd<'_>,
+        event: Event,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        epoll::modify(&self.epoll, fd, epoll_data(event), epoll_flags(event, mode))
+    }
+
+    pub(super) fn delete(&self, fd: BorrowedFd<'_>) -> io::Result<()> {
+        epoll::delete(&self.epoll, fd)
+    }
+
+    pub(super) fn wait(
+        &self,
+        events: &mut Vec<Event>,
+        timeout: Option<Duration>,
+        wakeup: BorrowedFd<'_>,
+    ) -> io::Result<usize> {
+        // Make sure a concurrent `notify()` can wake us even if it wasn't
+        // registered yet.
+        let _ = epoll::add(
+            &self.epoll,
+            wakeup,
+            epoll::EventData::new_u64(u64::MAX),
+            epoll::EventFlags::IN,
+        );
+
+        let mut epoll_events = epoll::EventVec::with_capacity(events.capacity().max(32));
+        epoll::wait(&self.epoll, &mut epoll_events, timeout)?;
+
+        let mut count = 0;
+        for epoll_event in epoll_events.iter() {
+            let key = epoll_event.data.u64();
+            if key == u64::MAX {
+                // This was just the cross-thread wakeup notification; drain
+                // it so it doesn't stay readable forever (it's registered
+                // level-triggered, since more than one `notify()` may have
+                // queued up while we weren't waiting).
+                let _ = super::wakeup::drain(wakeup);
+                continue;
+            }
+            let flags = epoll_event.flags;
+            events.push(Event {
+                key,
+                readable: flags.intersects(epoll::EventFlags::IN),
+                writable: flags.intersects(epoll::EventFlags::OUT),
+                hangup: flags.intersects(epoll::EventFlags::HUP | epoll::EventFlags::RDHUP),
+                error: flags.intersects(epoll::EventFlags::ERR),
+            });
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+fn epoll_data(event: Event) -> epoll::EventData {
+    epoll::EventData::new_u64(event.key)
+}
+
+fn epoll_flags(event: Event, mode: PollMode) -> epoll::EventFlags {
+    let mut flags = epoll::EventFlags::empty();
+    if event.readable {
+        flags |= epoll::EventFlags::IN;
+    }
+    if event.writable {
+        flags |= epoll::EventFlags::OUT;
+    }
+    match mode {
+        PollMode::Oneshot => flags |= epoll::EventFlags::ONESHOT,
+        PollMode::Level => {}
+        PollMode::Edge => flags |= epoll::EventFlags::ET,
+    }
+    flags
+}
@@ -0,0 +1,130 @@
+//! The `kqueue`-based backend for [`super::Poller`], used on the BSDs and
+//! macOS.
+
+use super::{Event, PollMode};
+use crate::event::kqueue;
+use crate::fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd};
+use crate::io;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+pub(super) struct Poller {
+    kq: OwnedFd,
+}
+
+impl Poller {
+    pub(super) fn new() -> io::Result<Self> {
+        Ok(Self { kq: kqueue::kqueue()? })
+    }
+
+    pub(super) fn add(&self, fd: BorrowedFd<'_>, event: Event, mode: PollMode) -> io::Result<()> {
+        self.submit(fd, event, mode, kqueue::EventFlags::ADD | kqueue::EventFlags::RECEIPT)
+    }
+
+    pub(super) fn modify(
+        &self,
+        fd: BorrowedFd<'_>,
+        event: Event,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        // `kqueue` has no in-place modify; drop both filters, then re-add
+        // the ones the new event names, so the new registration replaces the
+        // prior one.
+        self.delete(fd)?;
+        self.submit(fd, event, mode, kqueue::EventFlags::ADD | kqueue::EventFlags::RECEIPT)
+    }
+
+    pub(super) fn delete(&self, fd: BorrowedFd<'_>) -> io::Result<()> {
+        let raw = fd.as_fd();
+        let changes = [
+            kqueue::Event::new(
+                kqueue::EventFilter::Read(raw.as_raw_fd()),
+                kqueue::EventFlags::DELETE | kqueue::EventFlags::RECEIPT,
+                0,
+            ),
+            kqueue::Event::new(
+                kqueue::EventFilter::Write(raw.as_raw_fd()),
+                kqueue::EventFlags::DELETE | kqueue::EventFlags::RECEIPT,
+                0,
+            ),
+        ];
+        let mut out = Vec::new();
+        // Ignore `ENOENT`: the filter may not have been registered.
+        let _ = unsafe { kqueue::kevent(&self.kq, &changes, &mut out, None) };
+        Ok(())
+    }
+
+    pub(super) fn wait(
+        &self,
+        events: &mut Vec<Event>,
+        timeout: Option<Duration>,
+        wakeup: BorrowedFd<'_>,
+    ) -> io::Result<usize> {
+        let _ = self.submit(
+            wakeup,
+            Event { key: u64::MAX, readable: true, writable: false, hangup: false, error: false },
+            PollMode::Level,
+            kqueue::EventFlags::ADD | kqueue::EventFlags::RECEIPT,
+        );
+
+        let mut kevents = Vec::new();
+        unsafe { kqueue::kevent(&self.kq, &[], &mut kevents, timeout)? };
+
+        let mut count = 0;
+        for kevent in &kevents {
+            let key = kevent.udata() as u64;
+            if key == u64::MAX {
+                // This was just the cross-thread wakeup notification; drain
+                // it so it doesn't stay readable forever (it's registered
+                // level-triggered, since more than one `notify()` may have
+                // queued up while we weren't waiting).
+                let _ = super::wakeup::drain(wakeup);
+                continue;
+            }
+            let flags = kevent.flags();
+            events.push(Event {
+                key,
+                readable: matches!(kevent.filter(), kqueue::EventFilter::Read(_)),
+                writable: matches!(kevent.filter(), kqueue::EventFilter::Write(_)),
+                hangup: flags.intersects(kqueue::EventFlags::EOF),
+                error: flags.intersects(kqueue::EventFlags::ERROR),
+            });
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    fn submit(
+        &self,
+        fd: BorrowedFd<'_>,
+        event: Event,
+        mode: PollMode,
+        base_flags: kqueue::EventFlags,
+    ) -> io::Result<()> {
+        let mut flags = base_flags;
+        if mode == PollMode::Oneshot {
+            flags |= kqueue::EventFlags::ONESHOT;
+        } else if mode == PollMode::Edge {
+            flags |= kqueue::EventFlags::CLEAR;
+        }
+
+        let raw = fd.as_raw_fd();
+        let mut changes = Vec::with_capacity(2);
+        if event.readable {
+            changes.push(kqueue::Event::new(
+                kqueue::EventFilter::Read(raw),
+                flags,
+                event.key as _,
+            ));
+        }
+        if event.writable {
+            changes.push(kqueue::Event::new(
+                kqueue::EventFilter::Write(raw),
+                flags,
+                event.key as _,
+            ));
+        }
+        let mut out = Vec::new();
+        unsafe { kqueue::kevent(&self.kq, &changes, &mut out, None) }.map(|_| ())
+    }
+}
@@ -0,0 +1,119 @@
+//! The `poll`-based fallback backend for [`super::Poller`], used on
+//! platforms without a native readiness-notification facility.
+//!
+//! `poll` has no native one-shot mode, so `Oneshot` registrations are
+//! emulated here by removing the file descriptor from the interest set
+//! after it reports an event.
+
+use super::{Event, PollMode};
+use crate::event::{poll, PollFd, PollFlags};
+use crate::fd::{AsRawFd, BorrowedFd, OwnedFd, RawFd};
+use crate::io;
+use alloc::vec::Vec;
+use core::time::Duration;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+struct Registration {
+    key: u64,
+    flags: PollFlags,
+    mode: PollMode,
+}
+
+pub(super) struct Poller {
+    registrations: Mutex<HashMap<RawFd, Registration>>,
+}
+
+impl Poller {
+    pub(super) fn new() -> io::Result<Self> {
+        Ok(Self { registrations: Mutex::new(HashMap::new()) })
+    }
+
+    pub(super) fn add(&self, fd: BorrowedFd<'_>, event: Event, mode: PollMode) -> io::Result<()> {
+        self.registrations.lock().unwrap().insert(
+            fd.as_raw_fd(),
+            Registration { key: event.key, flags: to_poll_flags(event), mode },
+        );
+        Ok(())
+    }
+
+    pub(super) fn modify(
+        &self,
+        fd: BorrowedFd<'_>,
+        event: Event,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        self.add(fd, event, mode)
+    }
+
+    pub(super) fn delete(&self, fd: BorrowedFd<'_>) -> io::Result<()> {
+        self.registrations.lock().unwrap().remove(&fd.as_raw_fd());
+        Ok(())
+    }
+
+    pub(super) fn wait(
+        &self,
+        events: &mut Vec<Event>,
+        timeout: Option<Duration>,
+        wakeup: BorrowedFd<'_>,
+    ) -> io::Result<usize> {
+        let mut registrations = self.registrations.lock().unwrap();
+
+        let mut pollfds = Vec::with_capacity(registrations.len() + 1);
+        pollfds.push(PollFd::from_borrowed_fd(wakeup, PollFlags::IN));
+        let fds: Vec<RawFd> = registrations.keys().copied().collect();
+        for &raw in &fds {
+            // SAFETY: `raw` is kept alive by the caller for as long as it is
+            // registered with this `Poller`, per `Poller::add`'s contract.
+            let borrowed = unsafe { BorrowedFd::borrow_raw(raw) };
+            let flags = registrations[&raw].flags;
+            pollfds.push(PollFd::from_borrowed_fd(borrowed, flags));
+        }
+
+        poll(&mut pollfds, timeout)?;
+
+        if !pollfds[0].revents().is_empty() {
+            // This was just the cross-thread wakeup notification; drain it
+            // so it doesn't stay readable forever (it's registered
+            // level-triggered, since more than one `notify()` may have
+            // queued up while we weren't waiting).
+            let _ = super::wakeup::drain(wakeup);
+        }
+
+        let mut count = 0;
+        let mut to_remove = Vec::new();
+        for (raw, pollfd) in fds.iter().zip(pollfds.iter().skip(1)) {
+            let revents = pollfd.revents();
+            if revents.is_empty() {
+                continue;
+            }
+            let registration = &registrations[raw];
+            events.push(Event {
+                key: registration.key,
+                readable: revents.intersects(PollFlags::IN),
+                writable: revents.intersects(PollFlags::OUT),
+                hangup: revents.intersects(PollFlags::HUP),
+                error: revents.intersects(PollFlags::ERR | PollFlags::NVAL),
+            });
+            count += 1;
+            if registration.mode == PollMode::Oneshot {
+                to_remove.push(*raw);
+            }
+        }
+        for raw in to_remove {
+            registrations.remove(&raw);
+        }
+        Ok(count)
+    }
+}
+
+fn to_poll_flags(event: Event) -> PollFlags {
+    let mut flags = PollFlags::empty();
+    if event.readable {
+        flags |= PollFlags::IN;
+    }
+    if event.writable {
+        flags |= PollFlags::OUT;
+    }
+    flags
+}
@@ -0,0 +1,89 @@
+//! A cross-thread handle used to interrupt a blocked [`super::Poller::wait`].
+//!
+//! Backed by `eventfd` where available (Linux, FreeBSD), and by a
+//! self-pipe otherwise.
+
+use crate::fd::{AsFd, BorrowedFd};
+use crate::io;
+
+#[cfg(any(linux_kernel, target_os = "freebsd"))]
+pub(super) struct Wakeup {
+    fd: crate::event::Eventfd,
+}
+
+#[cfg(any(linux_kernel, target_os = "freebsd"))]
+impl Wakeup {
+    pub(super) fn new() -> io::Result<Self> {
+        use crate::event::EventfdMode;
+        Ok(Self { fd: crate::event::Eventfd::new(0, EventfdMode::Counter, true)? })
+    }
+
+    pub(super) fn fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+
+    pub(super) fn notify(&self) -> io::Result<()> {
+        // A blocked `wait` treats the fd becoming readable as the wakeup
+        // signal; the actual count doesn't matter.
+        let _ = self.fd.write(1);
+        Ok(())
+    }
+}
+
+/// Drain the wakeup fd after a `wait` observes it readable, so the
+/// underlying eventfd/self-pipe doesn't stay readable forever and make
+/// every subsequent `wait` return immediately.
+#[cfg(any(linux_kernel, target_os = "freebsd"))]
+pub(super) fn drain(fd: BorrowedFd<'_>) -> io::Result<()> {
+    // `Eventfd::read` reads through an owned `Eventfd`, but all `wait` has
+    // is the borrowed fd it was registered with; read the 8-byte counter
+    // directly, the same as `Eventfd::read` does.
+    let mut buf = [0u8; 8];
+    match crate::io::read(fd, &mut buf) {
+        Ok(_) => Ok(()),
+        Err(io::Errno::AGAIN) => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Drain the wakeup fd after a `wait` observes it readable, so the
+/// underlying eventfd/self-pipe doesn't stay readable forever and make
+/// every subsequent `wait` return immediately.
+#[cfg(not(any(linux_kernel, target_os = "freebsd")))]
+pub(super) fn drain(fd: BorrowedFd<'_>) -> io::Result<()> {
+    let mut buf = [0u8; 64];
+    loop {
+        match crate::io::read(fd, &mut buf) {
+            Ok(0) => return Ok(()),
+            Ok(n) if n < buf.len() => return Ok(()),
+            Ok(_) => continue,
+            Err(io::Errno::AGAIN) => return Ok(()),
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+#[cfg(not(any(linux_kernel, target_os = "freebsd")))]
+pub(super) struct Wakeup {
+    read: crate::fd::OwnedFd,
+    write: crate::fd::OwnedFd,
+}
+
+#[cfg(not(any(linux_kernel, target_os = "freebsd")))]
+impl Wakeup {
+    pub(super) fn new() -> io::Result<Self> {
+        let (read, write) = crate::pipe::pipe_with(
+            crate::pipe::PipeFlags::CLOEXEC | crate::pipe::PipeFlags::NONBLOCK,
+        )?;
+        Ok(Self { read, write })
+    }
+
+    pub(super) fn fd(&self) -> BorrowedFd<'_> {
+        self.read.as_fd()
+    }
+
+    pub(super) fn notify(&self) -> io::Result<()> {
+        let _ = crate::io::write(&self.write, &[1u8]);
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+//! The Linux `memfd_secret` syscall.
+//!
+//! # References
+//!  - [Linux]
+//!
+//! [Linux]: https://man7.org/linux/man-pages/man2/memfd_secret.2.html
+
+use crate::backend::mm::syscalls;
+use crate::fd::OwnedFd;
+use crate::io;
+
+bitflags::bitflags! {
+    /// Flags for [`memfd_secret`].
+    #[repr(transparent)]
+    #[derive(Default)]
+    pub struct MemfdSecretFlags: u32 {
+        /// `FD_SECRET_EXCLUSIVE`—Fail `mmap` if the region would ever be
+        /// shared with another process (e.g. across `fork`).
+        const EXCLUSIVE = linux_raw_sys::memfd_secret::FD_SECRET_EXCLUSIVE;
+    }
+}
+
+/// `memfd_secret(flags)`—Creates an anonymous file descriptor backed by
+/// memory that is removed from the kernel's direct map, so its contents are
+/// never visible to the kernel itself (e.g. in a crash dump) or to other
+/// processes.
+///
+/// The returned file descriptor must be sized with [`crate::fs::ftruncate`]
+/// and then mapped with [`crate::mm::mmap`] before use; like a regular
+/// `memfd`, it has no directory entry and is freed when the last reference
+/// is dropped.
+#[inline]
+pub fn memfd_secret(flags: MemfdSecretFlags) -> io::Result<OwnedFd> {
+    syscalls::memfd_secret(flags)
+}
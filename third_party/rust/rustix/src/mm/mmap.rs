@@ -0,0 +1,69 @@
+//! `mmap` flags.
+
+use crate::backend::c;
+
+bitflags::bitflags! {
+    /// `MAP_*` flags for use with `mmap`.
+    #[repr(transparent)]
+    #[derive(Default)]
+    pub struct MapFlags: c::c_uint {
+        /// `MAP_SHARED`
+        const SHARED = c::MAP_SHARED as c::c_uint;
+        /// `MAP_SHARED_VALIDATE`
+        #[cfg(linux_kernel)]
+        const SHARED_VALIDATE = c::MAP_SHARED_VALIDATE as c::c_uint;
+        /// `MAP_PRIVATE`
+        const PRIVATE = c::MAP_PRIVATE as c::c_uint;
+        /// `MAP_DENYWRITE`
+        #[cfg(linux_kernel)]
+        const DENYWRITE = c::MAP_DENYWRITE as c::c_uint;
+        /// `MAP_FIXED`
+        const FIXED = c::MAP_FIXED as c::c_uint;
+        /// `MAP_FIXED_NOREPLACE`
+        #[cfg(linux_kernel)]
+        const FIXED_NOREPLACE = c::MAP_FIXED_NOREPLACE as c::c_uint;
+        /// `MAP_GROWSDOWN`
+        #[cfg(linux_kernel)]
+        const GROWSDOWN = c::MAP_GROWSDOWN as c::c_uint;
+        /// `MAP_HUGETLB`
+        #[cfg(linux_kernel)]
+        const HUGETLB = c::MAP_HUGETLB as c::c_uint;
+        /// `MAP_LOCKED`
+        #[cfg(linux_kernel)]
+        const LOCKED = c::MAP_LOCKED as c::c_uint;
+        /// `MAP_NORESERVE`
+        #[cfg(linux_kernel)]
+        const NORESERVE = c::MAP_NORESERVE as c::c_uint;
+        /// `MAP_POPULATE`
+        #[cfg(linux_kernel)]
+        const POPULATE = c::MAP_POPULATE as c::c_uint;
+        /// `MAP_STACK`—Hints that the mapping will be used as a thread
+        /// stack, so the kernel can apply stack-specific placement and
+        /// guard-gap behavior (mainly relevant to architectures where
+        /// stacks grow in a particular direction, such as avoiding
+        /// allocating them adjacent to other mappings without a gap).
+        #[cfg(linux_kernel)]
+        const STACK = c::MAP_STACK as c::c_uint;
+        /// `MAP_SYNC`
+        #[cfg(linux_kernel)]
+        const SYNC = c::MAP_SYNC as c::c_uint;
+        /// `MAP_NONBLOCK`
+        #[cfg(linux_kernel)]
+        const NONBLOCK = c::MAP_NONBLOCK as c::c_uint;
+        /// `MAP_EXECUTABLE`
+        #[cfg(linux_kernel)]
+        const EXECUTABLE = c::MAP_EXECUTABLE as c::c_uint;
+        /// `MAP_DROPPABLE`—Lets the kernel silently revert pages of this
+        /// mapping to zero under memory pressure instead of invoking the
+        /// OOM killer, which suits large reclaimable caches (decoded-image
+        /// or tile caches, say) that can tolerate losing their contents.
+        ///
+        /// Linux requires a droppable mapping to also be
+        /// [`MapFlags::empty`]-style anonymous (no [`MapFlags::SHARED`])
+        /// and rejects combining it with [`MapFlags::SHARED`] or locking it
+        /// with `mlock`; passing such a combination to `mmap` fails with
+        /// `EINVAL`. Added in Linux 6.11.
+        #[cfg(linux_kernel)]
+        const DROPPABLE = c::MAP_DROPPABLE as c::c_uint;
+    }
+}
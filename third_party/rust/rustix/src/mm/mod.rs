@@ -0,0 +1,13 @@
+//! Memory map operations.
+
+#[cfg(linux_kernel)]
+mod memfd_secret;
+mod mmap;
+#[cfg(linux_kernel)]
+mod mseal;
+
+#[cfg(linux_kernel)]
+pub use memfd_secret::{memfd_secret, MemfdSecretFlags};
+pub use mmap::MapFlags;
+#[cfg(linux_kernel)]
+pub use mseal::mseal;
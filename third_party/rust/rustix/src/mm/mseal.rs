@@ -0,0 +1,31 @@
+//! The Linux `mseal` syscall.
+//!
+//! # References
+//!  - [Linux]
+//!
+//! [Linux]: https://man7.org/linux/man-pages/man2/mseal.2.html
+
+use crate::backend::mm::syscalls;
+use crate::io;
+use core::ffi::c_void;
+
+/// `mseal(addr, len, 0)`—Seals a mapping so that future `mprotect`,
+/// `munmap`, `mmap` (at the same address), and similar operations against
+/// it fail.
+///
+/// `addr` must be page-aligned and `len` is rounded up to a multiple of the
+/// page size by the kernel; the sealed range covers the VMAs spanning
+/// `[addr, addr + len)`, all of which must already be mapped. Sealing is
+/// permanent for the lifetime of the mapping—there is no corresponding
+/// "unseal".
+///
+/// # Safety
+///
+/// `addr` and `len` must describe a region that is currently mapped in this
+/// process, and the caller is asserting that no remaining code path needs to
+/// `munmap`, `mremap`, `mprotect`, or replace the mapping for as long as the
+/// process runs.
+#[inline]
+pub unsafe fn mseal(addr: *mut c_void, len: usize) -> io::Result<()> {
+    syscalls::mseal(addr, len)
+}
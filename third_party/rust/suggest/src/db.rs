@@ -3,7 +3,15 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
-use std::{cell::OnceCell, path::Path, sync::Arc};
+use std::{
+    cell::{OnceCell, RefCell},
+    collections::HashSet,
+    hash::{Hash, Hasher},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
 
 use interrupt_support::{SqlInterruptHandle, SqlInterruptScope};
 use parking_lot::{Mutex, MutexGuard};
@@ -11,8 +19,9 @@ use remote_settings::RemoteSettingsResponse;
 use rusqlite::{
     named_params,
     types::{FromSql, ToSql},
-    Connection, OpenFlags, OptionalExtension,
+    Connection, DatabaseName, OpenFlags, OptionalExtension,
 };
+use serde::{Deserialize, Serialize};
 use sql_support::{open_database::open_database_with_flags, repeat_sql_vars, ConnExt};
 
 use crate::{
@@ -21,12 +30,13 @@ use crate::{
     fakespot,
     geoname::GeonameCache,
     pocket::{split_keyword, KeywordConfidence},
-    provider::{AmpMatchingStrategy, SuggestionProvider},
+    provider::{AmpMatchingStrategy, SuggestionProvider, WikipediaMatchingStrategy},
     query::{full_keywords_to_fts_content, FtsQuery},
     rs::{
         DownloadedAmoSuggestion, DownloadedAmpSuggestion, DownloadedAmpWikipediaSuggestion,
         DownloadedExposureSuggestion, DownloadedFakespotSuggestion, DownloadedMdnSuggestion,
-        DownloadedPocketSuggestion, DownloadedWikipediaSuggestion, Record, SuggestRecordId,
+        DownloadedPocketSuggestion, DownloadedSynonyms, DownloadedWikipediaSuggestion, Record,
+        SuggestRecordId,
     },
     schema::{clear_database, SuggestConnectionInitializer},
     suggestion::{cook_raw_suggestion_url, AmpSuggestionType, FtsMatchInfo, Suggestion},
@@ -46,6 +56,150 @@ pub const PROVIDER_CONFIG_META_KEY_PREFIX: &str = "provider_config_";
 // Default value when Suggestion does not have a value for score
 pub const DEFAULT_SUGGESTION_SCORE: f64 = 0.2;
 
+/// Metadata key whose value is a JSON array of the stopwords stripped from the AMP
+/// `full_keywords` FTS column at ingest. Stored in the DB (rather than recomputed from a constant
+/// at each call site) so that every ingest uses the same list even across upgrades that change
+/// the default. FTS match expressions built at query time are not stripped.
+pub const STOPWORDS_META_KEY: &str = "fts_stopwords";
+
+/// Suffix appended to the AMP provider's config meta key (see [provider_config_meta_key]) under
+/// which [AmpFtsWeights] is stored, separately from the main `SuggestProviderConfig` blob so the
+/// weights can be tuned without a schema or config-shape change.
+const AMP_FTS_WEIGHTS_META_KEY_SUFFIX: &str = "_amp_fts_weights";
+
+/// BM25 column weights used to rank `amp_fts` matches, in the order its columns were declared
+/// (`full_keywords`, then `title`). A higher weight makes a match in that column count for more
+/// in the combined score. Defaults to weighting `title` above `full_keywords`, since an exact
+/// title hit is usually the stronger relevance signal, while still letting an embedder retune
+/// this via [SuggestDao::set_amp_fts_weights] without waiting on a schema change.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AmpFtsWeights {
+    pub full_keywords: f64,
+    pub title: f64,
+}
+
+impl Default for AmpFtsWeights {
+    fn default() -> Self {
+        Self {
+            full_keywords: 1.0,
+            title: 2.0,
+        }
+    }
+}
+
+/// Suffix appended to the AMP provider's config meta key (see [provider_config_meta_key]) under
+/// which [AmpTrigramConfig] is stored, for the same reason [AMP_FTS_WEIGHTS_META_KEY_SUFFIX] is
+/// kept alongside rather than inside the main `SuggestProviderConfig` blob.
+const AMP_TRIGRAM_CONFIG_META_KEY_SUFFIX: &str = "_amp_trigram_config";
+
+/// Tuning knobs for the typo-tolerant trigram fallback used by
+/// [SuggestDao::fetch_amp_suggestions_using_trigrams] when an exact keyword lookup comes back
+/// empty.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AmpTrigramConfig {
+    /// Minimum number of trigrams a candidate must share with the query before it's considered
+    /// at all. Filters out weak candidates before the more expensive Jaccard comparison.
+    pub min_shared_trigrams: u32,
+    /// Minimum Jaccard similarity (|shared trigrams| / |union|) a candidate must reach, after
+    /// the `min_shared_trigrams` prefilter, to be returned as a match.
+    pub min_similarity: f64,
+}
+
+impl Default for AmpTrigramConfig {
+    fn default() -> Self {
+        Self {
+            min_shared_trigrams: 2,
+            min_similarity: 0.5,
+        }
+    }
+}
+
+/// The default English stopword set used for FTS indexing until a caller overrides it
+/// with [SuggestDao::set_stopwords].
+const DEFAULT_STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "has", "in", "is", "it", "of",
+    "on", "or", "that", "the", "to", "was", "with",
+];
+
+/// A single labeled timing sample, in microseconds.
+#[derive(Debug, Clone)]
+pub struct MetricsSample {
+    pub label: String,
+    pub elapsed_us: u64,
+}
+
+/// Timing samples collected by [SuggestDao] while answering a single `read()`
+/// call, so embedders can compare the cost of different matching strategies
+/// (e.g. keyword vs. FTS) without instrumenting every call site themselves.
+#[derive(Debug, Clone, Default)]
+pub struct SuggestQueryMetrics {
+    pub samples: Vec<MetricsSample>,
+}
+
+/// Timing samples collected by [SuggestDao] during ingestion. Only
+/// `insert_amp_wikipedia_suggestions` records a sample, covering the write of
+/// its whole batch; download and parse times are not measured.
+#[derive(Debug, Clone, Default)]
+pub struct SuggestIngestionMetrics {
+    pub samples: Vec<MetricsSample>,
+}
+
+/// A query result bundled with the query metrics gathered while producing it.
+#[derive(Debug, Clone)]
+pub struct QueryWithMetricsResult<T> {
+    pub result: T,
+    pub query_metrics: SuggestQueryMetrics,
+}
+
+/// Number of suggestion rows stored for a single provider, part of [SuggestDbStats].
+#[derive(Debug, Clone)]
+pub struct ProviderSuggestionCount {
+    pub provider: SuggestionProvider,
+    pub suggestion_count: u64,
+}
+
+/// Number of indexed rows in one of the `*_fts` virtual tables, part of [SuggestDbStats].
+#[derive(Debug, Clone)]
+pub struct FtsIndexSize {
+    pub table: &'static str,
+    pub row_count: u64,
+}
+
+/// The newest `last_modified` timestamp ingested for one collection, part of [SuggestDbStats].
+#[derive(Debug, Clone)]
+pub struct CollectionLastModified {
+    pub collection: String,
+    pub last_modified: u64,
+}
+
+/// A structured snapshot of how much of the Suggest database each provider and table is
+/// consuming, for diagnostics like [SuggestDao::db_stats] without hand-writing SQL against
+/// internal tables.
+#[derive(Debug, Clone)]
+pub struct SuggestDbStats {
+    pub suggestion_counts_by_provider: Vec<ProviderSuggestionCount>,
+    pub keyword_row_count: u64,
+    pub prefix_keyword_row_count: u64,
+    pub full_keyword_row_count: u64,
+    pub icon_count: u64,
+    pub icon_total_bytes: u64,
+    pub fts_index_sizes: Vec<FtsIndexSize>,
+    pub newest_last_modified_by_collection: Vec<CollectionLastModified>,
+}
+
+/// A simple wall-clock stopwatch used to time a query or ingestion step.
+struct DownloadTimer(Instant);
+
+impl DownloadTimer {
+    fn start() -> Self {
+        Self(Instant::now())
+    }
+
+    fn elapsed_us(&self) -> u64 {
+        u64::try_from(self.0.elapsed().as_micros()).unwrap_or(u64::MAX)
+    }
+}
+
 /// The database connection type.
 #[derive(Clone, Copy)]
 pub(crate) enum ConnectionType {
@@ -82,12 +236,25 @@ pub struct Sqlite3Extension {
 pub(crate) struct SuggestDb {
     pub conn: Mutex<Connection>,
 
+    /// A small pool of read-only connections, checked out by [Self::read] so
+    /// that concurrent queries run in parallel instead of serializing behind
+    /// `conn`'s lock. SQLite read-only connections don't contend with each
+    /// other or with the read-write connection, which matters when callers
+    /// issue a query per keystroke.
+    readers: ReaderPool,
+
     /// An object that's used to interrupt an ongoing database operation.
     ///
     /// When this handle is interrupted, the thread that's currently accessing
-    /// the database will be told to stop and release the `conn` lock as soon
-    /// as possible.
-    pub interrupt_handle: Arc<SqlInterruptHandle>,
+    /// the database will be told to stop and release the `conn` lock, or
+    /// whichever pooled reader it's using, as soon as possible.
+    pub interrupt_handle: Arc<SuggestDbInterruptHandle>,
+
+    /// The name of the FTS5 tokenizer registered by a loaded extension, if
+    /// one was passed to [Self::open]. It is handed to every `SuggestDao` and
+    /// exposed by `SuggestDao::fts_tokenizer_name`; no `CREATE VIRTUAL TABLE`
+    /// statement in this file reads it.
+    fts_tokenizer_name: Option<Arc<str>>,
 }
 
 impl SuggestDb {
@@ -99,35 +266,65 @@ impl SuggestDb {
         type_: ConnectionType,
     ) -> Result<Self> {
         let conn = open_database_with_flags(
-            path,
+            &path,
             type_.into(),
             &SuggestConnectionInitializer::new(extensions_to_load),
         )?;
-        Ok(Self::with_connection(conn))
+        Ok(Self::with_connection(conn, path, extensions_to_load))
     }
 
-    fn with_connection(conn: Connection) -> Self {
-        let interrupt_handle = Arc::new(SqlInterruptHandle::new(&conn));
+    fn with_connection(
+        conn: Connection,
+        path: impl AsRef<Path>,
+        extensions_to_load: &[Sqlite3Extension],
+    ) -> Self {
+        let interrupt_handle = Arc::new(SuggestDbInterruptHandle::new(Arc::new(
+            SqlInterruptHandle::new(&conn),
+        )));
         Self {
             conn: Mutex::new(conn),
+            readers: ReaderPool::new(path, extensions_to_load),
             interrupt_handle,
+            fts_tokenizer_name: fts_tokenizer_name_for_extensions(extensions_to_load),
         }
     }
 
     /// Accesses the Suggest database for reading.
+    ///
+    /// This checks out a read-only connection from the reader pool rather
+    /// than locking `conn`, so it can run concurrently with other `read()`
+    /// calls (and with an in-progress `write()`).
     pub fn read<T>(&self, op: impl FnOnce(&SuggestDao) -> Result<T>) -> Result<T> {
-        let conn = self.conn.lock();
-        let scope = self.interrupt_handle.begin_interrupt_scope()?;
-        let dao = SuggestDao::new(&conn, &scope);
-        op(&dao)
+        let reader = self.readers.checkout(&self.interrupt_handle)?;
+        let scope = reader.interrupt_handle.begin_interrupt_scope()?;
+        let dao = SuggestDao::new(&reader.conn, &scope, self.fts_tokenizer_name.clone());
+        let result = op(&dao);
+        self.readers.checkin(reader);
+        result
+    }
+
+    /// Like [Self::read], but also returns the query metrics collected while
+    /// producing the result, so embedders can compare matching strategies
+    /// (e.g. keyword vs. FTS) without instrumenting every call site.
+    pub fn read_with_metrics<T>(
+        &self,
+        op: impl FnOnce(&SuggestDao) -> Result<T>,
+    ) -> Result<QueryWithMetricsResult<T>> {
+        self.read(|dao| {
+            let result = op(dao)?;
+            Ok(QueryWithMetricsResult {
+                result,
+                query_metrics: dao.query_metrics(),
+            })
+        })
     }
 
     /// Accesses the Suggest database in a transaction for reading and writing.
     pub fn write<T>(&self, op: impl FnOnce(&mut SuggestDao) -> Result<T>) -> Result<T> {
         let mut conn = self.conn.lock();
-        let scope = self.interrupt_handle.begin_interrupt_scope()?;
+        let scope = self.interrupt_handle.writer.begin_interrupt_scope()?;
         let tx = conn.transaction()?;
-        let mut dao = SuggestDao::new(&tx, &scope);
+        let mut dao = SuggestDao::new(&tx, &scope, self.fts_tokenizer_name.clone());
         let result = op(&mut dao)?;
         tx.commit()?;
         Ok(result)
@@ -143,21 +340,121 @@ impl SuggestDb {
     pub fn write_scope(&self) -> Result<WriteScope> {
         Ok(WriteScope {
             conn: self.conn.lock(),
-            scope: self.interrupt_handle.begin_interrupt_scope()?,
+            scope: self.interrupt_handle.writer.begin_interrupt_scope()?,
+            fts_tokenizer_name: self.fts_tokenizer_name.clone(),
         })
     }
 }
 
+/// Derives the FTS5 tokenizer name an embedder's loaded extension registered, if any.
+///
+/// By convention, an extension intended to supply a custom tokenizer registers it under a name
+/// matching its own library file stem (e.g. `fuzzy_tokenizer.so` registers `fuzzy_tokenizer`;
+/// a `lib` prefix is kept, so `libfuzzy_tokenizer.so` yields `libfuzzy_tokenizer`). Only the
+/// first extension in the list is consulted.
+fn fts_tokenizer_name_for_extensions(extensions: &[Sqlite3Extension]) -> Option<Arc<str>> {
+    extensions.first().map(|ext| {
+        Path::new(&ext.library)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| ext.library.clone())
+            .into()
+    })
+}
+
+/// Fans interruption out to the read-write connection's handle and every
+/// read-only connection currently checked out of (or idle in) the reader
+/// pool, so a single `interrupt()` call aborts whichever connections have an
+/// operation in flight.
+pub(crate) struct SuggestDbInterruptHandle {
+    writer: Arc<SqlInterruptHandle>,
+    readers: Mutex<Vec<Arc<SqlInterruptHandle>>>,
+}
+
+impl SuggestDbInterruptHandle {
+    fn new(writer: Arc<SqlInterruptHandle>) -> Self {
+        Self {
+            writer,
+            readers: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn register_reader(&self, handle: Arc<SqlInterruptHandle>) {
+        self.readers.lock().push(handle);
+    }
+
+    /// Interrupts the read-write connection and every pooled read-only
+    /// connection.
+    pub fn interrupt(&self) {
+        self.writer.interrupt();
+        for handle in self.readers.lock().iter() {
+            handle.interrupt();
+        }
+    }
+}
+
+/// A read-only connection checked out of a [ReaderPool], along with the
+/// interrupt handle that was registered for it when it was first opened.
+struct PooledReader {
+    conn: Connection,
+    interrupt_handle: Arc<SqlInterruptHandle>,
+}
+
+/// A small pool of read-only connections to the Suggest database. New
+/// connections are opened lazily, on demand, and kept around for reuse once
+/// checked back in, so steady-state concurrent reading doesn't pay the cost
+/// of opening a connection per query.
+struct ReaderPool {
+    path: PathBuf,
+    extensions_to_load: Vec<Sqlite3Extension>,
+    idle: Mutex<Vec<PooledReader>>,
+}
+
+impl ReaderPool {
+    fn new(path: impl AsRef<Path>, extensions_to_load: &[Sqlite3Extension]) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            extensions_to_load: extensions_to_load.to_vec(),
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Checks out an idle connection, opening a new one if the pool is
+    /// currently empty.
+    fn checkout(&self, interrupt_handle: &SuggestDbInterruptHandle) -> Result<PooledReader> {
+        if let Some(reader) = self.idle.lock().pop() {
+            return Ok(reader);
+        }
+        let conn = open_database_with_flags(
+            &self.path,
+            ConnectionType::ReadOnly.into(),
+            &SuggestConnectionInitializer::new(&self.extensions_to_load),
+        )?;
+        let handle = Arc::new(SqlInterruptHandle::new(&conn));
+        interrupt_handle.register_reader(handle.clone());
+        Ok(PooledReader {
+            conn,
+            interrupt_handle: handle,
+        })
+    }
+
+    /// Returns a connection to the pool for reuse.
+    fn checkin(&self, reader: PooledReader) {
+        self.idle.lock().push(reader);
+    }
+}
+
 pub(crate) struct WriteScope<'a> {
     pub conn: MutexGuard<'a, Connection>,
     pub scope: SqlInterruptScope,
+    fts_tokenizer_name: Option<Arc<str>>,
 }
 
 impl WriteScope<'_> {
     /// Accesses the Suggest database in a transaction for reading and writing.
     pub fn write<T>(&mut self, op: impl FnOnce(&mut SuggestDao) -> Result<T>) -> Result<T> {
         let tx = self.conn.transaction()?;
-        let mut dao = SuggestDao::new(&tx, &self.scope);
+        let mut dao = SuggestDao::new(&tx, &self.scope, self.fts_tokenizer_name.clone());
         let result = op(&mut dao)?;
         tx.commit()?;
         Ok(result)
@@ -166,7 +463,7 @@ impl WriteScope<'_> {
     /// Accesses the Suggest database in a transaction for reading only
     pub fn read<T>(&mut self, op: impl FnOnce(&SuggestDao) -> Result<T>) -> Result<T> {
         let tx = self.conn.transaction()?;
-        let dao = SuggestDao::new(&tx, &self.scope);
+        let dao = SuggestDao::new(&tx, &self.scope, self.fts_tokenizer_name.clone());
         let result = op(&dao)?;
         tx.commit()?;
         Ok(result)
@@ -183,21 +480,123 @@ impl WriteScope<'_> {
 /// Methods that only read from the database take an immutable reference to
 /// `self` (`&self`), and methods that write to the database take a mutable
 /// reference (`&mut self`).
+/// The dismissed-suggestion identifiers memoized by [SuggestDao::is_dismissed]: the exact URLs
+/// recorded via `insert_dismissal`, and the stable keys recorded via
+/// [SuggestDao::insert_dismissal_by_key] (see its doc comment for why a URL alone isn't always
+/// enough).
+#[derive(Default)]
+struct DismissedSet {
+    urls: HashSet<String>,
+    keys: HashSet<String>,
+}
+
 pub(crate) struct SuggestDao<'a> {
     pub conn: &'a Connection,
     pub scope: &'a SqlInterruptScope,
     pub weather_cache: OnceCell<WeatherCache>,
     pub geoname_cache: OnceCell<GeonameCache>,
+    /// Memoized dismissed-suggestion URLs and keys (see [DismissedSet]), loaded at most once per
+    /// `SuggestDao` (i.e. at most once per [SuggestDb::read]/[SuggestDb::write] call) and shared
+    /// by every provider fetch in that call via [Self::is_dismissed], instead of each one
+    /// re-running its own `NOT EXISTS (SELECT 1 FROM dismissed_suggestions ...)` correlated
+    /// subquery per candidate row. A fresh dao (and thus a fresh cache) is built for every call,
+    /// so this is always current at the *start* of a call; it's a `RefCell` rather than a
+    /// `OnceCell` so that `insert_dismissal`/`insert_dismissal_by_key`/`clear_dismissals` can
+    /// invalidate it if a dismissal is recorded and then immediately queried for within that same
+    /// call.
+    dismissed: RefCell<Option<DismissedSet>>,
+    pub query_metrics: RefCell<SuggestQueryMetrics>,
+    pub ingestion_metrics: SuggestIngestionMetrics,
+    fts_tokenizer_name: Option<Arc<str>>,
 }
 
 impl<'a> SuggestDao<'a> {
-    fn new(conn: &'a Connection, scope: &'a SqlInterruptScope) -> Self {
+    fn new(
+        conn: &'a Connection,
+        scope: &'a SqlInterruptScope,
+        fts_tokenizer_name: Option<Arc<str>>,
+    ) -> Self {
         Self {
             conn,
             scope,
             weather_cache: std::cell::OnceCell::new(),
             geoname_cache: std::cell::OnceCell::new(),
+            dismissed: RefCell::new(None),
+            query_metrics: RefCell::new(SuggestQueryMetrics::default()),
+            ingestion_metrics: SuggestIngestionMetrics::default(),
+            fts_tokenizer_name,
+        }
+    }
+
+    /// The name of the FTS5 tokenizer an embedder's loaded extension registered, for use in
+    /// `CREATE VIRTUAL TABLE ... tokenize=...` when (re)creating the `*_fts` tables. `None` means
+    /// the built-in tokenizer should be used.
+    pub fn fts_tokenizer_name(&self) -> Option<&str> {
+        self.fts_tokenizer_name.as_deref()
+    }
+
+    /// Returns whether `url` (or, if provided, `key`) has been dismissed, loading the full set
+    /// of dismissed suggestion URLs/keys from `dismissed_suggestions` the first time it's needed
+    /// and reusing that result for the rest of this dao's call. See the doc comment on the
+    /// `dismissed` field for why this is safe to cache.
+    fn is_dismissed(&self, url: &str, key: Option<&str>) -> Result<bool> {
+        if self.dismissed.borrow().is_none() {
+            let rows: Vec<(String, Option<String>)> = self.conn.query_rows_and_then_cached(
+                "SELECT url, key FROM dismissed_suggestions",
+                (),
+                |row| -> rusqlite::Result<_> { Ok((row.get(0)?, row.get(1)?)) },
+            )?;
+            let mut set = DismissedSet::default();
+            for (url, key) in rows {
+                if !url.is_empty() {
+                    set.urls.insert(url);
+                }
+                if let Some(key) = key {
+                    set.keys.insert(key);
+                }
+            }
+            *self.dismissed.borrow_mut() = Some(set);
         }
+        let dismissed = self.dismissed.borrow();
+        let set = dismissed.as_ref().expect("just populated above");
+        Ok(set.urls.contains(url) || key.is_some_and(|key| set.keys.contains(key)))
+    }
+
+    /// Drops the memoized [Self::is_dismissed] set so the next call to it re-reads
+    /// `dismissed_suggestions`. Needed because `insert_dismissal`/`insert_dismissal_by_key`/
+    /// `clear_dismissals` take `&self` and so could run after the cache has already been
+    /// populated within the same call.
+    fn invalidate_dismissed(&self) {
+        self.dismissed.borrow_mut().take();
+    }
+
+    /// Times `op`, recording a labeled sample in [Self::query_metrics].
+    fn measure_query<T>(&self, label: impl Into<String>, op: impl FnOnce() -> T) -> T {
+        let timer = DownloadTimer::start();
+        let result = op();
+        self.query_metrics.borrow_mut().samples.push(MetricsSample {
+            label: label.into(),
+            elapsed_us: timer.elapsed_us(),
+        });
+        result
+    }
+
+    /// Records a labeled ingestion timing sample.
+    fn record_ingestion_sample(&mut self, label: impl Into<String>, timer: DownloadTimer) {
+        self.ingestion_metrics.samples.push(MetricsSample {
+            label: label.into(),
+            elapsed_us: timer.elapsed_us(),
+        });
+    }
+
+    /// Returns a snapshot of the query timing samples collected so far.
+    pub fn query_metrics(&self) -> SuggestQueryMetrics {
+        self.query_metrics.borrow().clone()
+    }
+
+    /// Returns a snapshot of the ingestion timing samples collected so far.
+    pub fn ingestion_metrics(&self) -> SuggestIngestionMetrics {
+        self.ingestion_metrics.clone()
     }
 
     // =============== High level API ===============
@@ -271,6 +670,84 @@ impl<'a> SuggestDao<'a> {
         rows.collect()
     }
 
+    /// Returns a snapshot of how much of the database each provider and table is consuming, so
+    /// an embedder can diagnose bloat or stale collections at runtime without hand-writing SQL
+    /// against internal tables. Backs a `debug_db_stats`-style command analogous to
+    /// `debug_ingestion_sizes`.
+    pub fn db_stats(&self) -> Result<SuggestDbStats> {
+        let suggestion_counts_by_provider = self
+            .conn
+            .prepare_cached("SELECT provider, COUNT(*) FROM suggestions GROUP BY provider")?
+            .query_and_then((), |row| -> Result<ProviderSuggestionCount> {
+                Ok(ProviderSuggestionCount {
+                    provider: row.get(0)?,
+                    suggestion_count: row.get(1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        let keyword_row_count = self
+            .conn
+            .query_row_and_then("SELECT COUNT(*) FROM keywords", (), |row| row.get(0))?;
+        let prefix_keyword_row_count = self.conn.query_row_and_then(
+            "SELECT COUNT(*) FROM prefix_keywords",
+            (),
+            |row| row.get(0),
+        )?;
+        let full_keyword_row_count = self.conn.query_row_and_then(
+            "SELECT COUNT(*) FROM full_keywords",
+            (),
+            |row| row.get(0),
+        )?;
+        let (icon_count, icon_total_bytes) = self.conn.query_row_and_then(
+            "SELECT COUNT(*), COALESCE(SUM(LENGTH(data)), 0) FROM icons",
+            (),
+            |row| -> Result<(u64, u64)> { Ok((row.get(0)?, row.get(1)?)) },
+        )?;
+
+        let fts_index_sizes = [
+            "amp_fts",
+            "wikipedia_fts",
+            "fakespot_fts",
+            "mdn_fts",
+            "pocket_fts",
+        ]
+        .into_iter()
+        .map(|table| -> Result<FtsIndexSize> {
+            let row_count = self.conn.query_row_and_then(
+                &format!("SELECT COUNT(*) FROM {table}"),
+                (),
+                |row| row.get(0),
+            )?;
+            Ok(FtsIndexSize { table, row_count })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+        let newest_last_modified_by_collection = self
+            .conn
+            .prepare_cached(
+                "SELECT collection, MAX(last_modified) FROM ingested_records GROUP BY collection",
+            )?
+            .query_and_then((), |row| -> Result<CollectionLastModified> {
+                Ok(CollectionLastModified {
+                    collection: row.get(0)?,
+                    last_modified: row.get(1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SuggestDbStats {
+            suggestion_counts_by_provider,
+            keyword_row_count,
+            prefix_keyword_row_count,
+            full_keyword_row_count,
+            icon_count,
+            icon_total_bytes,
+            fts_index_sizes,
+            newest_last_modified_by_collection,
+        })
+    }
+
     pub fn update_ingested_records(
         &mut self,
         collection: &str,
@@ -310,6 +787,65 @@ impl<'a> SuggestDao<'a> {
         Ok(())
     }
 
+    /// Records that a record was downloaded but couldn't be ingested, either because its
+    /// attachment failed to parse or because its `record_type` wasn't recognized by this version
+    /// of the component.
+    ///
+    /// We remember the schema version we were built against so that a later version, which knows
+    /// how to handle the record, can pick it back up via
+    /// [Self::reingest_unparsable_records_for_version] without forcing a full re-download of the
+    /// collection.
+    pub fn mark_unparsable_record(
+        &mut self,
+        record_id: &SuggestRecordId,
+        collection: &str,
+        schema_version: u32,
+    ) -> Result<()> {
+        self.conn.execute_cached(
+            "INSERT OR REPLACE INTO unparsable_records(id, collection, schema_version)
+             VALUES(:id, :collection, :schema_version)",
+            named_params! {
+                ":id": record_id.as_str(),
+                ":collection": collection,
+                ":schema_version": schema_version,
+            },
+        )?;
+        Ok(())
+    }
+
+    pub fn get_unparsable_records(&self) -> Result<Vec<UnparsableRecord>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT id, collection, schema_version FROM unparsable_records")?;
+        let rows = stmt.query_and_then((), UnparsableRecord::from_row)?;
+        rows.collect()
+    }
+
+    /// Resets the stored `last_modified` to 1 for any unparsable record that was recorded under an older schema
+    /// version than `current_version`, so the next ingestion re-downloads and re-attempts it.
+    ///
+    /// Records that are still unparsable under the current version are left alone: re-ingesting
+    /// them on every run would just waste bandwidth without making progress.
+    pub fn reingest_unparsable_records_for_version(&mut self, current_version: u32) -> Result<()> {
+        self.conn.execute_cached(
+            "UPDATE ingested_records
+             SET last_modified = 1
+             WHERE id IN (
+                 SELECT id FROM unparsable_records WHERE schema_version < :current_version
+             )",
+            named_params! {
+                ":current_version": current_version,
+            },
+        )?;
+        self.conn.execute_cached(
+            "DELETE FROM unparsable_records WHERE schema_version < :current_version",
+            named_params! {
+                ":current_version": current_version,
+            },
+        )?;
+        Ok(())
+    }
+
     pub fn suggestions_table_empty(&self) -> Result<bool> {
         Ok(self
             .conn
@@ -356,7 +892,11 @@ impl<'a> SuggestDao<'a> {
         } else {
             "AND INSTR(CONCAT(fk.full_keyword, ' '), k.keyword) != 0"
         };
-        let suggestions = self.conn.query_rows_and_then_cached(
+        let label = match suggestion_type {
+            AmpSuggestionType::Mobile => "fetch_amp_suggestions_using_keywords:amp_mobile",
+            AmpSuggestionType::Desktop => "fetch_amp_suggestions_using_keywords:amp",
+        };
+        let suggestions = self.measure_query(label, || self.conn.query_rows_and_then_cached(
             &format!(
                 r#"
                 SELECT
@@ -379,17 +919,19 @@ impl<'a> SuggestDao<'a> {
                   s.provider = :provider
                   AND k.keyword = :keyword
                   {where_extra}
-                AND NOT EXISTS (SELECT 1 FROM dismissed_suggestions WHERE url=s.url)
                 "#
             ),
             named_params! {
                 ":keyword": keyword_lowercased,
                 ":provider": provider
             },
-            |row| -> Result<Suggestion> {
+            |row| -> Result<Option<Suggestion>> {
                 let suggestion_id: i64 = row.get("id")?;
-                let title = row.get("title")?;
+                let title: String = row.get("title")?;
                 let raw_url: String = row.get("url")?;
+                if self.is_dismissed(&raw_url, Some(&dismissal_key(&raw_url, &title)))? {
+                    return Ok(None);
+                }
                 let score: f64 = row.get("score")?;
                 let full_keyword_from_db: Option<String> = row.get("full_keyword")?;
 
@@ -436,7 +978,7 @@ impl<'a> SuggestDao<'a> {
                         let raw_click_url = row.get::<_, String>("click_url")?;
                         let cooked_click_url = cook_raw_suggestion_url(&raw_click_url);
 
-                        Ok(Suggestion::Amp {
+                        Ok(Some(Suggestion::Amp {
                             block_id: row.get("block_id")?,
                             advertiser: row.get("advertiser")?,
                             iab_category: row.get("iab_category")?,
@@ -452,12 +994,165 @@ impl<'a> SuggestDao<'a> {
                             raw_click_url,
                             score,
                             fts_match_info: None,
-                        })
+                        }))
                     },
                 )
             },
+        ))?;
+        let suggestions: Vec<Suggestion> = suggestions.into_iter().flatten().collect();
+        if suggestions.is_empty() {
+            // The user's query didn't exactly match any stored keyword. Before giving up, check
+            // for a likely typo via the trigram index, so a mistyped prefix like "amazn" can
+            // still surface the suggestion a correctly-typed "amazon" would have.
+            self.fetch_amp_suggestions_using_trigrams(query, suggestion_type)
+        } else {
+            Ok(suggestions)
+        }
+    }
+
+    /// Typo-tolerant fallback for [Self::fetch_amp_suggestions_using_keywords]: finds AMP
+    /// suggestions whose keywords share enough 3-character trigrams with the query to plausibly
+    /// be what the user meant, ranked by Jaccard similarity against the query's trigram set.
+    ///
+    /// Only called once the exact-match fast path above has already come back empty, so normal,
+    /// correctly-typed queries never pay for this.
+    pub fn fetch_amp_suggestions_using_trigrams(
+        &self,
+        query: &SuggestionQuery,
+        suggestion_type: AmpSuggestionType,
+    ) -> Result<Vec<Suggestion>> {
+        let provider = match suggestion_type {
+            AmpSuggestionType::Mobile => SuggestionProvider::AmpMobile,
+            AmpSuggestionType::Desktop => SuggestionProvider::Amp,
+        };
+        let config = self.get_amp_trigram_config()?;
+        let query_trigrams = keyword_trigrams(&query.keyword);
+        if query_trigrams.len() < config.min_shared_trigrams as usize {
+            return Ok(Vec::new());
+        }
+
+        let label = "fetch_amp_suggestions_using_trigrams";
+        let candidate_ids: Vec<i64> = self.measure_query(label, || {
+            let params = rusqlite::params_from_iter(
+                std::iter::once(&provider as &dyn ToSql)
+                    .chain(query_trigrams.iter().map(|t| t as &dyn ToSql)),
+            );
+            self.conn.query_rows_and_then_cached(
+                &format!(
+                    r#"
+                    SELECT
+                      t.suggestion_id
+                    FROM
+                      amp_keyword_trigrams t
+                    JOIN
+                      suggestions s ON s.id = t.suggestion_id
+                    WHERE
+                      s.provider = ?
+                      AND t.trigram IN ({})
+                    GROUP BY
+                      t.suggestion_id
+                    HAVING
+                      COUNT(DISTINCT t.trigram) >= {}
+                    "#,
+                    repeat_sql_vars(query_trigrams.len()),
+                    config.min_shared_trigrams,
+                ),
+                params,
+                |row| row.get(0),
+            )
+        })?;
+
+        let mut scored = Vec::new();
+        for suggestion_id in candidate_ids {
+            let candidate_trigrams: HashSet<String> = self.conn.query_rows_and_then_cached(
+                "SELECT DISTINCT trigram FROM amp_keyword_trigrams WHERE suggestion_id = ?",
+                (suggestion_id,),
+                |row| row.get(0),
+            )?;
+            let similarity = trigram_jaccard_similarity(&query_trigrams, &candidate_trigrams);
+            if similarity >= config.min_similarity {
+                scored.push((suggestion_id, similarity));
+            }
+        }
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        scored
+            .into_iter()
+            .filter_map(|(suggestion_id, _similarity)| {
+                self.fetch_amp_suggestion_by_id(suggestion_id, query).transpose()
+            })
+            .collect()
+    }
+
+    /// Fetches a single AMP suggestion by its `suggestions.id`, for callers like
+    /// [Self::fetch_amp_suggestions_using_trigrams] that identify candidates out-of-band and just
+    /// need to materialize them into a [Suggestion]. Returns `None` if the suggestion no longer
+    /// exists or its URL has been dismissed.
+    fn fetch_amp_suggestion_by_id(
+        &self,
+        suggestion_id: i64,
+        query: &SuggestionQuery,
+    ) -> Result<Option<Suggestion>> {
+        let suggestion = self.conn.try_query_row(
+            r#"
+            SELECT
+              s.title,
+              s.url,
+              s.score,
+              amp.advertiser,
+              amp.block_id,
+              amp.iab_category,
+              amp.impression_url,
+              amp.click_url,
+              i.data AS icon,
+              i.mimetype AS icon_mimetype
+            FROM
+              suggestions s
+            JOIN
+              amp_custom_details amp ON amp.suggestion_id = s.id
+            LEFT JOIN
+              icons i ON amp.icon_id = i.id
+            WHERE
+              s.id = :suggestion_id
+            "#,
+            named_params! { ":suggestion_id": suggestion_id },
+            |row| -> Result<Suggestion> {
+                let title: String = row.get("title")?;
+                let raw_url: String = row.get("url")?;
+                let score: f64 = row.get("score")?;
+                let cooked_url = cook_raw_suggestion_url(&raw_url);
+                let raw_click_url = row.get::<_, String>("click_url")?;
+                let cooked_click_url = cook_raw_suggestion_url(&raw_click_url);
+                Ok(Suggestion::Amp {
+                    block_id: row.get("block_id")?,
+                    advertiser: row.get("advertiser")?,
+                    iab_category: row.get("iab_category")?,
+                    title,
+                    url: cooked_url,
+                    raw_url,
+                    full_keyword: query.keyword.clone(),
+                    icon: row.get("icon")?,
+                    icon_mimetype: row.get("icon_mimetype")?,
+                    impression_url: row.get("impression_url")?,
+                    click_url: cooked_click_url,
+                    raw_click_url,
+                    score,
+                    fts_match_info: None,
+                })
+            },
+            true,
         )?;
-        Ok(suggestions)
+        // A URL-based `NOT EXISTS` filter in SQL can't see a dismissal keyed on the
+        // volatile-URL-resistant `dismissal_key` below, so check dismissal here
+        // instead, same as the other AMP fetch paths.
+        match suggestion {
+            Some(Suggestion::Amp { ref raw_url, ref title, .. })
+                if self.is_dismissed(raw_url, Some(&dismissal_key(raw_url, title)))? =>
+            {
+                Ok(None)
+            }
+            other => Ok(other),
+        }
     }
 
     pub fn fetch_amp_suggestions_using_fts(
@@ -472,7 +1167,9 @@ impl<'a> SuggestDao<'a> {
             AmpSuggestionType::Mobile => SuggestionProvider::AmpMobile,
             AmpSuggestionType::Desktop => SuggestionProvider::Amp,
         };
-        let suggestions = self.conn.query_rows_and_then_cached(
+        let weights = self.get_amp_fts_weights()?;
+        let label = format!("fetch_amp_suggestions_using_fts:{fts_column}");
+        let suggestions = self.measure_query(label, || self.conn.query_rows_and_then_cached(
             &format!(
                 r#"
                 SELECT
@@ -480,7 +1177,8 @@ impl<'a> SuggestDao<'a> {
                   s.title,
                   s.url,
                   s.provider,
-                  s.score
+                  s.score,
+                  bm25(amp_fts, :full_keywords_weight, :title_weight) AS bm25_score
                 FROM
                   suggestions s
                 JOIN
@@ -489,19 +1187,24 @@ impl<'a> SuggestDao<'a> {
                 WHERE
                   s.provider = :provider
                   AND amp_fts match '{fts_column}: {match_arg}'
-                AND NOT EXISTS (SELECT 1 FROM dismissed_suggestions WHERE url=s.url)
-                ORDER BY rank
-                LIMIT 1
+                ORDER BY bm25_score
+                LIMIT 5
                 "#
             ),
             named_params! {
-                ":provider": provider
+                ":provider": provider,
+                ":full_keywords_weight": weights.full_keywords,
+                ":title_weight": weights.title,
             },
-            |row| -> Result<Suggestion> {
+            |row| -> Result<Option<Suggestion>> {
                 let suggestion_id: i64 = row.get("id")?;
                 let title: String = row.get("title")?;
                 let raw_url: String = row.get("url")?;
+                if self.is_dismissed(&raw_url, Some(&dismissal_key(&raw_url, &title)))? {
+                    return Ok(None);
+                }
                 let score: f64 = row.get("score")?;
+                let bm25_score: f64 = row.get("bm25_score")?;
 
                 self.conn.query_row_and_then(
                     r#"
@@ -534,7 +1237,7 @@ impl<'a> SuggestDao<'a> {
                             &title,
                         )?;
 
-                        Ok(Suggestion::Amp {
+                        Ok(Some(Suggestion::Amp {
                             block_id: row.get("block_id")?,
                             advertiser: row.get("advertiser")?,
                             iab_category: row.get("iab_category")?,
@@ -547,14 +1250,18 @@ impl<'a> SuggestDao<'a> {
                             impression_url: row.get("impression_url")?,
                             click_url: cooked_click_url,
                             raw_click_url,
-                            score,
+                            score: score + Self::amp_fts_relevance_bonus(bm25_score),
                             fts_match_info: Some(match_info),
-                        })
+                        }))
                     },
                 )
             },
-        )?;
-        Ok(suggestions)
+        ))?;
+        // `dismissal_key` is computed in Rust, so dismissal can't be filtered in the SQL
+        // above the way a plain URL match could; widen the `LIMIT` past 1 so a dismissed
+        // top bm25 match doesn't hide the next-best candidate, then take the first
+        // survivor here instead.
+        Ok(suggestions.into_iter().flatten().take(1).collect())
     }
 
     fn fetch_amp_fts_match_info(
@@ -600,8 +1307,36 @@ impl<'a> SuggestDao<'a> {
         })
     }
 
+    /// Converts an `amp_fts` `bm25()` value (lower is a better match, and the scale is
+    /// unbounded since it is a function of corpus statistics) into a small additive score
+    /// bonus, mirroring [Self::fakespot_proximity_bonus]. Capped well below the typical spread
+    /// between distinct AMP scores, so the bm25 signal can only nudge ordering among
+    /// similarly-scored candidates rather than override the base relevance score.
+    fn amp_fts_relevance_bonus(bm25_score: f64) -> f64 {
+        const MAX_BONUS: f64 = 0.02;
+        const HALF_BONUS_POINT: f64 = 5.0;
+        let relevance = (-bm25_score).max(0.0);
+        MAX_BONUS * relevance / (relevance + HALF_BONUS_POINT)
+    }
+
     /// Fetches Suggestions of type Wikipedia provider that match the given query
     pub fn fetch_wikipedia_suggestions(&self, query: &SuggestionQuery) -> Result<Vec<Suggestion>> {
+        let strategy = query
+            .provider_constraints
+            .as_ref()
+            .and_then(|c| c.wikipedia_alternative_matching.as_ref());
+        match strategy {
+            None => self.fetch_wikipedia_suggestions_using_keywords(query),
+            Some(WikipediaMatchingStrategy::Fts) => {
+                self.fetch_wikipedia_suggestions_using_fts(query)
+            }
+        }
+    }
+
+    fn fetch_wikipedia_suggestions_using_keywords(
+        &self,
+        query: &SuggestionQuery,
+    ) -> Result<Vec<Suggestion>> {
         let keyword_lowercased = &query.keyword.to_lowercase();
         let suggestions = self.conn.query_rows_and_then_cached(
             r#"
@@ -618,16 +1353,18 @@ impl<'a> SuggestDao<'a> {
             WHERE
               s.provider = :provider
               AND k.keyword = :keyword
-              AND NOT EXISTS (SELECT 1 FROM dismissed_suggestions WHERE url=s.url)
             "#,
             named_params! {
                 ":keyword": keyword_lowercased,
                 ":provider": SuggestionProvider::Wikipedia
             },
-            |row| -> Result<Suggestion> {
+            |row| -> Result<Option<Suggestion>> {
                 let suggestion_id: i64 = row.get("id")?;
                 let title = row.get("title")?;
                 let raw_url = row.get::<_, String>("url")?;
+                if self.is_dismissed(&raw_url, None)? {
+                    return Ok(None);
+                }
 
                 let keywords: Vec<String> = self.conn.query_rows_and_then_cached(
                     "SELECT keyword FROM keywords
@@ -660,28 +1397,150 @@ impl<'a> SuggestDao<'a> {
                     )?
                     .unwrap_or((None, None));
 
-                Ok(Suggestion::Wikipedia {
+                Ok(Some(Suggestion::Wikipedia {
                     title,
                     url: raw_url,
                     full_keyword: full_keyword(keyword_lowercased, &keywords),
                     icon,
                     icon_mimetype,
-                })
+                    fts_match_info: None,
+                }))
             },
         )?;
-        Ok(suggestions)
+        Ok(suggestions.into_iter().flatten().collect())
     }
 
-    /// Query for suggestions using the keyword prefix and provider
-    fn map_prefix_keywords<T>(
+    /// Fetches Suggestions of type Wikipedia provider by matching the query
+    /// against the `wikipedia_fts` virtual table, mirroring
+    /// `fetch_amp_suggestions_using_fts`.
+    fn fetch_wikipedia_suggestions_using_fts(
         &self,
         query: &SuggestionQuery,
+    ) -> Result<Vec<Suggestion>> {
+        let fts_query = query.fts_query();
+        let suggestions = self.conn.query_rows_and_then_cached(
+            r#"
+            SELECT
+              s.id,
+              s.title,
+              s.url
+            FROM
+              suggestions s
+            JOIN
+              wikipedia_fts fts
+              ON fts.rowid = s.id
+            WHERE
+              s.provider = :provider
+              AND wikipedia_fts MATCH :match_arg
+            ORDER BY rank
+            LIMIT 5
+            "#,
+            named_params! {
+                ":provider": SuggestionProvider::Wikipedia,
+                ":match_arg": fts_query.match_arg,
+            },
+            |row| -> Result<Option<Suggestion>> {
+                let suggestion_id: i64 = row.get("id")?;
+                let title: String = row.get("title")?;
+                let raw_url: String = row.get("url")?;
+                if self.is_dismissed(&raw_url, None)? {
+                    return Ok(None);
+                }
+
+                let (icon, icon_mimetype) = self
+                    .conn
+                    .try_query_row(
+                        "SELECT i.data, i.mimetype
+                     FROM icons i
+                     JOIN wikipedia_custom_details s ON s.icon_id = i.id
+                     WHERE s.suggestion_id = :suggestion_id
+                     LIMIT 1",
+                        named_params! {
+                            ":suggestion_id": suggestion_id
+                        },
+                        |row| -> Result<_> {
+                            Ok((
+                                row.get::<_, Option<Vec<u8>>>(0)?,
+                                row.get::<_, Option<String>>(1)?,
+                            ))
+                        },
+                        true,
+                    )?
+                    .unwrap_or((None, None));
+
+                let match_info =
+                    self.fetch_wikipedia_fts_match_info(&fts_query, suggestion_id, &title)?;
+
+                Ok(Some(Suggestion::Wikipedia {
+                    title,
+                    url: raw_url,
+                    full_keyword: query.keyword.clone(),
+                    icon,
+                    icon_mimetype,
+                    fts_match_info: Some(match_info),
+                }))
+            },
+        )?;
+        // Widen the `LIMIT` past 1 so a dismissed top-ranked match doesn't hide the
+        // next-best candidate, then take the first survivor here instead of filtering
+        // dismissal in SQL (see `fetch_amp_suggestions_using_fts`).
+        Ok(suggestions.into_iter().flatten().take(1).collect())
+    }
+
+    fn fetch_wikipedia_fts_match_info(
+        &self,
+        fts_query: &FtsQuery<'_>,
+        suggestion_id: i64,
+        title: &str,
+    ) -> Result<FtsMatchInfo> {
+        let prefix = if fts_query.is_prefix_query {
+            // If the query was a prefix match query then test if the query without the prefix
+            // match would have also matched.  If not, then this counts as a prefix match.
+            let sql = "SELECT 1 FROM wikipedia_fts WHERE rowid = ? AND wikipedia_fts MATCH ?";
+            let params = (&suggestion_id, &fts_query.match_arg_without_prefix_match);
+            !self.conn.exists(sql, params)?
+        } else {
+            // If not, then it definitely wasn't a prefix match
+            false
+        };
+
+        Ok(FtsMatchInfo {
+            prefix,
+            stemming: fts_query.match_required_stemming(&title.to_lowercase()),
+        })
+    }
+
+    /// Maximum number of synonym alternates expanded per query keyword, so a heavily-aliased
+    /// term can't blow up the number of prefix lookups `map_prefix_keywords` has to run.
+    const MAX_SYNONYM_ALTERNATES: usize = 3;
+
+    /// Looks up the configured alternate forms of `word` (e.g. "addon" for "add-on"), capped at
+    /// `limit` results.
+    fn keyword_synonyms(&self, word: &str, limit: usize) -> Result<Vec<String>> {
+        self.conn
+            .query_rows_and_then_cached(
+                "SELECT alias FROM keyword_synonyms WHERE term = :term LIMIT :limit",
+                named_params! { ":term": word, ":limit": limit as i64 },
+                |row| row.get(0),
+            )
+    }
+
+    /// Runs the prefix-keyword lookup against `prefix_keywords`/`suggestions` for a single
+    /// candidate last word, tagging every row that comes back with `penalty` before handing it to
+    /// `mapper`. Doesn't filter out dismissed suggestions itself — `mapper` is expected to check
+    /// [Self::is_dismissed] and return `None`/skip as appropriate, since by the time a row
+    /// reaches here `mapper` already needs the URL to build its result.
+    fn query_prefix_keywords<T>(
+        &self,
+        exact_prefix: &str,
+        candidate_last_word: &str,
+        penalty: u32,
         provider: &SuggestionProvider,
-        mut mapper: impl FnMut(&rusqlite::Row, &str) -> Result<T>,
-    ) -> Result<Vec<T>> {
-        let keyword_lowercased = &query.keyword.to_lowercase();
-        let (keyword_prefix, keyword_suffix) = split_keyword(keyword_lowercased);
-        let suggestions_limit = query.limit.unwrap_or(-1);
+        suggestions_limit: i64,
+        mapper: &mut impl FnMut(&rusqlite::Row, &str, u32) -> Result<T>,
+    ) -> Result<Vec<(i64, T)>> {
+        let candidate_keyword = format!("{exact_prefix}{candidate_last_word}");
+        let (keyword_prefix, keyword_suffix) = split_keyword(&candidate_keyword);
         self.conn.query_rows_and_then_cached(
             r#"
                 SELECT
@@ -701,7 +1560,6 @@ impl<'a> SuggestDao<'a> {
                   k.keyword_prefix = :keyword_prefix
                   AND (k.keyword_suffix BETWEEN :keyword_suffix AND :keyword_suffix || x'FFFF')
                   AND s.provider = :provider
-                  AND NOT EXISTS (SELECT 1 FROM dismissed_suggestions WHERE url=s.url)
                 GROUP BY
                   s.id
                 ORDER BY
@@ -716,21 +1574,89 @@ impl<'a> SuggestDao<'a> {
                 (":provider", provider as &dyn ToSql),
                 (":suggestions_limit", &suggestions_limit as &dyn ToSql),
             ],
-            |row| mapper(row, keyword_suffix),
+            |row| -> Result<(i64, T)> {
+                let suggestion_id: i64 = row.get("id")?;
+                Ok((suggestion_id, mapper(row, keyword_suffix, penalty)?))
+            },
         )
     }
 
+    /// Matches the query keyword against `prefix_keywords` for `provider`.
+    ///
+    /// Only the final term of the (whitespace-split) query keyword is treated as a prefix; any
+    /// earlier terms are kept verbatim so they still have to match exactly. The final term is
+    /// expanded into a set of candidate forms: itself, any configured synonyms (e.g. "addon" for
+    /// "add-on", capped at [Self::MAX_SYNONYM_ALTERNATES]), and — if
+    /// `query.allow_fuzzy_matching` is set — a bounded set of Damerau-Levenshtein derivations
+    /// (never touching the first character, since that's what selects the `keyword_prefix`
+    /// bucket), using MeiliSearch's typo thresholds. Each candidate is run through the same
+    /// prefix lookup and results are deduped by suggestion id, keeping the first (lowest-penalty)
+    /// copy, so an exact or synonym match always wins over a fuzzy one.
+    fn map_prefix_keywords<T>(
+        &self,
+        query: &SuggestionQuery,
+        provider: &SuggestionProvider,
+        mut mapper: impl FnMut(&rusqlite::Row, &str, u32) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let keyword_lowercased = query.keyword.to_lowercase();
+        let mut words: Vec<&str> = keyword_lowercased.split_whitespace().collect();
+        let Some(last_word) = words.pop() else {
+            return Ok(vec![]);
+        };
+        let exact_prefix = if words.is_empty() {
+            String::new()
+        } else {
+            format!("{} ", words.join(" "))
+        };
+        let suggestions_limit = query.limit.unwrap_or(-1);
+
+        let mut candidates = if query.allow_fuzzy_matching {
+            keyword_derivations(last_word, max_typos_for_len(last_word.chars().count()))
+        } else {
+            vec![(last_word.to_string(), 0)]
+        };
+        for synonym in self.keyword_synonyms(last_word, Self::MAX_SYNONYM_ALTERNATES)? {
+            candidates.push((synonym, 0));
+        }
+        // Stable sort: the exact word stays first, and synonyms move ahead of fuzzy derivations.
+        candidates.sort_by_key(|&(_, penalty)| penalty);
+
+        // Candidates are in non-decreasing penalty order (exact/synonym matches before fuzzy
+        // ones), so the first time we see a given suggestion id is always its best match.
+        let mut seen = std::collections::HashSet::<i64>::new();
+        let mut results = Vec::new();
+        for (candidate_last_word, penalty) in candidates {
+            let rows = self.query_prefix_keywords(
+                &exact_prefix,
+                &candidate_last_word,
+                penalty,
+                provider,
+                suggestions_limit,
+                &mut mapper,
+            )?;
+            for (suggestion_id, mapped) in rows {
+                if seen.insert(suggestion_id) {
+                    results.push(mapped);
+                }
+            }
+        }
+        Ok(results)
+    }
+
     /// Fetches Suggestions of type Amo provider that match the given query
     pub fn fetch_amo_suggestions(&self, query: &SuggestionQuery) -> Result<Vec<Suggestion>> {
         let suggestions = self
             .map_prefix_keywords(
                 query,
                 &SuggestionProvider::Amo,
-                |row, keyword_suffix| -> Result<Option<Suggestion>> {
+                |row, keyword_suffix, typo_penalty| -> Result<Option<Suggestion>> {
                     let suggestion_id: i64 = row.get("id")?;
                     let title = row.get("title")?;
                     let raw_url = row.get::<_, String>("url")?;
-                    let score = row.get::<_, f64>("score")?;
+                    if self.is_dismissed(&raw_url, None)? {
+                        return Ok(None);
+                    }
+                    let score = row.get::<_, f64>("score")? - typo_penalty as f64;
 
                     let full_suffix = row.get::<_, String>("keyword_suffix")?;
                     full_suffix
@@ -801,7 +1727,6 @@ impl<'a> SuggestDao<'a> {
               k.keyword_prefix = :keyword_prefix
               AND (k.keyword_suffix BETWEEN :keyword_suffix AND :keyword_suffix || x'FFFF')
               AND s.provider = :provider
-              AND NOT EXISTS (SELECT 1 FROM dismissed_suggestions WHERE url=s.url)
             GROUP BY
               s.id,
               k.confidence
@@ -817,6 +1742,9 @@ impl<'a> SuggestDao<'a> {
                 |row| -> Result<Option<Suggestion>> {
                     let title = row.get("title")?;
                     let raw_url = row.get::<_, String>("url")?;
+                    if self.is_dismissed(&raw_url, None)? {
+                        return Ok(None);
+                    }
                     let score = row.get::<_, f64>("score")?;
                     let confidence = row.get("confidence")?;
                     let full_suffix = row.get::<_, String>("keyword_suffix")?;
@@ -854,11 +1782,14 @@ impl<'a> SuggestDao<'a> {
             .map_prefix_keywords(
                 query,
                 &SuggestionProvider::Mdn,
-                |row, keyword_suffix| -> Result<Option<Suggestion>> {
+                |row, keyword_suffix, typo_penalty| -> Result<Option<Suggestion>> {
                     let suggestion_id: i64 = row.get("id")?;
                     let title = row.get("title")?;
                     let raw_url = row.get::<_, String>("url")?;
-                    let score = row.get::<_, f64>("score")?;
+                    if self.is_dismissed(&raw_url, None)? {
+                        return Ok(None);
+                    }
+                    let score = row.get::<_, f64>("score")? - typo_penalty as f64;
 
                     let full_suffix = row.get::<_, String>("keyword_suffix")?;
                     full_suffix
@@ -896,6 +1827,12 @@ impl<'a> SuggestDao<'a> {
         Ok(suggestions)
     }
 
+    /// Number of top-scoring Fakespot FTS candidates we bother computing term-proximity for.
+    /// Keeping this small bounds the extra `offsets()` queries a single call can issue; rows
+    /// past this cutoff score low enough already that a proximity nudge couldn't move them to
+    /// the top anyway.
+    const FAKESPOT_PROXIMITY_RERANK_LIMIT: usize = 5;
+
     /// Fetches Fakespot suggestions
     pub fn fetch_fakespot_suggestions(&self, query: &SuggestionQuery) -> Result<Vec<Suggestion>> {
         let fts_query = query.fts_query();
@@ -959,11 +1896,29 @@ impl<'a> SuggestDao<'a> {
                         id,
                     ))
                 })?;
-        // Sort the results, then add the FTS match info to the first one
+        // Sort by score first, then nudge the top candidates by how close together their
+        // matched terms sit in the title (MeiliSearch's proximity ranking rule): a product
+        // whose query words appear right next to each other should outrank one where they're
+        // scattered, even though raw FTS5 relevance doesn't otherwise factor in term distance.
+        // This only touches the leading `FAKESPOT_PROXIMITY_RERANK_LIMIT` rows since a
+        // proximity bonus is too small to matter for anything further down the list.
+        results.sort();
+        for (suggestion, id) in results
+            .iter_mut()
+            .take(Self::FAKESPOT_PROXIMITY_RERANK_LIMIT)
+        {
+            if let Suggestion::Fakespot { score, .. } = suggestion {
+                if let Some(gap) = self.fetch_fakespot_term_proximity(&fts_query, *id)? {
+                    *score += Self::fakespot_proximity_bonus(gap);
+                }
+            }
+        }
+        results.sort();
+
+        // Add the FTS match info to the first result.
         // For performance reasons, this is only calculated for the result with the highest score.
         // We assume that only one that will be shown to the user and therefore the only one we'll
         // collect metrics for.
-        results.sort();
         if let Some((suggestion, id)) = results.first_mut() {
             match suggestion {
                 Suggestion::Fakespot {
@@ -1003,6 +1958,89 @@ impl<'a> SuggestDao<'a> {
         })
     }
 
+    /// Computes how far apart a Fakespot suggestion's matched query terms sit in its FTS
+    /// content, for use as a proximity tiebreaker. Returns `None` when the query has fewer
+    /// than two terms (there's nothing to be close together), when SQLite reports no
+    /// occurrences for some reason (this should not normally happen for a row the caller
+    /// already matched, but the ranking signal simply isn't available if it does), or when the
+    /// terms only occur out of query order.
+    ///
+    /// Lower is better. The cost is the sum of byte distances between the start offsets of
+    /// consecutive terms, so even terms matched back-to-back cost more than zero.
+    fn fetch_fakespot_term_proximity(
+        &self,
+        fts_query: &FtsQuery<'_>,
+        suggestion_id: usize,
+    ) -> Result<Option<i64>> {
+        let term_count = fts_query.match_arg.split_whitespace().count();
+        if term_count < 2 {
+            return Ok(None);
+        }
+
+        let offsets: Option<String> = self.conn.try_query_row(
+            "SELECT offsets(fakespot_fts) FROM fakespot_fts WHERE rowid = ? AND fakespot_fts MATCH ?",
+            (&suggestion_id, &fts_query.match_arg),
+            |row| row.get(0),
+            true,
+        )?;
+        let Some(offsets) = offsets else {
+            return Ok(None);
+        };
+
+        // `offsets()` returns a space-separated list of `column term_index byte_offset size`
+        // quadruples, one per matched term occurrence, in column/content order.
+        let mut occurrences: Vec<(usize, i64)> = offsets
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .chunks_exact(4)
+            .filter_map(|quad| {
+                let term_index = quad[1].parse::<usize>().ok()?;
+                let byte_offset = quad[2].parse::<i64>().ok()?;
+                Some((term_index, byte_offset))
+            })
+            .collect();
+        occurrences.sort_by_key(|&(_, byte_offset)| byte_offset);
+
+        // Minimum-cost walk through query terms `0..term_count` in order, à la MeiliSearch's
+        // K-shortest-path proximity rule: `best[t]` tracks the cheapest way found so far to
+        // have matched terms `0..=t`, ending at a particular occurrence of term `t`. Each step
+        // costs the byte distance between the two start offsets (at least 1). Occurrences are
+        // visited in byte order, so an occurrence of term `t` that comes before every
+        // occurrence of term `t - 1` is never linked: out-of-order matches add no path at all.
+        let mut best: Vec<Option<(i64, i64)>> = vec![None; term_count];
+        for (term_index, byte_offset) in occurrences {
+            if term_index >= term_count {
+                continue;
+            }
+            let cost = if term_index == 0 {
+                Some(0)
+            } else {
+                best[term_index - 1]
+                    .map(|(prev_offset, prev_cost)| prev_cost + (byte_offset - prev_offset).abs().max(1))
+            };
+            let Some(cost) = cost else { continue };
+            let is_better = match best[term_index] {
+                Some((_, existing_cost)) => cost < existing_cost,
+                None => true,
+            };
+            if is_better {
+                best[term_index] = Some((byte_offset, cost));
+            }
+        }
+
+        Ok(best[term_count - 1].map(|(_, cost)| cost))
+    }
+
+    /// Converts a term-proximity cost (lower = closer together) from
+    /// [Self::fetch_fakespot_term_proximity] into a small additive score bonus. Capped well
+    /// below the typical spread between distinct Fakespot scores, so proximity can only break
+    /// ties among similarly-scored candidates rather than override the base relevance score.
+    fn fakespot_proximity_bonus(gap: i64) -> f64 {
+        const MAX_BONUS: f64 = 0.02;
+        const DECAY_PER_BYTE: f64 = 0.2;
+        MAX_BONUS / (1.0 + gap.max(0) as f64 * DECAY_PER_BYTE)
+    }
+
     /// Fetches exposure suggestions
     pub fn fetch_exposure_suggestions(&self, query: &SuggestionQuery) -> Result<Vec<Suggestion>> {
         // A single exposure suggestion can be spread across multiple remote
@@ -1080,6 +2118,169 @@ impl<'a> SuggestDao<'a> {
         )?)
     }
 
+    /// Fetches a Yelp suggestion for the query, if the keyword parses as a Yelp subject
+    /// optionally combined with a pre/post modifier and a location.
+    ///
+    /// Unlike the other providers, Yelp doesn't store one row per keyword in `suggestions`;
+    /// instead it's configured by a small, mostly static set of subjects, modifiers, and
+    /// location signs, and the URL is cooked up at query time from whichever of those the
+    /// keyword actually matched.
+    pub fn fetch_yelp_suggestions(&self, query: &SuggestionQuery) -> Result<Vec<Suggestion>> {
+        let keyword_lowercased = query.keyword.trim().to_lowercase();
+        let Some(parsed) = self.parse_yelp_keyword(&keyword_lowercased)? else {
+            return Ok(vec![]);
+        };
+        if parsed.need_location && parsed.location_param.is_none() {
+            return Ok(vec![]);
+        }
+
+        let details = self
+            .conn
+            .query_row_and_then_cached(
+                r#"
+                SELECT
+                  d.score,
+                  i.data AS icon,
+                  i.mimetype AS icon_mimetype
+                FROM
+                  yelp_custom_details d
+                LEFT JOIN
+                  icons i ON d.icon_id = i.id
+                "#,
+                (),
+                |row| -> Result<(f64, Option<Vec<u8>>, Option<String>)> {
+                    Ok((row.get("score")?, row.get("icon")?, row.get("icon_mimetype")?))
+                },
+            )
+            .optional()?;
+        let Some((score, icon, icon_mimetype)) = details else {
+            return Ok(vec![]);
+        };
+
+        let url = cook_raw_suggestion_url(&format!(
+            "https://www.yelp.com/search?find_desc={}&find_loc={}",
+            parsed.subject,
+            parsed.location_param.as_deref().unwrap_or(""),
+        ));
+        if self.is_dismissed(&url, None)? {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![Suggestion::Yelp {
+            url,
+            title: parsed.subject,
+            icon,
+            icon_mimetype,
+            score,
+            has_location_sign: parsed.has_location_sign,
+            subject_exact_match: parsed.subject_exact_match,
+            location_param: parsed.location_param,
+            need_location: parsed.need_location,
+        }])
+    }
+
+    /// Parses a lowercased Yelp query keyword into its subject, optional location, and the
+    /// modifier/location-sign bookkeeping `fetch_yelp_suggestions` needs to build the suggestion.
+    ///
+    /// Returns `None` if no configured subject is found anywhere in the keyword.
+    fn parse_yelp_keyword(&self, keyword_lowercased: &str) -> Result<Option<ParsedYelpKeyword>> {
+        let geoname_cache = self
+            .geoname_cache
+            .get_or_try_init(|| GeonameCache::new(self.conn))?;
+        let geoname_match = geoname_cache.find_in_text(keyword_lowercased)?;
+
+        let (mut subject_text, location_param, mut has_location_sign) =
+            match &geoname_match {
+                Some(m) => {
+                    let before = keyword_lowercased[..m.start].trim_end();
+                    let after = keyword_lowercased[m.end..].trim_start();
+                    (format!("{before} {after}").trim().to_string(), Some(m.name.clone()), false)
+                }
+                None => (keyword_lowercased.to_string(), None, false),
+            };
+
+        if location_param.is_some() {
+            for sign in ["near", "in", "by"] {
+                if let Some(rest) = subject_text.strip_suffix(&format!(" {sign}")) {
+                    subject_text = rest.to_string();
+                    has_location_sign = true;
+                    break;
+                }
+                if let Some(rest) = subject_text.strip_prefix(&format!("{sign} ")) {
+                    subject_text = rest.to_string();
+                    has_location_sign = true;
+                    break;
+                }
+            }
+        }
+        let subject_text = subject_text.trim().to_string();
+
+        let exact_match = self.conn.exists(
+            "SELECT 1 FROM yelp_subjects WHERE keyword = :keyword",
+            named_params! { ":keyword": &subject_text },
+        )?;
+        if exact_match {
+            return Ok(Some(ParsedYelpKeyword {
+                subject: subject_text,
+                location_param,
+                has_location_sign,
+                subject_exact_match: true,
+                need_location: self.yelp_subject_needs_location(&subject_text)?,
+            }));
+        }
+
+        // Try stripping a single pre- or post-modifier (e.g. "best <subject>", "<subject> near
+        // me") and matching what's left against the configured subjects.
+        for word_count in 1..=subject_text.split_whitespace().count() {
+            let words: Vec<&str> = subject_text.split_whitespace().collect();
+            let (modifier, rest) = words.split_at(word_count);
+            let modifier_text = modifier.join(" ");
+            let rest_text = rest.join(" ");
+            if self.conn.exists(
+                "SELECT 1 FROM yelp_modifiers WHERE keyword = :keyword AND type = 'pre'",
+                named_params! { ":keyword": &modifier_text },
+            )? && self.conn.exists(
+                "SELECT 1 FROM yelp_subjects WHERE keyword = :keyword",
+                named_params! { ":keyword": &rest_text },
+            )? {
+                return Ok(Some(ParsedYelpKeyword {
+                    subject: rest_text.clone(),
+                    location_param,
+                    has_location_sign,
+                    subject_exact_match: false,
+                    need_location: self.yelp_subject_needs_location(&rest_text)?,
+                }));
+            }
+            let (rest, modifier) = words.split_at(words.len() - word_count);
+            let rest_text = rest.join(" ");
+            let modifier_text = modifier.join(" ");
+            if self.conn.exists(
+                "SELECT 1 FROM yelp_modifiers WHERE keyword = :keyword AND type = 'post'",
+                named_params! { ":keyword": &modifier_text },
+            )? && self.conn.exists(
+                "SELECT 1 FROM yelp_subjects WHERE keyword = :keyword",
+                named_params! { ":keyword": &rest_text },
+            )? {
+                return Ok(Some(ParsedYelpKeyword {
+                    subject: rest_text.clone(),
+                    location_param,
+                    has_location_sign,
+                    subject_exact_match: false,
+                    need_location: self.yelp_subject_needs_location(&rest_text)?,
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn yelp_subject_needs_location(&self, subject: &str) -> Result<bool> {
+        Ok(self.conn.exists(
+            "SELECT 1 FROM yelp_subjects WHERE keyword = :keyword AND need_location = 1",
+            named_params! { ":keyword": subject },
+        )?)
+    }
+
     pub fn is_amp_fts_data_ingested(&self, record_id: &SuggestRecordId) -> Result<bool> {
         Ok(self.conn.exists(
             r#"
@@ -1123,6 +2324,7 @@ impl<'a> SuggestDao<'a> {
                     keyword_prefix,
                     keyword_suffix,
                     index,
+                    None,
                 )?;
             }
         }
@@ -1137,13 +2339,17 @@ impl<'a> SuggestDao<'a> {
         suggestions: &[DownloadedAmpWikipediaSuggestion],
         enable_fts: bool,
     ) -> Result<()> {
+        let timer = DownloadTimer::start();
         // Prepare statements outside of the loop.  This results in a large performance
         // improvement on a fresh ingest, since there are so many rows.
         let mut suggestion_insert = SuggestionInsertStatement::new(self.conn)?;
         let mut amp_insert = AmpInsertStatement::new(self.conn)?;
         let mut wiki_insert = WikipediaInsertStatement::new(self.conn)?;
         let mut keyword_insert = KeywordInsertStatement::new(self.conn)?;
-        let mut fts_insert = AmpFtsInsertStatement::new(self.conn)?;
+        let mut amp_fts_insert = AmpFtsInsertStatement::new(self.conn)?;
+        let mut amp_trigram_insert = AmpTrigramInsertStatement::new(self.conn)?;
+        let mut wikipedia_fts_insert = WikipediaFtsInsertStatement::new(self.conn)?;
+        let stopwords = self.stopwords()?;
         for suggestion in suggestions {
             self.scope.err_if_interrupted()?;
             let common_details = suggestion.common_details();
@@ -1159,18 +2365,21 @@ impl<'a> SuggestDao<'a> {
             match suggestion {
                 DownloadedAmpWikipediaSuggestion::Amp(amp) => {
                     amp_insert.execute(suggestion_id, amp)?;
+                    if enable_fts {
+                        amp_fts_insert.execute(
+                            suggestion_id,
+                            &strip_stopwords(&common_details.full_keywords_fts_column(), &stopwords),
+                            &common_details.title,
+                        )?;
+                    }
                 }
                 DownloadedAmpWikipediaSuggestion::Wikipedia(wikipedia) => {
                     wiki_insert.execute(suggestion_id, wikipedia)?;
+                    if enable_fts {
+                        wikipedia_fts_insert.execute(suggestion_id, &common_details.title)?;
+                    }
                 }
             }
-            if enable_fts {
-                fts_insert.execute(
-                    suggestion_id,
-                    &common_details.full_keywords_fts_column(),
-                    &common_details.title,
-                )?;
-            }
             let mut full_keyword_inserter = FullKeywordInserter::new(self.conn, suggestion_id);
             for keyword in common_details.keywords() {
                 let full_keyword_id = match (suggestion, keyword.full_keyword) {
@@ -1188,8 +2397,17 @@ impl<'a> SuggestDao<'a> {
                     full_keyword_id,
                     keyword.rank,
                 )?;
+
+                if enable_fts {
+                    if let DownloadedAmpWikipediaSuggestion::Amp(_) = suggestion {
+                        for trigram in keyword_trigrams(keyword.keyword) {
+                            amp_trigram_insert.execute(suggestion_id, &trigram)?;
+                        }
+                    }
+                }
             }
         }
+        self.record_ingestion_sample("insert_amp_wikipedia_suggestions", timer);
         Ok(())
     }
 
@@ -1238,9 +2456,11 @@ impl<'a> SuggestDao<'a> {
         &mut self,
         record_id: &SuggestRecordId,
         suggestions: &[DownloadedPocketSuggestion],
+        enable_fts: bool,
     ) -> Result<()> {
         let mut suggestion_insert = SuggestionInsertStatement::new(self.conn)?;
         let mut prefix_keyword_insert = PrefixKeywordInsertStatement::new(self.conn)?;
+        let mut pocket_fts_insert = PocketFtsInsertStatement::new(self.conn)?;
         for suggestion in suggestions {
             self.scope.err_if_interrupted()?;
             let suggestion_id = suggestion_insert.execute(
@@ -1250,6 +2470,17 @@ impl<'a> SuggestDao<'a> {
                 suggestion.score,
                 SuggestionProvider::Pocket,
             )?;
+            if enable_fts {
+                let full_keywords = suggestion
+                    .high_confidence_keywords
+                    .iter()
+                    .chain(suggestion.low_confidence_keywords.iter())
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                pocket_fts_insert.execute(suggestion_id, &suggestion.title, &full_keywords)?;
+            }
+            let mut full_keyword_inserter = FullKeywordInserter::new(self.conn, suggestion_id);
             for ((rank, keyword), confidence) in suggestion
                 .high_confidence_keywords
                 .iter()
@@ -1263,6 +2494,7 @@ impl<'a> SuggestDao<'a> {
                         .zip(std::iter::repeat(KeywordConfidence::Low)),
                 )
             {
+                let full_keyword_id = full_keyword_inserter.maybe_insert(keyword)?;
                 let (keyword_prefix, keyword_suffix) = split_keyword(keyword);
                 prefix_keyword_insert.execute(
                     suggestion_id,
@@ -1270,6 +2502,7 @@ impl<'a> SuggestDao<'a> {
                     keyword_prefix,
                     keyword_suffix,
                     rank,
+                    Some(full_keyword_id),
                 )?;
             }
         }
@@ -1282,10 +2515,12 @@ impl<'a> SuggestDao<'a> {
         &mut self,
         record_id: &SuggestRecordId,
         suggestions: &[DownloadedMdnSuggestion],
+        enable_fts: bool,
     ) -> Result<()> {
         let mut suggestion_insert = SuggestionInsertStatement::new(self.conn)?;
         let mut mdn_insert = MdnInsertStatement::new(self.conn)?;
         let mut prefix_keyword_insert = PrefixKeywordInsertStatement::new(self.conn)?;
+        let mut mdn_fts_insert = MdnFtsInsertStatement::new(self.conn)?;
         for suggestion in suggestions {
             self.scope.err_if_interrupted()?;
             let suggestion_id = suggestion_insert.execute(
@@ -1296,7 +2531,12 @@ impl<'a> SuggestDao<'a> {
                 SuggestionProvider::Mdn,
             )?;
             mdn_insert.execute(suggestion_id, suggestion)?;
+            if enable_fts {
+                mdn_fts_insert.execute(suggestion_id, &suggestion.title, &suggestion.description)?;
+            }
+            let mut full_keyword_inserter = FullKeywordInserter::new(self.conn, suggestion_id);
             for (index, keyword) in suggestion.keywords.iter().enumerate() {
+                let full_keyword_id = full_keyword_inserter.maybe_insert(keyword)?;
                 let (keyword_prefix, keyword_suffix) = split_keyword(keyword);
                 prefix_keyword_insert.execute(
                     suggestion_id,
@@ -1304,12 +2544,35 @@ impl<'a> SuggestDao<'a> {
                     keyword_prefix,
                     keyword_suffix,
                     index,
+                    Some(full_keyword_id),
                 )?;
             }
         }
         Ok(())
     }
 
+    /// Inserts a batch of keyword synonym groups from a downloaded Synonyms attachment into the
+    /// database, so that a query for either surface form of a group (e.g. "add-on"/"addon")
+    /// finds suggestions indexed under the other via [Self::keyword_synonyms]. Each alias is
+    /// stored both ways (term -> alias and alias -> term).
+    pub fn insert_keyword_synonyms(
+        &mut self,
+        record_id: &SuggestRecordId,
+        synonyms: &[DownloadedSynonyms],
+    ) -> Result<()> {
+        let mut insert = self.conn.prepare_cached(
+            "INSERT OR IGNORE INTO keyword_synonyms(record_id, term, alias) VALUES(?, ?, ?)",
+        )?;
+        for group in synonyms {
+            self.scope.err_if_interrupted()?;
+            for alias in &group.aliases {
+                insert.execute((record_id.as_str(), group.term.as_str(), alias.as_str()))?;
+                insert.execute((record_id.as_str(), alias.as_str(), group.term.as_str()))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Inserts all suggestions from a downloaded Fakespot attachment into the database.
     pub fn insert_fakespot_suggestions(
         &mut self,
@@ -1364,6 +2627,11 @@ impl<'a> SuggestDao<'a> {
         Ok(())
     }
 
+    /// Chunk size used by [Self::put_icon_streaming] and [Self::get_icon_streaming] when
+    /// reading or writing icon BLOBs incrementally, so a single in-flight chunk never gets
+    /// much larger than this regardless of the icon's total size.
+    const ICON_BLOB_CHUNK_SIZE: usize = 32 * 1024;
+
     /// Inserts or replaces an icon for a suggestion into the database.
     pub fn put_icon(&mut self, icon_id: &str, data: &[u8], mimetype: &str) -> Result<()> {
         self.conn.execute(
@@ -1386,6 +2654,77 @@ impl<'a> SuggestDao<'a> {
         Ok(())
     }
 
+    /// Like [Self::put_icon], but streams `data` into the row's BLOB in
+    /// fixed-size chunks through an incremental BLOB handle instead of
+    /// binding it as a single parameter. Prefer this for the multi-hundred-KB
+    /// icons that providers like Fakespot and AMP ship in bulk, since it
+    /// keeps at most one chunk materialized at a time rather than the whole
+    /// icon.
+    pub fn put_icon_streaming(&mut self, icon_id: &str, data: &[u8], mimetype: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO icons(
+                 id,
+                 data,
+                 mimetype
+             )
+             VALUES(
+                 :id,
+                 zeroblob(:len),
+                 :mimetype
+             )",
+            named_params! {
+                ":id": icon_id,
+                ":len": data.len() as i64,
+                ":mimetype": mimetype,
+            },
+        )?;
+        let row_id = self.conn.last_insert_rowid();
+        let mut blob = self
+            .conn
+            .blob_open(DatabaseName::Main, "icons", "data", row_id, false)?;
+        for chunk in data.chunks(Self::ICON_BLOB_CHUNK_SIZE) {
+            self.scope.err_if_interrupted()?;
+            blob.write_all(chunk)?;
+        }
+        Ok(())
+    }
+
+    /// Like reading an icon's `data`/`mimetype` columns directly, but streams
+    /// the BLOB out in fixed-size chunks through an incremental BLOB handle
+    /// rather than letting the row fetch materialize the whole icon at once.
+    /// Returns `None` if there's no icon with this id.
+    pub fn get_icon_streaming(&self, icon_id: &str) -> Result<Option<(Vec<u8>, String)>> {
+        let Some((row_id, mimetype, len)) = self.conn.try_query_row(
+            "SELECT rowid, mimetype, length(data) FROM icons WHERE id = :id",
+            named_params! { ":id": icon_id },
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            },
+            true,
+        )?
+        else {
+            return Ok(None);
+        };
+        let mut blob = self
+            .conn
+            .blob_open(DatabaseName::Main, "icons", "data", row_id, true)?;
+        let mut data = Vec::with_capacity(len.max(0) as usize);
+        let mut chunk = [0u8; Self::ICON_BLOB_CHUNK_SIZE];
+        loop {
+            self.scope.err_if_interrupted()?;
+            let n = blob.read(&mut chunk)?;
+            if n == 0 {
+                break;
+            }
+            data.extend_from_slice(&chunk[..n]);
+        }
+        Ok(Some((data, mimetype)))
+    }
+
     pub fn insert_dismissal(&self, url: &str) -> Result<()> {
         self.conn.execute(
             "INSERT OR IGNORE INTO dismissed_suggestions(url)
@@ -1394,11 +2733,41 @@ impl<'a> SuggestDao<'a> {
                 ":url": url,
             },
         )?;
+        self.invalidate_dismissed();
+        Ok(())
+    }
+
+    /// Dismisses a suggestion by a stable key rather than its exact URL. Use this for providers
+    /// whose URL can change out from under an otherwise-identical suggestion (AMP's
+    /// timestamped/template-expanded impression and click URLs being the motivating case, see
+    /// [dismissal_key]) — callers compute the key the same way the matching fetch path does, so a
+    /// dismissal recorded for one URL variant still matches later variants of the same
+    /// suggestion. The existing URL-based API keeps working unchanged; the query path checks
+    /// both.
+    ///
+    /// `dismissed_suggestions.url` is the table's only uniqueness constraint, so every by-key
+    /// dismissal needs its own distinct, non-empty placeholder there -- a shared `url = ''` would
+    /// let the first by-key dismissal claim that row via `INSERT OR IGNORE` and silently drop
+    /// every subsequent dismissal for a *different* key. This namespaces the placeholder by the
+    /// key itself so distinct keys get distinct rows; the real fix is a schema migration giving
+    /// `key` its own (nullable-`url`-friendly) unique index, which belongs in this crate's
+    /// `schema` module and isn't part of this change.
+    pub fn insert_dismissal_by_key(&self, key: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO dismissed_suggestions(url, key)
+             VALUES(:placeholder_url, :key)",
+            named_params! {
+                ":placeholder_url": format!("dismissal-key:{key}"),
+                ":key": key,
+            },
+        )?;
+        self.invalidate_dismissed();
         Ok(())
     }
 
     pub fn clear_dismissals(&self) -> Result<()> {
         self.conn.execute("DELETE FROM dismissed_suggestions", ())?;
+        self.invalidate_dismissed();
         Ok(())
     }
 
@@ -1432,6 +2801,14 @@ impl<'a> SuggestDao<'a> {
             named_params! { ":record_id": record_id.as_str() },
         )?;
         self.scope.err_if_interrupted()?;
+        self.conn.execute_cached(
+            "
+            DELETE FROM amp_keyword_trigrams
+            WHERE suggestion_id IN (SELECT id from suggestions WHERE record_id = :record_id)
+            ",
+            named_params! { ":record_id": record_id.as_str() },
+        )?;
+        self.scope.err_if_interrupted()?;
         self.conn.execute_cached(
             "
             DELETE FROM fakespot_fts
@@ -1440,6 +2817,22 @@ impl<'a> SuggestDao<'a> {
             named_params! { ":record_id": record_id.as_str() },
         )?;
         self.scope.err_if_interrupted()?;
+        self.conn.execute_cached(
+            "
+            DELETE FROM mdn_fts
+            WHERE rowid IN (SELECT id from suggestions WHERE record_id = :record_id)
+            ",
+            named_params! { ":record_id": record_id.as_str() },
+        )?;
+        self.scope.err_if_interrupted()?;
+        self.conn.execute_cached(
+            "
+            DELETE FROM pocket_fts
+            WHERE rowid IN (SELECT id from suggestions WHERE record_id = :record_id)
+            ",
+            named_params! { ":record_id": record_id.as_str() },
+        )?;
+        self.scope.err_if_interrupted()?;
         self.conn.execute_cached(
             "DELETE FROM suggestions WHERE record_id = :record_id",
             named_params! { ":record_id": record_id.as_str() },
@@ -1474,6 +2867,16 @@ impl<'a> SuggestDao<'a> {
             "DELETE FROM geonames_metrics WHERE record_id = :record_id",
             named_params! { ":record_id": record_id.as_str() },
         )?;
+        self.scope.err_if_interrupted()?;
+        self.conn.execute_cached(
+            "DELETE FROM keyword_synonyms WHERE record_id = :record_id",
+            named_params! { ":record_id": record_id.as_str() },
+        )?;
+        self.scope.err_if_interrupted()?;
+        self.conn.execute_cached(
+            "DELETE FROM unparsable_records WHERE id = :record_id",
+            named_params! { ":record_id": record_id.as_str() },
+        )?;
 
         // Invalidate these caches since we might have deleted a record their
         // contents are based on.
@@ -1530,6 +2933,61 @@ impl<'a> SuggestDao<'a> {
             )
     }
 
+    /// Gets the stopword set used when building the AMP FTS column at ingest, falling back to
+    /// [DEFAULT_STOPWORDS] if none has been stored yet.
+    pub fn stopwords(&self) -> Result<std::collections::HashSet<String>> {
+        Ok(self
+            .get_meta::<String>(STOPWORDS_META_KEY)?
+            .map_or_else(
+                || {
+                    Ok(DEFAULT_STOPWORDS
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect())
+                },
+                |json| -> Result<Vec<String>> { Ok(serde_json::from_str(&json)?) },
+            )?
+            .into_iter()
+            .collect())
+    }
+
+    /// Overrides the stopword set stripped from the AMP `full_keywords` FTS column at ingest.
+    /// Stored in the `meta` table so that later ingests keep using the same list even across
+    /// upgrades. Queries do not read this set; their FTS match expressions keep every word.
+    pub fn set_stopwords(&mut self, stopwords: &[String]) -> Result<()> {
+        self.put_meta(STOPWORDS_META_KEY, serde_json::to_string(stopwords)?)
+    }
+
+    /// Gets the BM25 column weights used to rank `amp_fts` matches, falling back to
+    /// [AmpFtsWeights::default] if none has been stored yet.
+    pub fn get_amp_fts_weights(&self) -> Result<AmpFtsWeights> {
+        self.get_meta::<String>(&amp_fts_weights_meta_key())?
+            .map_or_else(
+                || Ok(AmpFtsWeights::default()),
+                |json| Ok(serde_json::from_str(&json)?),
+            )
+    }
+
+    /// Overrides the BM25 column weights used to rank `amp_fts` matches.
+    pub fn set_amp_fts_weights(&mut self, weights: &AmpFtsWeights) -> Result<()> {
+        self.put_meta(&amp_fts_weights_meta_key(), serde_json::to_string(weights)?)
+    }
+
+    /// Gets the tuning knobs for the AMP trigram fallback, falling back to
+    /// [AmpTrigramConfig::default] if none has been stored yet.
+    pub fn get_amp_trigram_config(&self) -> Result<AmpTrigramConfig> {
+        self.get_meta::<String>(&amp_trigram_config_meta_key())?
+            .map_or_else(
+                || Ok(AmpTrigramConfig::default()),
+                |json| Ok(serde_json::from_str(&json)?),
+            )
+    }
+
+    /// Overrides the tuning knobs for the AMP trigram fallback.
+    pub fn set_amp_trigram_config(&mut self, config: &AmpTrigramConfig) -> Result<()> {
+        self.put_meta(&amp_trigram_config_meta_key(), serde_json::to_string(config)?)
+    }
+
     /// Stores configuration data for a given provider.
     pub fn put_provider_config(
         &mut self,
@@ -1542,14 +3000,62 @@ impl<'a> SuggestDao<'a> {
         )
     }
 
-    /// Gets the stored configuration data for a given provider or None if none
-    /// is stored.
+    /// Gets the stored configuration data for a given provider or None if none is stored,
+    /// layering a local override (see [Self::set_provider_config_override]) and a
+    /// process-environment override, in that order, on top of the remote-settings-ingested
+    /// value. Each layer overlays the previous one field-by-field rather than replacing it
+    /// wholesale, so e.g. overriding just the AMP trigram weights doesn't require also
+    /// reproducing every other field the ingested config set.
+    ///
+    /// This is the one place config reads should go through, so staging/QA can flip a field
+    /// without waiting on a new remote-settings record.
     pub fn get_provider_config(
         &self,
         provider: SuggestionProvider,
     ) -> Result<Option<SuggestProviderConfig>> {
-        self.get_meta::<String>(&provider_config_meta_key(provider))?
-            .map_or_else(|| Ok(None), |json| Ok(serde_json::from_str(&json)?))
+        let mut merged: Option<serde_json::Value> = self
+            .get_meta::<String>(&provider_config_meta_key(provider))?
+            .map(|json| serde_json::from_str(&json))
+            .transpose()?;
+
+        let override_json = self.get_meta::<String>(&provider_config_override_meta_key(provider))?;
+        let env_json = std::env::var(provider_config_env_key(provider)).ok();
+        for layer_json in [override_json, env_json].into_iter().flatten() {
+            let layer: serde_json::Value = serde_json::from_str(&layer_json)?;
+            match &mut merged {
+                Some(base) => merge_json_layer(base, layer),
+                None => merged = Some(layer),
+            }
+        }
+
+        merged
+            .map(|value| Ok(serde_json::from_value(value)?))
+            .transpose()
+    }
+
+    /// Sets a local override for a provider's configuration, layered on top of the
+    /// remote-settings-ingested value by [Self::get_provider_config]. `json` need only contain
+    /// the fields being overridden, not a full `SuggestProviderConfig` -- e.g. `{"bm25Weights":
+    /// {"title": 3.0}}` overrides one nested field and leaves everything else, at every level,
+    /// as the ingested config set it.
+    pub fn set_provider_config_override(
+        &mut self,
+        provider: SuggestionProvider,
+        json: &str,
+    ) -> Result<()> {
+        let _: serde_json::Value = serde_json::from_str(json)?;
+        self.put_meta(&provider_config_override_meta_key(provider), json)
+    }
+
+    /// Removes a provider's local config override, restoring reads through
+    /// [Self::get_provider_config] to whatever the remote-settings-ingested value (plus any
+    /// environment override) resolves to.
+    pub fn clear_provider_config_override(&mut self, provider: SuggestionProvider) -> Result<()> {
+        self.conn.execute_cached(
+            "DELETE FROM meta WHERE key = :key",
+            named_params! { ":key": provider_config_override_meta_key(provider) },
+        )?;
+        Ok(())
     }
 }
 
@@ -1572,6 +3078,35 @@ impl IngestedRecord {
     }
 }
 
+/// A record that was downloaded but couldn't be ingested, either because its attachment failed
+/// to parse or because its `record_type` wasn't recognized by the schema version that downloaded
+/// it.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct UnparsableRecord {
+    pub id: SuggestRecordId,
+    pub collection: String,
+    pub schema_version: u32,
+}
+
+impl UnparsableRecord {
+    fn from_row(row: &rusqlite::Row) -> Result<Self> {
+        Ok(Self {
+            id: SuggestRecordId::new(row.get("id")?),
+            collection: row.get("collection")?,
+            schema_version: row.get("schema_version")?,
+        })
+    }
+}
+
+/// The result of parsing a Yelp query keyword into its constituent parts.
+struct ParsedYelpKeyword {
+    subject: String,
+    location_param: Option<String>,
+    has_location_sign: bool,
+    subject_exact_match: bool,
+    need_location: bool,
+}
+
 /// Helper struct to get full_keyword_ids for a suggestion
 ///
 /// `FullKeywordInserter` handles repeated full keywords efficiently.  The first instance will
@@ -1903,9 +3438,10 @@ impl<'conn> PrefixKeywordInsertStatement<'conn> {
                  confidence,
                  keyword_prefix,
                  keyword_suffix,
-                 rank
+                 rank,
+                 full_keyword_id
              )
-             VALUES(?, ?, ?, ?, ?)
+             VALUES(?, ?, ?, ?, ?, ?)
              ",
         )?))
     }
@@ -1917,6 +3453,7 @@ impl<'conn> PrefixKeywordInsertStatement<'conn> {
         keyword_prefix: &str,
         keyword_suffix: &str,
         rank: usize,
+        full_keyword_id: Option<i64>,
     ) -> Result<()> {
         self.0
             .execute((
@@ -1925,6 +3462,7 @@ impl<'conn> PrefixKeywordInsertStatement<'conn> {
                 keyword_prefix,
                 keyword_suffix,
                 rank,
+                full_keyword_id,
             ))
             .with_context("prefix keyword insert")?;
         Ok(())
@@ -1985,6 +3523,296 @@ impl<'conn> AmpFtsInsertStatement<'conn> {
     }
 }
 
+/// Writes one row per (suggestion, trigram) pair into `amp_keyword_trigrams`, the companion
+/// index [SuggestDao::fetch_amp_suggestions_using_trigrams] scans for typo-tolerant matches. This
+/// table is a plain rowid-free table, not an FTS5 virtual table like `amp_fts`, since it's
+/// queried by exact trigram equality and `GROUP BY suggestion_id`, not by MATCH.
+pub(crate) struct AmpTrigramInsertStatement<'conn>(rusqlite::Statement<'conn>);
+
+impl<'conn> AmpTrigramInsertStatement<'conn> {
+    pub(crate) fn new(conn: &'conn Connection) -> Result<Self> {
+        Ok(Self(conn.prepare(
+            "INSERT INTO amp_keyword_trigrams(suggestion_id, trigram)
+             VALUES(?, ?)
+             ",
+        )?))
+    }
+
+    pub(crate) fn execute(&mut self, suggestion_id: i64, trigram: &str) -> Result<()> {
+        self.0
+            .execute((suggestion_id, trigram))
+            .with_context("amp trigram insert")?;
+        Ok(())
+    }
+}
+
+pub(crate) struct WikipediaFtsInsertStatement<'conn>(rusqlite::Statement<'conn>);
+
+impl<'conn> WikipediaFtsInsertStatement<'conn> {
+    pub(crate) fn new(conn: &'conn Connection) -> Result<Self> {
+        Ok(Self(conn.prepare(
+            "INSERT INTO wikipedia_fts(rowid, title)
+             VALUES(?, ?)
+             ",
+        )?))
+    }
+
+    pub(crate) fn execute(&mut self, suggestion_id: i64, title: &str) -> Result<()> {
+        self.0
+            .execute((suggestion_id, title))
+            .with_context("wikipedia fts insert")?;
+        Ok(())
+    }
+}
+
+pub(crate) struct MdnFtsInsertStatement<'conn>(rusqlite::Statement<'conn>);
+
+impl<'conn> MdnFtsInsertStatement<'conn> {
+    pub(crate) fn new(conn: &'conn Connection) -> Result<Self> {
+        Ok(Self(conn.prepare(
+            "INSERT INTO mdn_fts(rowid, title, description)
+             VALUES(?, ?, ?)
+             ",
+        )?))
+    }
+
+    pub(crate) fn execute(
+        &mut self,
+        suggestion_id: i64,
+        title: &str,
+        description: &str,
+    ) -> Result<()> {
+        self.0
+            .execute((suggestion_id, title, description))
+            .with_context("mdn fts insert")?;
+        Ok(())
+    }
+}
+
+pub(crate) struct PocketFtsInsertStatement<'conn>(rusqlite::Statement<'conn>);
+
+impl<'conn> PocketFtsInsertStatement<'conn> {
+    pub(crate) fn new(conn: &'conn Connection) -> Result<Self> {
+        Ok(Self(conn.prepare(
+            "INSERT INTO pocket_fts(rowid, title, full_keywords)
+             VALUES(?, ?, ?)
+             ",
+        )?))
+    }
+
+    pub(crate) fn execute(
+        &mut self,
+        suggestion_id: i64,
+        title: &str,
+        full_keywords: &str,
+    ) -> Result<()> {
+        self.0
+            .execute((suggestion_id, title, full_keywords))
+            .with_context("pocket fts insert")?;
+        Ok(())
+    }
+}
+
+/// Derives a stable dismissal key for a suggestion from its raw URL and title, for providers
+/// (AMP in particular) whose URL carries volatile components — timestamped impression/click
+/// params, template expansions via `cook_raw_suggestion_url` — so the same logical suggestion
+/// can still be recognized as dismissed after its URL changes underneath it. The URL is
+/// normalized by dropping everything from the first `?` on, which covers the common case of
+/// query-string-only volatility; this is deliberately simple rather than attempting to parse or
+/// understand the provider-specific template syntax.
+fn dismissal_key(raw_url: &str, title: &str) -> String {
+    let normalized_url = raw_url.split('?').next().unwrap_or(raw_url);
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    normalized_url.hash(&mut hasher);
+    title.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
 fn provider_config_meta_key(provider: SuggestionProvider) -> String {
     format!("{}{}", PROVIDER_CONFIG_META_KEY_PREFIX, provider as u8)
 }
+
+fn amp_fts_weights_meta_key() -> String {
+    format!(
+        "{}{}",
+        provider_config_meta_key(SuggestionProvider::Amp),
+        AMP_FTS_WEIGHTS_META_KEY_SUFFIX
+    )
+}
+
+fn amp_trigram_config_meta_key() -> String {
+    format!(
+        "{}{}",
+        provider_config_meta_key(SuggestionProvider::Amp),
+        AMP_TRIGRAM_CONFIG_META_KEY_SUFFIX
+    )
+}
+
+/// Sibling meta key to [provider_config_meta_key] holding a provider's local config override
+/// (see [SuggestDao::set_provider_config_override]), kept separate so clearing the override
+/// can't ever touch the remote-settings-ingested value underneath it.
+fn provider_config_override_meta_key(provider: SuggestionProvider) -> String {
+    format!("{}_override", provider_config_meta_key(provider))
+}
+
+/// Environment variable consulted for a provider's config override, the highest-precedence
+/// layer [SuggestDao::get_provider_config] merges in. Keyed by the provider's numeric
+/// discriminant, the same identifier [provider_config_meta_key] already uses, since
+/// `SuggestionProvider` has no stable name string available from this file.
+fn provider_config_env_key(provider: SuggestionProvider) -> String {
+    format!("SUGGEST_PROVIDER_CONFIG_OVERRIDE_{}", provider as u8)
+}
+
+/// Recursively overlays `overlay` onto `base` in place: for two JSON objects, each key in
+/// `overlay` is merged into the same key of `base` (recursing if both sides are themselves
+/// objects), leaving keys only `base` has untouched; for any other combination of JSON value
+/// kinds, `overlay` simply replaces `base`. This is what lets
+/// [SuggestDao::get_provider_config]'s override layers change a single nested field without
+/// restating the rest of the config.
+fn merge_json_layer(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match overlay {
+        serde_json::Value::Object(overlay_map) => {
+            if let serde_json::Value::Object(base_map) = base {
+                for (key, overlay_value) in overlay_map {
+                    match base_map.get_mut(&key) {
+                        Some(base_value) => merge_json_layer(base_value, overlay_value),
+                        None => {
+                            base_map.insert(key, overlay_value);
+                        }
+                    }
+                }
+            } else {
+                *base = serde_json::Value::Object(overlay_map);
+            }
+        }
+        other => *base = other,
+    }
+}
+
+/// Splits `keyword` into lowercase, sentinel-padded 3-character shingles ("trigrams") per
+/// whitespace-delimited word, for typo-tolerant matching via [SuggestDao::fetch_amp_suggestions_using_trigrams].
+///
+/// Each word is padded with one sentinel character on either side before shingling, so that
+/// trigrams near a word boundary (which would otherwise be shorter, or blend into the next word)
+/// carry positional information too -- "cat" becomes "\u{2}ca", "cat", "at\u{2}" rather than just
+/// "cat". Words shorter than a single trigram once padded contribute nothing.
+fn keyword_trigrams(keyword: &str) -> HashSet<String> {
+    const SENTINEL: char = '\u{2}';
+    let mut trigrams = HashSet::new();
+    for word in keyword.to_lowercase().split_whitespace() {
+        let padded: Vec<char> = std::iter::once(SENTINEL)
+            .chain(word.chars())
+            .chain(std::iter::once(SENTINEL))
+            .collect();
+        for window in padded.windows(3) {
+            trigrams.insert(window.iter().collect());
+        }
+    }
+    trigrams
+}
+
+/// Jaccard similarity between two trigram sets: `|a ∩ b| / |a ∪ b|`, in `0.0..=1.0`. Returns
+/// `0.0` if both sets are empty rather than dividing by zero.
+fn trigram_jaccard_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Removes any word in `stopwords` from `text`, for building/matching FTS columns that shouldn't
+/// waste candidates on filler words. If stripping stopwords would leave nothing behind (the text
+/// is made up entirely of stopwords), the original text is returned unchanged so the column or
+/// query still matches literally instead of matching everything.
+fn strip_stopwords(text: &str, stopwords: &std::collections::HashSet<String>) -> String {
+    let stripped = text
+        .split_whitespace()
+        .filter(|word| !stopwords.contains(&word.to_lowercase()))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if stripped.is_empty() {
+        text.to_string()
+    } else {
+        stripped
+    }
+}
+
+/// The number of Damerau-Levenshtein typos tolerated for a word of the given length, borrowed
+/// from MeiliSearch's typo thresholds.
+fn max_typos_for_len(len: usize) -> u32 {
+    if len <= 4 {
+        0
+    } else if len <= 8 {
+        1
+    } else {
+        2
+    }
+}
+
+const TYPO_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+
+/// All words one Damerau-Levenshtein edit (insertion, deletion, substitution, or adjacent
+/// transposition) away from `word`, never touching the first character so the `keyword_prefix`
+/// bucket it selects stays stable across derivations.
+fn single_edit_derivations(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    if chars.len() <= 1 {
+        return Vec::new();
+    }
+    let mut out = Vec::new();
+    for i in 1..chars.len() {
+        // Deletion of the character at `i`.
+        let mut v = chars.clone();
+        v.remove(i);
+        out.push(v.into_iter().collect());
+        // Substitution of the character at `i`.
+        for c in TYPO_ALPHABET.chars() {
+            if c == chars[i] {
+                continue;
+            }
+            let mut v = chars.clone();
+            v[i] = c;
+            out.push(v.into_iter().collect());
+        }
+    }
+    // Insertion before index `i`, for every position after the first character.
+    for i in 1..=chars.len() {
+        for c in TYPO_ALPHABET.chars() {
+            let mut v = chars.clone();
+            v.insert(i, c);
+            out.push(v.into_iter().collect());
+        }
+    }
+    // Adjacent transpositions, excluding the pair that would move the first character.
+    for i in 1..chars.len().saturating_sub(1) {
+        let mut v = chars.clone();
+        v.swap(i, i + 1);
+        out.push(v.into_iter().collect());
+    }
+    out
+}
+
+/// Expands `word` into itself plus every derivation within `max_typos` edits (per
+/// [single_edit_derivations]), paired with the typo penalty (edit count) at which it was first
+/// reached. Derivations are yielded in non-decreasing penalty order, and each distinct word
+/// appears only once, at its lowest penalty.
+fn keyword_derivations(word: &str, max_typos: u32) -> Vec<(String, u32)> {
+    let mut seen = std::collections::HashSet::new();
+    seen.insert(word.to_string());
+    let mut ordered = vec![(word.to_string(), 0)];
+    let mut frontier = vec![word.to_string()];
+    for penalty in 1..=max_typos {
+        let mut next_frontier = Vec::new();
+        for w in &frontier {
+            for derived in single_edit_derivations(w) {
+                if seen.insert(derived.clone()) {
+                    ordered.push((derived.clone(), penalty));
+                    next_frontier.push(derived);
+                }
+            }
+        }
+        frontier = next_frontier;
+    }
+    ordered
+}
@@ -2,7 +2,28 @@ use crate::classify;
 use crate::expr::Expr;
 use crate::precedence::Precedence;
 
-pub(crate) struct FixupContext {
+/// Tracks enough context about where an expression sits in its enclosing
+/// syntax (statement, match arm, condition, or a subexpression thereof) to
+/// decide where parentheses must be inserted so that printing followed by
+/// re-parsing round-trips.
+///
+/// This is reusable by anything that assembles its own token stream and
+/// needs the same statement/match-arm/condition parenthesization that
+/// `ToTokens for Expr` applies by default: construct a `FixupContext` with
+/// [`new_stmt`], [`new_match_arm`], or [`new_condition`], thread it through
+/// the expression tree via [`leftmost_subexpression`], [`subsequent_subexpression`],
+/// etc. as each subexpression is printed, and consult
+/// [`would_cause_statement_boundary`] / [`needs_group_as_let_scrutinee`] to
+/// decide where to emit grouping parens.
+///
+/// [`new_stmt`]: FixupContext::new_stmt
+/// [`new_match_arm`]: FixupContext::new_match_arm
+/// [`new_condition`]: FixupContext::new_condition
+/// [`leftmost_subexpression`]: FixupContext::leftmost_subexpression
+/// [`subsequent_subexpression`]: FixupContext::subsequent_subexpression
+/// [`would_cause_statement_boundary`]: FixupContext::would_cause_statement_boundary
+/// [`needs_group_as_let_scrutinee`]: FixupContext::needs_group_as_let_scrutinee
+pub struct FixupContext {
     // Print expression such that it can be parsed back as a statement
     // consisting of the original expression.
     //
@@ -85,6 +106,22 @@ pub(crate) struct FixupContext {
     //     }
     //
     parenthesize_exterior_struct_lit: bool,
+
+    // Whether the expression being printed is one operand of a let-chain
+    // (`$a && let _ = $b && $c`), as opposed to the sole scrutinee of a
+    // legacy `if let`/`while let`.
+    //
+    // This matters for `needs_group_as_let_scrutinee`: the chain's own `&&`
+    // separators are structural and must not be parenthesized away, but an
+    // operand that is itself a lower-precedence expression (for example one
+    // containing `||`) still needs grouping so it doesn't get swallowed into
+    // the surrounding chain.
+    //
+    //     if let _ = a && let _ = b {}  // chain: bare `&&` between operands
+    //
+    //     if (a || b) {}  // non-chain scrutinee: whole thing still grouped
+    //
+    let_chain_operand: bool,
 }
 
 impl FixupContext {
@@ -96,6 +133,7 @@ impl FixupContext {
         match_arm: false,
         leftmost_subexpression_in_match_arm: false,
         parenthesize_exterior_struct_lit: false,
+        let_chain_operand: false,
     };
 
     /// Create the initial fixup for printing an expression in statement
@@ -127,6 +165,17 @@ impl FixupContext {
         }
     }
 
+    /// Create the initial fixup for printing one operand of a let-chain
+    /// (`$a && let _ = $b && $c`) making up an `if`/`while` condition, as
+    /// opposed to the single scrutinee of a legacy `if let`/`while let`.
+    pub fn new_let_chain_operand() -> Self {
+        FixupContext {
+            parenthesize_exterior_struct_lit: true,
+            let_chain_operand: true,
+            ..FixupContext::NONE
+        }
+    }
+
     /// Transform this fixup into the one that should apply when printing the
     /// leftmost subexpression of the current expression.
     ///
@@ -189,7 +238,8 @@ impl FixupContext {
     pub fn would_cause_statement_boundary(self, expr: &Expr) -> bool {
         (self.leftmost_subexpression_in_stmt && !classify::requires_semi_to_be_stmt(expr))
             || (self.leftmost_subexpression_in_match_arm
-                && !classify::requires_comma_to_be_match_arm(expr))
+                && !classify::requires_comma_to_be_match_arm(expr)
+                && classify::expr_is_complete(expr))
     }
 
     /// Determine whether parentheses are needed around the given `let`
@@ -205,7 +255,15 @@ impl FixupContext {
     ///     "let chain".
     pub fn needs_group_as_let_scrutinee(self, expr: &Expr) -> bool {
         self.parenthesize_exterior_struct_lit && classify::confusable_with_adjacent_block(expr)
-            || Precedence::of_rhs(expr) <= Precedence::And
+            || if self.let_chain_operand {
+                // The chain's own `&&` separators are not part of any single
+                // operand, so an operand sitting at exactly `Precedence::And`
+                // is this operator itself and needs no parens; anything
+                // lower (`||`, `..`, assignment, etc.) still does.
+                Precedence::of_rhs(expr) < Precedence::And
+            } else {
+                Precedence::of_rhs(expr) <= Precedence::And
+            }
     }
 }
 
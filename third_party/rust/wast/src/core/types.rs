@@ -1,4 +1,5 @@
 use crate::core::*;
+use crate::encode::Encode;
 use crate::kw;
 use crate::parser::{Cursor, Parse, Parser, Peek, Result};
 use crate::token::{Id, Index, LParen, NameAnnotation, Span};
@@ -89,6 +90,11 @@ pub enum HeapType<'a> {
     None,
     /// The bottom type of the exnref hierarchy. Part of the exceptions proposal.
     NoExn,
+    /// A reference to a continuation. Part of the stack-switching proposal.
+    Cont,
+    /// The bottom type of the contref hierarchy. Part of the stack-switching
+    /// proposal.
+    NoCont,
     /// A reference to a concrete function, struct, or array type defined by
     /// Wasm: `ref T`. This is part of the function references and GC proposals.
     Concrete(Index<'a>),
@@ -130,6 +136,12 @@ impl<'a> Parse<'a> for HeapType<'a> {
         } else if l.peek::<kw::noexn>()? {
             parser.parse::<kw::noexn>()?;
             Ok(HeapType::NoExn)
+        } else if l.peek::<kw::cont>()? {
+            parser.parse::<kw::cont>()?;
+            Ok(HeapType::Cont)
+        } else if l.peek::<kw::nocont>()? {
+            parser.parse::<kw::nocont>()?;
+            Ok(HeapType::NoCont)
         } else if l.peek::<kw::none>()? {
             parser.parse::<kw::none>()?;
             Ok(HeapType::None)
@@ -154,6 +166,8 @@ impl<'a> Peek for HeapType<'a> {
             || kw::nofunc::peek(cursor)?
             || kw::noextern::peek(cursor)?
             || kw::noexn::peek(cursor)?
+            || kw::cont::peek(cursor)?
+            || kw::nocont::peek(cursor)?
             || kw::none::peek(cursor)?
             || (LParen::peek(cursor)? && kw::r#type::peek2(cursor)?))
     }
@@ -266,6 +280,22 @@ impl<'a> RefType<'a> {
             heap: HeapType::NoExn,
         }
     }
+
+    /// A `contref` as an abbreviation for `(ref null cont)`.
+    pub fn cont() -> Self {
+        RefType {
+            nullable: true,
+            heap: HeapType::Cont,
+        }
+    }
+
+    /// A `nullcontref` as an abbreviation for `(ref null nocont)`.
+    pub fn nullcontref() -> Self {
+        RefType {
+            nullable: true,
+            heap: HeapType::NoCont,
+        }
+    }
 }
 
 impl<'a> Parse<'a> for RefType<'a> {
@@ -304,6 +334,12 @@ impl<'a> Parse<'a> for RefType<'a> {
         } else if l.peek::<kw::nullexnref>()? {
             parser.parse::<kw::nullexnref>()?;
             Ok(RefType::nullexnref())
+        } else if l.peek::<kw::contref>()? {
+            parser.parse::<kw::contref>()?;
+            Ok(RefType::cont())
+        } else if l.peek::<kw::nullcontref>()? {
+            parser.parse::<kw::nullcontref>()?;
+            Ok(RefType::nullcontref())
         } else if l.peek::<kw::nullref>()? {
             parser.parse::<kw::nullref>()?;
             Ok(RefType::nullref())
@@ -346,6 +382,8 @@ impl<'a> Peek for RefType<'a> {
             || kw::nullfuncref::peek(cursor)?
             || kw::nullexternref::peek(cursor)?
             || kw::nullexnref::peek(cursor)?
+            || kw::contref::peek(cursor)?
+            || kw::nullcontref::peek(cursor)?
             || kw::nullref::peek(cursor)?
             || (LParen::peek(cursor)? && kw::r#ref::peek2(cursor)?))
     }
@@ -467,16 +505,31 @@ impl<'a> Parse<'a> for Limits {
 pub struct TableType<'a> {
     /// Limits on the element sizes of this table
     pub limits: Limits,
+    /// Whether or not this is a shared table, as part of the
+    /// shared-everything-threads proposal.
+    pub shared: bool,
     /// The type of element stored in this table
     pub elem: RefType<'a>,
 }
 
 impl<'a> Parse<'a> for TableType<'a> {
     fn parse(parser: Parser<'a>) -> Result<Self> {
-        Ok(TableType {
-            limits: parser.parse()?,
-            elem: parser.parse()?,
-        })
+        if parser.peek2::<kw::shared>()? {
+            parser.parens(|p| {
+                p.parse::<kw::shared>()?;
+                Ok(TableType {
+                    limits: p.parse()?,
+                    shared: true,
+                    elem: p.parse()?,
+                })
+            })
+        } else {
+            Ok(TableType {
+                limits: parser.parse()?,
+                shared: false,
+                elem: parser.parse()?,
+            })
+        }
     }
 }
 
@@ -719,6 +772,58 @@ impl<'a> Parse<'a> for ArrayType<'a> {
     }
 }
 
+/// A tag type, as used by the exceptions proposal to declare, import, or
+/// export a `tag`.
+///
+/// A tag's signature may be given inline, reusing the same `(param
+/// ...)`/`(result ...)` grammar as [`FunctionType`], or by reference to a
+/// previously declared function type via `(type $idx)`, just like
+/// [`TypeUse`].
+#[derive(Clone, Debug)]
+pub struct TagType<'a> {
+    /// The kind of tag being defined.
+    pub attribute: Attribute,
+    /// The type signature of this tag, either inline or by reference to an
+    /// existing function type.
+    pub func_ty: TypeUse<'a, FunctionType<'a>>,
+}
+
+/// The kind of a [`TagType`].
+///
+/// Currently the exceptions proposal only defines one kind of tag, but this
+/// is a separate enum (rather than folding `Exception` directly into
+/// `TagType`) so additional tag kinds can be added without breaking the
+/// `TagType` shape.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub enum Attribute {
+    Exception,
+}
+
+impl<'a> Parse<'a> for TagType<'a> {
+    fn parse(parser: Parser<'a>) -> Result<Self> {
+        let attribute = if parser.peek::<kw::exception>()? {
+            parser.parse::<kw::exception>()?;
+            Attribute::Exception
+        } else {
+            Attribute::Exception
+        };
+        let func_ty = parser.parse()?;
+        Ok(TagType { attribute, func_ty })
+    }
+}
+
+impl<'a> Peek for TagType<'a> {
+    fn peek(cursor: Cursor<'_>) -> Result<bool> {
+        Ok(kw::exception::peek(cursor)?
+            || FunctionType::peek(cursor)?
+            || (LParen::peek(cursor)? && kw::r#type::peek2(cursor)?))
+    }
+    fn display() -> &'static str {
+        "tag type"
+    }
+}
+
 /// The type of an exported item from a module or instance.
 #[derive(Debug, Clone)]
 pub struct ExportType<'a> {
@@ -748,6 +853,9 @@ pub enum TypeDef<'a> {
     Struct(StructType<'a>),
     /// An array type definition.
     Array(ArrayType<'a>),
+    /// A continuation type definition, as part of the stack-switching
+    /// proposal.
+    Cont(ContType<'a>),
 }
 
 impl<'a> Parse<'a> for TypeDef<'a> {
@@ -762,12 +870,32 @@ impl<'a> Parse<'a> for TypeDef<'a> {
         } else if l.peek::<kw::array>()? {
             parser.parse::<kw::array>()?;
             Ok(TypeDef::Array(parser.parse()?))
+        } else if l.peek::<kw::cont>()? {
+            parser.parse::<kw::cont>()?;
+            Ok(TypeDef::Cont(parser.parse()?))
         } else {
             Err(l.error())
         }
     }
 }
 
+/// A continuation type, as part of the stack-switching proposal:
+/// `(cont $ft)`, where `$ft` refers to a previously declared function
+/// type describing the continuation's stack shape.
+#[derive(Clone, Debug)]
+pub struct ContType<'a> {
+    /// The function type this continuation's stack shape is drawn from.
+    pub func_ty: Index<'a>,
+}
+
+impl<'a> Parse<'a> for ContType<'a> {
+    fn parse(parser: Parser<'a>) -> Result<Self> {
+        Ok(ContType {
+            func_ty: parser.parse()?,
+        })
+    }
+}
+
 /// A type declaration in a module
 #[derive(Debug)]
 pub struct Type<'a> {
@@ -780,10 +908,16 @@ pub struct Type<'a> {
     pub name: Option<NameAnnotation<'a>>,
     /// The type that we're declaring.
     pub def: TypeDef<'a>,
-    /// The declared parent type of this definition.
-    pub parent: Option<Index<'a>>,
+    /// The declared parent types of this definition, from a `sub` clause.
+    ///
+    /// The GC proposal allows zero or more supertypes here, though in
+    /// practice a single supertype is the common case.
+    pub parents: Vec<Index<'a>>,
     /// Whether this type is final or not. By default types are final.
     pub final_type: Option<bool>,
+    /// Whether this is a `(shared ...)` composite type, as part of the
+    /// shared-everything-threads proposal.
+    pub shared: bool,
 }
 
 impl<'a> Peek for Type<'a> {
@@ -801,7 +935,7 @@ impl<'a> Parse<'a> for Type<'a> {
         let id = parser.parse()?;
         let name = parser.parse()?;
 
-        let (parent, def, final_type) = if parser.peek2::<kw::sub>()? {
+        let (parents, shared, def, final_type) = if parser.peek2::<kw::sub>()? {
             parser.parens(|parser| {
                 parser.parse::<kw::sub>()?;
 
@@ -812,16 +946,16 @@ impl<'a> Parse<'a> for Type<'a> {
                     Some(false)
                 };
 
-                let parent = if parser.peek::<Index<'a>>()? {
-                    parser.parse()?
-                } else {
-                    None
-                };
-                let def = parser.parens(|parser| parser.parse())?;
-                Ok((parent, def, final_type))
+                let mut parents = Vec::new();
+                while parser.peek::<Index<'a>>()? {
+                    parents.push(parser.parse()?);
+                }
+                let (shared, def) = parser.parens(parse_comptype)?;
+                Ok((parents, shared, def, final_type))
             })?
         } else {
-            (None, parser.parens(|parser| parser.parse())?, None)
+            let (shared, def) = parser.parens(parse_comptype)?;
+            (Vec::new(), shared, def, None)
         };
 
         Ok(Type {
@@ -829,13 +963,32 @@ impl<'a> Parse<'a> for Type<'a> {
             id,
             name,
             def,
-            parent,
+            parents,
             final_type,
+            shared,
         })
     }
 }
 
-/// A recursion group declaration in a module
+/// Parses a composite type (`func`/`struct`/`array`), optionally wrapped in
+/// `(shared ...)` per the shared-everything-threads proposal, returning
+/// whether it was shared alongside the parsed definition.
+fn parse_comptype<'a>(parser: Parser<'a>) -> Result<(bool, TypeDef<'a>)> {
+    if parser.peek::<kw::shared>()? {
+        parser.parse::<kw::shared>()?;
+        Ok((true, parser.parens(|parser| parser.parse())?))
+    } else {
+        Ok((false, parser.parse()?))
+    }
+}
+
+/// A recursion group declaration in a module.
+///
+/// Types inside a `rec` may reference each other by index, including
+/// indices that come later in the group, so name resolution must register
+/// every type in the group (they occupy a contiguous index range) before
+/// resolving any field or element references among them. A bare `(type
+/// ...)` outside of a `rec` is equivalent to a singleton recursion group.
 #[derive(Debug)]
 pub struct Rec<'a> {
     /// Where this recursion group was defined.
@@ -899,3 +1052,608 @@ impl<'a> From<TypeUse<'a, FunctionTypeNoNames<'a>>> for TypeUse<'a, FunctionType
         }
     }
 }
+
+impl Encode for ValType<'_> {
+    fn encode(&self, e: &mut Vec<u8>) {
+        match self {
+            ValType::I32 => e.push(0x7f),
+            ValType::I64 => e.push(0x7e),
+            ValType::F32 => e.push(0x7d),
+            ValType::F64 => e.push(0x7c),
+            ValType::V128 => e.push(0x7b),
+            ValType::Ref(ty) => ty.encode(e),
+        }
+    }
+}
+
+impl Encode for HeapType<'_> {
+    fn encode(&self, e: &mut Vec<u8>) {
+        match self {
+            // Note that this is encoded as a negative integer in sLEB128
+            // format, so the single-byte opcodes below are all written out
+            // with their high bit already clear.
+            HeapType::Func => e.push(0x70),
+            HeapType::Extern => e.push(0x6f),
+            HeapType::Exn => e.push(0x69),
+            HeapType::Any => e.push(0x6e),
+            HeapType::Eq => e.push(0x6d),
+            HeapType::Struct => e.push(0x6b),
+            HeapType::Array => e.push(0x6a),
+            HeapType::I31 => e.push(0x6c),
+            HeapType::NoFunc => e.push(0x73),
+            HeapType::NoExtern => e.push(0x72),
+            HeapType::None => e.push(0x71),
+            HeapType::NoExn => e.push(0x74),
+            HeapType::Cont => e.push(0x68),
+            HeapType::NoCont => e.push(0x75),
+            HeapType::Concrete(index) => index.encode(e),
+        }
+    }
+}
+
+impl Encode for RefType<'_> {
+    fn encode(&self, e: &mut Vec<u8>) {
+        match (self.nullable, self.heap) {
+            // Nullable `func`, `extern` and `exn` references get their
+            // single-byte shorthand encoding; every other reference,
+            // including the non-nullable forms of these three, is written
+            // as a `0x63`/`0x64` prefix followed by the heap type.
+            (true, HeapType::Func) => e.push(0x70),
+            (true, HeapType::Extern) => e.push(0x6f),
+            (true, HeapType::Exn) => e.push(0x69),
+
+            (true, heap) => {
+                e.push(0x63);
+                heap.encode(e);
+            }
+            (false, heap) => {
+                e.push(0x64);
+                heap.encode(e);
+            }
+        }
+    }
+}
+
+impl Encode for StorageType<'_> {
+    fn encode(&self, e: &mut Vec<u8>) {
+        match self {
+            StorageType::I8 => e.push(0x78),
+            StorageType::I16 => e.push(0x77),
+            StorageType::Val(ty) => ty.encode(e),
+        }
+    }
+}
+
+impl Encode for GlobalType<'_> {
+    fn encode(&self, e: &mut Vec<u8>) {
+        self.ty.encode(e);
+        let flag = (self.mutable as u8) | ((self.shared as u8) << 1);
+        e.push(flag);
+    }
+}
+
+impl Encode for Limits {
+    fn encode(&self, e: &mut Vec<u8>) {
+        // The flags byte packs whether a maximum is present (bit 0),
+        // whether the memory or table is shared (bit 1) and whether the
+        // limits are 64-bit (bit 2). Bit 1 is never set here: `MemoryType`
+        // and `TableType` write their own flags byte when shared.
+        let flag_max = self.max.is_some() as u8;
+        let flag_64 = (self.is64 as u8) << 2;
+        let flags = flag_max | flag_64;
+        e.push(flags);
+        self.encode_bounds(e);
+    }
+}
+
+impl Encode for TableType<'_> {
+    fn encode(&self, e: &mut Vec<u8>) {
+        self.elem.encode(e);
+        // A shared table, like a shared memory, sets the shared bit (bit 1)
+        // of its limits flags byte.
+        if self.shared {
+            let flag_max = self.limits.max.is_some() as u8;
+            let flag_64 = (self.limits.is64 as u8) << 2;
+            e.push(flag_max | flag_64 | 0x02);
+            self.limits.encode_bounds(e);
+        } else {
+            self.limits.encode(e);
+        }
+    }
+}
+
+impl Encode for MemoryType {
+    fn encode(&self, e: &mut Vec<u8>) {
+        match self.page_size_log2 {
+            Some(log2) => {
+                // The custom-page-size flag doesn't fit in `Limits`'s own
+                // flags byte, so it's folded in here instead, followed by
+                // the page size itself.
+                let flag_max = self.limits.max.is_some() as u8;
+                let flag_64 = (self.limits.is64 as u8) << 2;
+                let flag_shared = (self.shared as u8) << 1;
+                let flag_page_size = 1u8 << 3;
+                e.push(flag_max | flag_64 | flag_shared | flag_page_size);
+                self.limits.encode_bounds(e);
+                log2.encode(e);
+            }
+            None if self.shared => {
+                let flag_max = self.limits.max.is_some() as u8;
+                let flag_64 = (self.limits.is64 as u8) << 2;
+                e.push(flag_max | flag_64 | 0x02);
+                self.limits.encode_bounds(e);
+            }
+            None => self.limits.encode(e),
+        }
+    }
+}
+
+impl Limits {
+    fn encode_bounds(&self, e: &mut Vec<u8>) {
+        if self.is64 {
+            self.min.encode(e);
+            if let Some(max) = self.max {
+                max.encode(e);
+            }
+        } else {
+            u32::try_from(self.min).unwrap().encode(e);
+            if let Some(max) = self.max {
+                u32::try_from(max).unwrap().encode(e);
+            }
+        }
+    }
+}
+
+impl Encode for FunctionType<'_> {
+    fn encode(&self, e: &mut Vec<u8>) {
+        e.push(0x60);
+        self.params.len().encode(e);
+        for (_, _, ty) in self.params.iter() {
+            ty.encode(e);
+        }
+        self.results.len().encode(e);
+        for ty in self.results.iter() {
+            ty.encode(e);
+        }
+    }
+}
+
+impl Encode for StructType<'_> {
+    fn encode(&self, e: &mut Vec<u8>) {
+        self.fields.len().encode(e);
+        for field in self.fields.iter() {
+            field.ty.encode(e);
+            e.push(field.mutable as u8);
+        }
+    }
+}
+
+impl Encode for ArrayType<'_> {
+    fn encode(&self, e: &mut Vec<u8>) {
+        self.ty.encode(e);
+        e.push(self.mutable as u8);
+    }
+}
+
+impl Encode for ContType<'_> {
+    fn encode(&self, e: &mut Vec<u8>) {
+        e.push(0x5d);
+        self.func_ty.encode(e);
+    }
+}
+
+impl<'a> Encode for TypeDef<'a> {
+    fn encode(&self, e: &mut Vec<u8>) {
+        match self {
+            TypeDef::Func(ty) => ty.encode(e),
+            TypeDef::Struct(ty) => {
+                e.push(0x5f);
+                ty.encode(e);
+            }
+            TypeDef::Array(ty) => {
+                e.push(0x5e);
+                ty.encode(e);
+            }
+            TypeDef::Cont(ty) => ty.encode(e),
+        }
+    }
+}
+
+impl<'a> Encode for Type<'a> {
+    fn encode(&self, e: &mut Vec<u8>) {
+        // The `sub`/`sub final` wrapper is only written when it's actually
+        // needed to carry supertypes or a finality written out in the text
+        // (`sub` or `sub final`); a type with neither is encoded as its bare
+        // comptype.
+        // The shared marker, when present, always immediately precedes the
+        // comptype itself, inside any `sub` wrapping.
+        if self.parents.is_empty() && self.final_type.is_none() {
+            self.encode_comptype(e);
+            return;
+        }
+
+        e.push(if self.final_type.unwrap_or(true) { 0x4f } else { 0x50 });
+        self.parents.len().encode(e);
+        for parent in self.parents.iter() {
+            parent.encode(e);
+        }
+        self.encode_comptype(e);
+    }
+}
+
+impl<'a> Type<'a> {
+    fn encode_comptype(&self, e: &mut Vec<u8>) {
+        if self.shared {
+            e.push(0x65);
+        }
+        self.def.encode(e);
+    }
+}
+
+impl<'a> Encode for Rec<'a> {
+    fn encode(&self, e: &mut Vec<u8>) {
+        e.push(0x4e);
+        self.types.len().encode(e);
+        for ty in self.types.iter() {
+            ty.encode(e);
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for ValType<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValType::I32 => f.write_str("i32"),
+            ValType::I64 => f.write_str("i64"),
+            ValType::F32 => f.write_str("f32"),
+            ValType::F64 => f.write_str("f64"),
+            ValType::V128 => f.write_str("v128"),
+            ValType::Ref(ty) => std::fmt::Display::fmt(ty, f),
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for HeapType<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeapType::Func => f.write_str("func"),
+            HeapType::Extern => f.write_str("extern"),
+            HeapType::Exn => f.write_str("exn"),
+            HeapType::Any => f.write_str("any"),
+            HeapType::Eq => f.write_str("eq"),
+            HeapType::Struct => f.write_str("struct"),
+            HeapType::Array => f.write_str("array"),
+            HeapType::I31 => f.write_str("i31"),
+            HeapType::NoFunc => f.write_str("nofunc"),
+            HeapType::NoExtern => f.write_str("noextern"),
+            HeapType::None => f.write_str("none"),
+            HeapType::NoExn => f.write_str("noexn"),
+            HeapType::Cont => f.write_str("cont"),
+            HeapType::NoCont => f.write_str("nocont"),
+            HeapType::Concrete(index) => write!(f, "{index}"),
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for RefType<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Prefer the well-known shorthands (`funcref`, `i31ref`, ...) over
+        // the general `(ref null? ...)` form wherever one applies, since
+        // that's what a human author would actually write.
+        match (self.nullable, self.heap) {
+            (true, HeapType::Func) => f.write_str("funcref"),
+            (true, HeapType::Extern) => f.write_str("externref"),
+            (true, HeapType::Exn) => f.write_str("exnref"),
+            (true, HeapType::Any) => f.write_str("anyref"),
+            (true, HeapType::Eq) => f.write_str("eqref"),
+            (true, HeapType::Struct) => f.write_str("structref"),
+            (true, HeapType::Array) => f.write_str("arrayref"),
+            (true, HeapType::I31) => f.write_str("i31ref"),
+            (true, HeapType::NoFunc) => f.write_str("nullfuncref"),
+            (true, HeapType::NoExtern) => f.write_str("nullexternref"),
+            (true, HeapType::None) => f.write_str("nullref"),
+            (true, HeapType::NoExn) => f.write_str("nullexnref"),
+            (true, HeapType::Cont) => f.write_str("contref"),
+            (true, HeapType::NoCont) => f.write_str("nullcontref"),
+            (true, heap) => write!(f, "(ref null {heap})"),
+            (false, heap) => write!(f, "(ref {heap})"),
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for StorageType<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageType::I8 => f.write_str("i8"),
+            StorageType::I16 => f.write_str("i16"),
+            StorageType::Val(ty) => std::fmt::Display::fmt(ty, f),
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for GlobalType<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.shared, self.mutable) {
+            (false, false) => write!(f, "{}", self.ty),
+            (false, true) => write!(f, "(mut {})", self.ty),
+            (true, false) => write!(f, "(shared {})", self.ty),
+            (true, true) => write!(f, "(shared (mut {}))", self.ty),
+        }
+    }
+}
+
+impl std::fmt::Display for Limits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is64 {
+            write!(f, "i64 {}", self.min)?;
+        } else {
+            write!(f, "{}", self.min)?;
+        }
+        if let Some(max) = self.max {
+            write!(f, " {max}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> std::fmt::Display for TableType<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.shared {
+            write!(f, "(shared {}) {}", self.limits, self.elem)
+        } else {
+            write!(f, "{} {}", self.limits, self.elem)
+        }
+    }
+}
+
+impl std::fmt::Display for MemoryType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.shared {
+            write!(f, "(shared {})", self.limits)?;
+        } else {
+            write!(f, "{}", self.limits)?;
+        }
+        if let Some(log2) = self.page_size_log2 {
+            write!(f, " (pagesize {})", 1u64 << log2)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> std::fmt::Display for FunctionType<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (id, _name, ty) in self.params.iter() {
+            match id {
+                Some(id) => write!(f, "(param {id} {ty}) ")?,
+                None => write!(f, "(param {ty}) ")?,
+            }
+        }
+        if !self.results.is_empty() {
+            f.write_str("(result")?;
+            for ty in self.results.iter() {
+                write!(f, " {ty}")?;
+            }
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> std::fmt::Display for StructType<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("(struct")?;
+        for field in self.fields.iter() {
+            write!(f, " {field}")?;
+        }
+        f.write_str(")")
+    }
+}
+
+impl<'a> std::fmt::Display for StructField<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("(field ")?;
+        if let Some(id) = self.id {
+            write!(f, "{id} ")?;
+        }
+        if self.mutable {
+            write!(f, "(mut {}))", self.ty)
+        } else {
+            write!(f, "{})", self.ty)
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for ArrayType<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("(array ")?;
+        if self.mutable {
+            write!(f, "(mut {}))", self.ty)
+        } else {
+            write!(f, "{})", self.ty)
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for ContType<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(cont {})", self.func_ty)
+    }
+}
+
+impl<'a> std::fmt::Display for Type<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(type")?;
+        if let Some(id) = self.id {
+            write!(f, " {id}")?;
+        }
+        f.write_str(" ")?;
+
+        let def = match &self.def {
+            TypeDef::Func(ty) => format!("(func {ty})"),
+            TypeDef::Struct(ty) => ty.to_string(),
+            TypeDef::Array(ty) => ty.to_string(),
+            TypeDef::Cont(ty) => ty.to_string(),
+        };
+        let def = if self.shared {
+            format!("(shared {def})")
+        } else {
+            def
+        };
+
+        if self.parents.is_empty() && self.final_type.is_none() {
+            write!(f, "{def})")
+        } else {
+            write!(f, "(sub")?;
+            if self.final_type == Some(true) {
+                write!(f, " final")?;
+            }
+            for parent in self.parents.iter() {
+                write!(f, " {parent}")?;
+            }
+            write!(f, " {def}))")
+        }
+    }
+}
+
+/// The structural signature of a type definition, used by
+/// [`TypeCanonicalizer`] to recognize when two inline definitions describe
+/// the same type and can therefore share one type-section entry.
+///
+/// This intentionally ignores everything that doesn't affect the binary
+/// encoding of the definition itself (parameter names, `id`/`name`
+/// annotations) so that e.g. `(func (param $x i32))` and `(func (param
+/// i32))` are considered the same signature.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum TypeSignature<'a> {
+    Func(Vec<ValType<'a>>, Vec<ValType<'a>>),
+    Struct(Vec<(bool, StorageType<'a>)>),
+    Array(bool, StorageType<'a>),
+}
+
+impl<'a> TypeSignature<'a> {
+    fn of(def: &TypeDef<'a>) -> Option<Self> {
+        match def {
+            TypeDef::Func(ty) => Some(TypeSignature::Func(
+                ty.params.iter().map(|(_, _, ty)| *ty).collect(),
+                ty.results.to_vec(),
+            )),
+            TypeDef::Struct(ty) => Some(TypeSignature::Struct(
+                ty.fields.iter().map(|f| (f.mutable, f.ty)).collect(),
+            )),
+            TypeDef::Array(ty) => Some(TypeSignature::Array(ty.mutable, ty.ty)),
+            // Continuation types reference another type by index rather
+            // than describing a structural shape of their own, so there's
+            // nothing here to intern against.
+            TypeDef::Cont(_) => None,
+        }
+    }
+}
+
+/// A previously-registered type's `sub`/`final` shape: whether it's final,
+/// and how many supertypes it declares. Two types with the same structural
+/// signature but a different shape here aren't interchangeable.
+type Annotations = (bool, usize);
+
+/// Interns inline `TypeUse`/field-type definitions against a module's
+/// declared types — including types nested inside `rec` groups — so that
+/// structurally identical inline definitions collapse to a single shared
+/// type-section entry instead of each synthesizing a fresh duplicate.
+///
+/// Usage: seed the canonicalizer with every `Type`/`Rec` already in the
+/// module via [`register`]/[`register_rec`], in declaration order, then
+/// run each inline definition encountered elsewhere in the module (e.g. on
+/// a `TypeUse`) through [`intern`]. This is meant to run as a pass over
+/// the module immediately before binary encoding.
+///
+/// [`register`]: TypeCanonicalizer::register
+/// [`register_rec`]: TypeCanonicalizer::register_rec
+/// [`intern`]: TypeCanonicalizer::intern
+#[derive(Debug, Default)]
+pub struct TypeCanonicalizer<'a> {
+    len: usize,
+    by_signature: std::collections::HashMap<TypeSignature<'a>, Vec<(Annotations, Index<'a>)>>,
+}
+
+impl<'a> TypeCanonicalizer<'a> {
+    /// Creates an empty canonicalizer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a single already-declared type at the next index,
+    /// recording its signature for future lookups.
+    pub fn register(&mut self, ty: &Type<'a>) {
+        let index = Index::Num(self.len as u32, ty.span);
+        self.len += 1;
+        if let Some(signature) = TypeSignature::of(&ty.def) {
+            let annotations = (ty.final_type.unwrap_or(true), ty.parents.len());
+            // The first declaration of a given shape wins; later
+            // identical declarations are themselves candidates for
+            // removal by a future pass, but this canonicalizer only
+            // rewrites inline `TypeUse`s, not existing explicit `type`
+            // declarations.
+            self.by_signature
+                .entry(signature)
+                .or_default()
+                .push((annotations, index));
+        }
+    }
+
+    /// Registers every type in a `rec` group.
+    ///
+    /// All of a group's types occupy a contiguous index range and may
+    /// reference each other, so they must be registered together before
+    /// any of them is used as a dedup target.
+    pub fn register_rec(&mut self, rec: &Rec<'a>) {
+        for ty in rec.types.iter() {
+            self.register(ty);
+        }
+    }
+
+    /// Looks up or allocates an index for an inline function signature
+    /// encountered on a `TypeUse`, returning the index of a matching
+    /// previously-registered type if one exists, or allocating a fresh
+    /// index (at `span`) and registering `inline` under it otherwise.
+    ///
+    /// When `keep_distinct_annotations` is `true`, a matching signature is
+    /// only reused if it also has the same `sub`/`final` shape as
+    /// `final_type` and the number of `parents` describe; pass `false` to
+    /// match on structural shape
+    /// alone, reusing the first registration of that shape regardless of
+    /// its annotations.
+    pub fn intern(
+        &mut self,
+        inline: &FunctionType<'a>,
+        final_type: Option<bool>,
+        parents: &[Index<'a>],
+        keep_distinct_annotations: bool,
+        span: Span,
+    ) -> Index<'a> {
+        let signature = TypeSignature::Func(
+            inline.params.iter().map(|(_, _, ty)| *ty).collect(),
+            inline.results.to_vec(),
+        );
+        let wanted = (final_type.unwrap_or(true), parents.len());
+
+        if let Some(candidates) = self.by_signature.get(&signature) {
+            let found = if keep_distinct_annotations {
+                candidates
+                    .iter()
+                    .find(|(annotations, _)| *annotations == wanted)
+            } else {
+                candidates.first()
+            };
+            if let Some((_, index)) = found {
+                return *index;
+            }
+        }
+
+        let index = Index::Num(self.len as u32, span);
+        self.len += 1;
+        self.by_signature
+            .entry(signature)
+            .or_default()
+            .push((wanted, index));
+        index
+    }
+}
@@ -32,10 +32,9 @@ use crate::{
         ResourceUses, UsageConflict,
     },
 };
-use hal::TextureUses;
+use hal::{TextureBarrier, TextureUses};
 
 use arrayvec::ArrayVec;
-use naga::FastHashMap;
 
 use wgt::{strict_assert, strict_assert_eq};
 
@@ -128,25 +127,111 @@ impl ComplexTextureState {
 
     /// Convert a complex state into an iterator over all states stored.
     ///
+    /// Adjacent mips whose per-layer states are identical are coalesced into
+    /// a single entry spanning the whole mip run, so downstream barrier
+    /// generation emits one transition per uniform mip range instead of one
+    /// per mip.
+    ///
     /// [`Self::from_selector_state_iter`] can be used to consume such an iterator.
     fn to_selector_state_iter(
         &self,
     ) -> impl Iterator<Item = (TextureSelector, TextureUses)> + Clone + '_ {
-        self.mips.iter().enumerate().flat_map(|(mip, inner)| {
+        let mut runs: Vec<(Range<u32>, Vec<(Range<u32>, TextureUses)>)> = Vec::new();
+        for (mip, inner) in self.mips.iter().enumerate() {
             let mip = mip as u32;
-            {
-                inner.iter().map(move |&(ref layers, inner)| {
-                    (
-                        TextureSelector {
-                            mips: mip..mip + 1,
-                            layers: layers.clone(),
-                        },
-                        inner,
-                    )
-                })
+            let layers: Vec<(Range<u32>, TextureUses)> = inner
+                .iter()
+                .map(|&(ref layers, state)| (layers.clone(), state))
+                .collect();
+
+            match runs.last_mut() {
+                Some((mip_range, last_layers)) if *last_layers == layers => {
+                    mip_range.end = mip + 1;
+                }
+                _ => runs.push((mip..mip + 1, layers)),
             }
+        }
+
+        runs.into_iter().flat_map(|(mip_range, layers)| {
+            layers.into_iter().map(move |(layer_range, state)| {
+                (
+                    TextureSelector {
+                        mips: mip_range.clone(),
+                        layers: layer_range,
+                    },
+                    state,
+                )
+            })
         })
     }
+
+    /// If every subresource in this complex state holds the same
+    /// [`TextureUses`], returns that state so the caller can demote this
+    /// entry back to the `simple` representation.
+    ///
+    /// Returns `None` if the state isn't uniform, or if the uniform state is
+    /// `UNKNOWN`: `UNKNOWN` only exists as a complex-state placeholder for
+    /// subresources the tracker knows nothing about yet, and must never
+    /// escape into a simple state a transition could act on.
+    fn collapse(&self) -> Option<TextureUses> {
+        let mut states = self.mips.iter().flat_map(|mip| mip.iter().map(|&(_, state)| state));
+
+        let first = states.next()?;
+        if first == TextureUses::UNKNOWN {
+            return None;
+        }
+
+        if states.all(|state| state == first) {
+            Some(first)
+        } else {
+            None
+        }
+    }
+}
+
+/// If the complex entry at `index` in `state_set` is now uniform across all
+/// its subresources, demotes it back to a `simple` entry, keeping the
+/// invariant that a `simple` slot equal to `COMPLEX` implies a `complex`
+/// entry exists (and vice versa).
+#[inline(always)]
+fn demote_if_uniform(state_set: &mut TextureStateSet, index: usize) {
+    let uniform = state_set.complex[index].as_ref().and_then(ComplexTextureState::collapse);
+
+    if let Some(uniform) = uniform {
+        state_set.complex[index] = None;
+        unsafe { *state_set.simple.get_unchecked_mut(index) = uniform };
+    }
+}
+
+/// The state a touched index held immediately before a single
+/// [`TextureUsageScope`] merge call, recorded so it can be restored by
+/// [`TextureUsageScope::restore`].
+#[derive(Clone, Debug)]
+enum PriorTextureState {
+    Simple(TextureUses),
+    Complex(ComplexTextureState),
+}
+
+/// One entry of a [`TextureUsageScope`]'s undo log: the index touched, and
+/// the state it held right before the merge that touched it.
+#[derive(Clone, Debug)]
+struct UndoEntry {
+    index: usize,
+    prior: PriorTextureState,
+}
+
+/// A checkpoint of a [`TextureUsageScope`], captured by
+/// [`TextureUsageScope::snapshot`] and reverted to by
+/// [`TextureUsageScope::restore`].
+///
+/// This is just a position in the scope's undo log, so taking a snapshot is
+/// O(1) regardless of how many complex subresources the scope is tracking.
+/// Every merge into an already-tracked texture appends one log entry
+/// (cloning its complex state, if any), whether or not a snapshot is
+/// outstanding; the log is emptied only by [`TextureUsageScope::clear`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TrackerSnapshot {
+    generation: usize,
 }
 
 #[derive(Debug)]
@@ -187,6 +272,23 @@ impl<A: HalApi> TextureBindGroupState<A> {
             .into_iter()
     }
 
+    /// Returns the tracker index of every texture this bind group stores,
+    /// without draining the list or cloning the `Arc`s.
+    ///
+    /// Mirrors the buffer tracker's equivalent; useful for callers that only
+    /// need to know which textures were touched, such as device-side
+    /// lifetime tracking, without paying [`Self::drain_resources`]'s refcount
+    /// churn. The indices are still collected into a `Vec` while the lock is
+    /// held, so one allocation per call remains.
+    pub fn used_tracker_indices(&self) -> impl Iterator<Item = TrackerIndex> + '_ {
+        let textures = self.textures.lock();
+        textures
+            .iter()
+            .map(|v| v.texture.as_info().tracker_index())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
     /// Adds the given resource with the given state.
     pub fn add_single<'a>(
         &self,
@@ -205,17 +307,23 @@ impl<A: HalApi> TextureBindGroupState<A> {
 }
 
 /// Container for corresponding simple and complex texture states.
+///
+/// `complex` is a dense, index-keyed side table rather than a hash map:
+/// indices are the same dense slot ids used by `simple`, so a complex lookup
+/// or promotion is a direct slot access rather than a hash and probe,
+/// which matters since complex subresources are already the more expensive
+/// case to merge.
 #[derive(Debug)]
 pub(crate) struct TextureStateSet {
     simple: Vec<TextureUses>,
-    complex: FastHashMap<usize, ComplexTextureState>,
+    complex: Vec<Option<ComplexTextureState>>,
 }
 
 impl TextureStateSet {
     fn new() -> Self {
         Self {
             simple: Vec::new(),
-            complex: FastHashMap::default(),
+            complex: Vec::new(),
         }
     }
 
@@ -226,6 +334,7 @@ impl TextureStateSet {
 
     fn set_size(&mut self, size: usize) {
         self.simple.resize(size, TextureUses::UNINITIALIZED);
+        self.complex.resize_with(size, || None);
     }
 }
 
@@ -234,6 +343,11 @@ impl TextureStateSet {
 pub(crate) struct TextureUsageScope<A: HalApi> {
     set: TextureStateSet,
     metadata: ResourceMetadata<Texture<A>>,
+
+    /// Prior states of indices touched by a merge, in the order they were
+    /// touched, so a checkpoint taken with [`Self::snapshot`] can be undone
+    /// by [`Self::restore`] without deep-cloning the whole scope up front.
+    undo_log: Vec<UndoEntry>,
 }
 
 impl<A: HalApi> Default for TextureUsageScope<A> {
@@ -241,6 +355,7 @@ impl<A: HalApi> Default for TextureUsageScope<A> {
         Self {
             set: TextureStateSet::new(),
             metadata: ResourceMetadata::new(),
+            undo_log: Vec::new(),
         }
     }
 }
@@ -254,7 +369,7 @@ impl<A: HalApi> TextureUsageScope<A> {
         strict_assert!(if self.metadata.contains(index)
             && self.set.simple[index] == TextureUses::COMPLEX
         {
-            self.set.complex.contains_key(&index)
+            self.set.complex[index].is_some()
         } else {
             true
         });
@@ -263,6 +378,7 @@ impl<A: HalApi> TextureUsageScope<A> {
     pub fn clear(&mut self) {
         self.set.clear();
         self.metadata.clear();
+        self.undo_log.clear();
     }
 
     /// Sets the size of all the vectors inside the tracker.
@@ -274,6 +390,41 @@ impl<A: HalApi> TextureUsageScope<A> {
         self.metadata.set_size(size);
     }
 
+    /// Captures a checkpoint of this scope's merged state that
+    /// [`Self::restore`] can later revert to.
+    pub(crate) fn snapshot(&self) -> TrackerSnapshot {
+        TrackerSnapshot {
+            generation: self.undo_log.len(),
+        }
+    }
+
+    /// Reverts every merge performed since `snapshot` was taken, e.g. after a
+    /// render/compute pass fails validation partway through and the states it
+    /// spuriously merged must not leak into the next pass.
+    ///
+    /// This only unwinds resources that were already tracked by this scope
+    /// when the snapshot was taken; resources the scope started tracking for
+    /// the first time since then stay tracked, at the state they were first
+    /// added with, since callers that abandon a
+    /// pass also discard the command buffer recording it, and with it any use
+    /// of those resources.
+    pub(crate) fn restore(&mut self, snapshot: TrackerSnapshot) {
+        while self.undo_log.len() > snapshot.generation {
+            let UndoEntry { index, prior } = self.undo_log.pop().unwrap();
+
+            match prior {
+                PriorTextureState::Simple(state) => {
+                    self.set.complex[index] = None;
+                    self.set.simple[index] = state;
+                }
+                PriorTextureState::Complex(complex) => {
+                    self.set.simple[index] = TextureUses::COMPLEX;
+                    self.set.complex[index] = Some(complex);
+                }
+            }
+        }
+    }
+
     /// Drains all textures tracked.
     pub(crate) fn drain_resources(&mut self) -> impl Iterator<Item = Arc<Texture<A>>> + '_ {
         let resources = self.metadata.drain_resources();
@@ -317,6 +468,7 @@ impl<A: HalApi> TextureUsageScope<A> {
                     ResourceMetadataProvider::Indirect {
                         metadata: &scope.metadata,
                     },
+                    Some(&mut self.undo_log),
                 )?
             };
         }
@@ -382,6 +534,7 @@ impl<A: HalApi> TextureUsageScope<A> {
                 ResourceMetadataProvider::Direct {
                     resource: Cow::Borrowed(texture),
                 },
+                Some(&mut self.undo_log),
             )?
         };
 
@@ -389,6 +542,41 @@ impl<A: HalApi> TextureUsageScope<A> {
     }
 }
 
+/// Captures the "set a single texture's state and hand back any resulting
+/// transitions" surface shared by every texture tracker, so barrier-generating
+/// code such as `clear_texture` can be generic over whichever tracker it's
+/// handed (a command-buffer-scoped [`TextureTracker`] or a device-level one)
+/// instead of being duplicated per caller.
+///
+/// [`TextureUsageScope`] doesn't implement this trait: merging into a usage
+/// scope reports a [`UsageConflict`] rather than a [`PendingTransition`], since
+/// a scope only accumulates the union of states a pass touches and defers
+/// producing transitions to [`TextureTracker::set_from_usage_scope`] once the
+/// scope is committed against a tracker.
+pub(crate) trait TextureTrackerSetSingle<A: HalApi> {
+    /// Sets the state of a single texture, returning any transitions needed
+    /// to reach it.
+    ///
+    /// See [`TextureTracker::set_single`] for details.
+    fn set_single(
+        &mut self,
+        texture: &Arc<Texture<A>>,
+        selector: TextureSelector,
+        new_state: TextureUses,
+    ) -> Option<Drain<'_, PendingTransition<TextureUses>>>;
+}
+
+impl<A: HalApi> TextureTrackerSetSingle<A> for TextureTracker<A> {
+    fn set_single(
+        &mut self,
+        texture: &Arc<Texture<A>>,
+        selector: TextureSelector,
+        new_state: TextureUses,
+    ) -> Option<Drain<'_, PendingTransition<TextureUses>>> {
+        TextureTracker::set_single(self, texture, selector, new_state)
+    }
+}
+
 /// Stores all texture state within a command buffer or device.
 pub(crate) struct TextureTracker<A: HalApi> {
     start_set: TextureStateSet,
@@ -424,8 +612,8 @@ impl<A: HalApi> ResourceTracker for TextureTracker<A> {
                 //RefCount 2 means that resource is hold just by DeviceTracker and this suspected resource itself
                 //so it's already been released from user and so it's not inside Registry\Storage
                 if existing_ref_count <= 2 {
-                    self.start_set.complex.remove(&index);
-                    self.end_set.complex.remove(&index);
+                    self.start_set.complex[index] = None;
+                    self.end_set.complex[index] = None;
                     self.metadata.remove(index);
                     return true;
                 }
@@ -460,14 +648,14 @@ impl<A: HalApi> TextureTracker<A> {
         strict_assert!(if self.metadata.contains(index)
             && self.start_set.simple[index] == TextureUses::COMPLEX
         {
-            self.start_set.complex.contains_key(&index)
+            self.start_set.complex[index].is_some()
         } else {
             true
         });
         strict_assert!(if self.metadata.contains(index)
             && self.end_set.simple[index] == TextureUses::COMPLEX
         {
-            self.end_set.complex.contains_key(&index)
+            self.end_set.complex[index].is_some()
         } else {
             true
         });
@@ -590,6 +778,62 @@ impl<A: HalApi> TextureTracker<A> {
         Some(self.temp.drain(..))
     }
 
+    /// Sets the given state for all textures in `other`, then immediately
+    /// resolves the resulting transitions into HAL barriers, resolving each
+    /// transition's texture through `snatch_guard` inline.
+    ///
+    /// This fuses [`Self::set_from_tracker`] and [`Self::drain_transitions`]
+    /// into a single pass over `self.temp`, avoiding the intermediate `Vec`
+    /// of transitions and the parallel `Vec` of textures the two-step
+    /// version needs.
+    ///
+    /// If the ID is higher than the length of internal vectors,
+    /// the vectors will be extended. A call to set_size is not needed.
+    pub fn set_from_tracker_and_drain_transitions<'a>(
+        &'a mut self,
+        other: &'a Self,
+        snatch_guard: &'a SnatchGuard<'a>,
+    ) -> impl Iterator<Item = TextureBarrier<'a, A>> {
+        let incoming_size = other.start_set.simple.len();
+        if incoming_size > self.start_set.simple.len() {
+            self.set_size(incoming_size);
+        }
+
+        for index in other.metadata.owned_indices() {
+            self.tracker_assert_in_bounds(index);
+            other.tracker_assert_in_bounds(index);
+            unsafe {
+                let texture_selector = &other.metadata.get_resource_unchecked(index).full_range;
+                insert_or_barrier_update(
+                    texture_selector,
+                    Some(&mut self.start_set),
+                    &mut self.end_set,
+                    &mut self.metadata,
+                    index,
+                    TextureStateProvider::TextureSet {
+                        set: &other.start_set,
+                    },
+                    Some(TextureStateProvider::TextureSet {
+                        set: &other.end_set,
+                    }),
+                    ResourceMetadataProvider::Indirect {
+                        metadata: &other.metadata,
+                    },
+                    &mut self.temp,
+                );
+            }
+        }
+
+        self.temp.drain(..).map(move |pending| {
+            let tex = unsafe { self.metadata.get_resource_unchecked(pending.id as _) };
+            let texture = tex
+                .inner
+                .get(snatch_guard)
+                .expect("texture should not be destroyed while tracked");
+            pending.into_hal(texture)
+        })
+    }
+
     /// Sets the given state for all texture in the given tracker.
     ///
     /// If a transition is needed to get the texture into the needed state,
@@ -630,6 +874,70 @@ impl<A: HalApi> TextureTracker<A> {
         }
     }
 
+    /// Merges `other`'s start/end state pairs into this tracker, producing a
+    /// correctly composed start/end pair for the union of textures either
+    /// tracks.
+    ///
+    /// Unlike [`Self::set_from_tracker`], which treats `other`'s state as a
+    /// target to transition into and lets the barrier machinery reconcile
+    /// the two trackers' start states, this merges the two trackers' end
+    /// states texture by texture, checking that they can coexist the same way
+    /// [`TextureUsageScope::merge_usage_scope`] does, and is meant for
+    /// composing two trackers that haven't gone through a HAL barrier yet
+    /// (e.g. combining the trackers of two render bundles before they're
+    /// recorded into a command buffer).
+    ///
+    /// If any of the resulting states is invalid, stops the merge and
+    /// returns a usage conflict with the details of the invalid state.
+    pub fn extend_from_tracker(&mut self, other: &Self) -> Result<(), UsageConflict> {
+        let incoming_size = other.start_set.simple.len();
+        if incoming_size > self.start_set.simple.len() {
+            self.set_size(incoming_size);
+        }
+
+        for index in other.metadata.owned_indices() {
+            self.tracker_assert_in_bounds(index);
+            other.tracker_assert_in_bounds(index);
+
+            unsafe {
+                let texture_selector = &other.metadata.get_resource_unchecked(index).full_range;
+
+                if self.metadata.contains_unchecked(index) {
+                    extend_complex::<A>(
+                        texture_selector,
+                        &mut self.start_set,
+                        &mut self.end_set,
+                        index,
+                        &other.start_set,
+                        &other.end_set,
+                        ResourceMetadataProvider::Indirect {
+                            metadata: &other.metadata,
+                        },
+                    )?;
+                } else {
+                    insert(
+                        Some(texture_selector),
+                        Some(&mut self.start_set),
+                        &mut self.end_set,
+                        &mut self.metadata,
+                        index,
+                        TextureStateProvider::TextureSet {
+                            set: &other.start_set,
+                        },
+                        Some(TextureStateProvider::TextureSet {
+                            set: &other.end_set,
+                        }),
+                        ResourceMetadataProvider::Indirect {
+                            metadata: &other.metadata,
+                        },
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Sets the given state for all textures in the given UsageScope.
     ///
     /// If a transition is needed to get the textures into the needed state,
@@ -740,8 +1048,8 @@ impl<A: HalApi> TextureTracker<A> {
 
         unsafe {
             if self.metadata.contains_unchecked(index) {
-                self.start_set.complex.remove(&index);
-                self.end_set.complex.remove(&index);
+                self.start_set.complex[index] = None;
+                self.end_set.complex[index] = None;
                 self.metadata.remove(index);
                 return true;
             }
@@ -839,7 +1147,8 @@ impl<'a> TextureStateProvider<'a> {
                 let new_state = *unsafe { set.simple.get_unchecked(index) };
 
                 if new_state == TextureUses::COMPLEX {
-                    let new_complex = unsafe { set.complex.get(&index).unwrap_unchecked() };
+                    let new_complex =
+                        unsafe { set.complex.get_unchecked(index).as_ref().unwrap_unchecked() };
 
                     SingleOrManyStates::Many(EitherIter::Right(
                         new_complex.to_selector_state_iter(),
@@ -869,6 +1178,7 @@ unsafe fn insert_or_merge<A: HalApi>(
     index: usize,
     state_provider: TextureStateProvider<'_>,
     metadata_provider: ResourceMetadataProvider<'_, Texture<A>>,
+    undo_log: Option<&mut Vec<UndoEntry>>,
 ) -> Result<(), UsageConflict> {
     let currently_owned = unsafe { resource_metadata.contains_unchecked(index) };
 
@@ -895,6 +1205,7 @@ unsafe fn insert_or_merge<A: HalApi>(
             index,
             state_provider,
             metadata_provider,
+            undo_log,
         )
     }
 }
@@ -1008,13 +1319,13 @@ unsafe fn insert<A: HalApi>(
 
             if let Some(start_state) = start_state {
                 unsafe { *start_state.simple.get_unchecked_mut(index) = TextureUses::COMPLEX };
-                start_state.complex.insert(index, complex.clone());
+                unsafe { *start_state.complex.get_unchecked_mut(index) = Some(complex.clone()) };
             }
 
             // We only need to insert ourselves the end state if there is no end state provider.
             if end_state_provider.is_none() {
                 unsafe { *end_state.simple.get_unchecked_mut(index) = TextureUses::COMPLEX };
-                end_state.complex.insert(index, complex);
+                unsafe { *end_state.complex.get_unchecked_mut(index) = Some(complex) };
             }
         }
     }
@@ -1044,7 +1355,7 @@ unsafe fn insert<A: HalApi>(
                 // We only need to insert into the end, as there is guaranteed to be
                 // a start state provider.
                 unsafe { *end_state.simple.get_unchecked_mut(index) = TextureUses::COMPLEX };
-                end_state.complex.insert(index, complex);
+                unsafe { *end_state.complex.get_unchecked_mut(index) = Some(complex) };
             }
         }
     }
@@ -1055,6 +1366,114 @@ unsafe fn insert<A: HalApi>(
     }
 }
 
+/// Reads the state `set` records for `index` at the single mip/layer
+/// subrange `layers` of mip `mip_id`, for [`backfill_start_from_other`].
+///
+/// `layers` is assumed to be wholly contained within a single run of `set`'s
+/// complex state for that mip (e.g. one produced by isolating on it), so a
+/// linear scan for the containing run is enough; this isn't called from any
+/// hot path.
+fn complex_state_at(set: &TextureStateSet, index: usize, mip_id: usize, layers: &Range<u32>) -> TextureUses {
+    let simple = set.simple[index];
+    if simple != TextureUses::COMPLEX {
+        return simple;
+    }
+
+    let complex = set.complex[index]
+        .as_ref()
+        .expect("simple state is COMPLEX, so a complex state must be present");
+
+    for &(ref range, state) in complex.mips[mip_id].iter() {
+        if range.start <= layers.start && layers.end <= range.end {
+            return state;
+        }
+    }
+
+    TextureUses::UNKNOWN
+}
+
+/// Backfills subresources of `self_start` that [`extend_complex`] is about to
+/// merge away the only record of: wherever `self_end` doesn't know anything
+/// about a subresource yet (still `UNKNOWN`) but `other_end` does, the
+/// subresource's start state is set to `other`'s *start* state, not its end
+/// state, so a barrier computed later against `self_start` reflects the
+/// texture's true initial layout rather than skipping straight to wherever
+/// `other` left it.
+///
+/// Must run before `self_end` is merged with `other_end`, since it reads
+/// `self_end`'s pre-merge state to find the still-unknown subresources.
+fn backfill_start_from_other(
+    self_start: &mut TextureStateSet,
+    self_end: &TextureStateSet,
+    index: usize,
+    other_start: &TextureStateSet,
+    other_end: &TextureStateSet,
+) {
+    let self_end_complex = match self_end.complex[index].as_ref() {
+        Some(complex) => complex,
+        None => return,
+    };
+    if self_start.complex[index].is_none() {
+        return;
+    }
+
+    for (mip_id, mip) in self_end_complex.mips.iter().enumerate() {
+        for &(ref layers, current_layer_state) in mip.iter() {
+            if current_layer_state != TextureUses::UNKNOWN {
+                continue;
+            }
+
+            let other_end_state = complex_state_at(other_end, index, mip_id, layers);
+            if other_end_state == TextureUses::UNKNOWN {
+                continue;
+            }
+
+            let other_start_state = complex_state_at(other_start, index, mip_id, layers);
+
+            let start_complex = unsafe { self_start.complex[index].as_mut().unwrap_unchecked() };
+            let start_mip = &mut start_complex.mips[mip_id];
+
+            for &mut (_, ref mut current_start_state) in start_mip.isolate(layers, TextureUses::UNKNOWN) {
+                strict_assert_eq!(*current_start_state, TextureUses::UNKNOWN);
+                *current_start_state = other_start_state;
+            }
+
+            start_mip.coalesce();
+        }
+    }
+}
+
+/// Reconciles `self`'s already-tracked start/end pair at `index` with
+/// `other`'s, for [`TextureTracker::extend_from_tracker`].
+///
+/// # Safety
+///
+/// `index` must already be a valid, in-bounds index tracked by both
+/// `self_start`/`self_end` and `other_start`/`other_end`.
+#[inline(always)]
+unsafe fn extend_complex<A: HalApi>(
+    texture_selector: &TextureSelector,
+    self_start: &mut TextureStateSet,
+    self_end: &mut TextureStateSet,
+    index: usize,
+    other_start: &TextureStateSet,
+    other_end: &TextureStateSet,
+    metadata_provider: ResourceMetadataProvider<'_, Texture<A>>,
+) -> Result<(), UsageConflict> {
+    backfill_start_from_other(self_start, self_end, index, other_start, other_end);
+
+    unsafe {
+        merge(
+            texture_selector,
+            self_end,
+            index,
+            TextureStateProvider::TextureSet { set: other_end },
+            metadata_provider,
+            None,
+        )
+    }
+}
+
 #[inline(always)]
 unsafe fn merge<A: HalApi>(
     texture_selector: &TextureSelector,
@@ -1062,16 +1481,32 @@ unsafe fn merge<A: HalApi>(
     index: usize,
     state_provider: TextureStateProvider<'_>,
     metadata_provider: ResourceMetadataProvider<'_, Texture<A>>,
+    undo_log: Option<&mut Vec<UndoEntry>>,
 ) -> Result<(), UsageConflict> {
     let current_simple = unsafe { current_state_set.simple.get_unchecked_mut(index) };
     let current_state = if *current_simple == TextureUses::COMPLEX {
         SingleOrManyStates::Many(unsafe {
-            current_state_set.complex.get_mut(&index).unwrap_unchecked()
+            current_state_set
+                .complex
+                .get_unchecked_mut(index)
+                .as_mut()
+                .unwrap_unchecked()
         })
     } else {
         SingleOrManyStates::Single(current_simple)
     };
 
+    // Record what this index held right before this merge touches it, so a
+    // checkpoint taken before this call can be undone without having had to
+    // eagerly clone every complex state up front.
+    if let Some(log) = undo_log {
+        let prior = match &current_state {
+            SingleOrManyStates::Single(state) => PriorTextureState::Simple(**state),
+            SingleOrManyStates::Many(complex) => PriorTextureState::Complex(complex.clone()),
+        };
+        log.push(UndoEntry { index, prior });
+    }
+
     let new_state = unsafe { state_provider.get_state(Some(texture_selector), index) };
 
     match (current_state, new_state) {
@@ -1130,7 +1565,7 @@ unsafe fn merge<A: HalApi>(
             }
 
             *current_simple = TextureUses::COMPLEX;
-            current_state_set.complex.insert(index, new_complex);
+            unsafe { *current_state_set.complex.get_unchecked_mut(index) = Some(new_complex) };
         }
         (SingleOrManyStates::Many(current_complex), SingleOrManyStates::Single(new_simple)) => {
             for (mip_id, mip) in current_complex.mips.iter_mut().enumerate() {
@@ -1208,6 +1643,9 @@ unsafe fn merge<A: HalApi>(
             }
         }
     }
+
+    demote_if_uniform(current_state_set, index);
+
     Ok(())
 }
 
@@ -1222,7 +1660,11 @@ unsafe fn barrier(
     let current_simple = unsafe { *current_state_set.simple.get_unchecked(index) };
     let current_state = if current_simple == TextureUses::COMPLEX {
         SingleOrManyStates::Many(unsafe {
-            current_state_set.complex.get(&index).unwrap_unchecked()
+            current_state_set
+                .complex
+                .get_unchecked(index)
+                .as_ref()
+                .unwrap_unchecked()
         })
     } else {
         SingleOrManyStates::Single(current_simple)
@@ -1348,13 +1790,23 @@ unsafe fn update(
     // If the state is simple, the first insert to the tracker would cover it.
     let mut start_complex = None;
     if start_simple == TextureUses::COMPLEX {
-        start_complex = Some(unsafe { start_state_set.complex.get_mut(&index).unwrap_unchecked() });
+        start_complex = Some(unsafe {
+            start_state_set
+                .complex
+                .get_unchecked_mut(index)
+                .as_mut()
+                .unwrap_unchecked()
+        });
     }
 
     let current_simple = unsafe { current_state_set.simple.get_unchecked_mut(index) };
     let current_state = if *current_simple == TextureUses::COMPLEX {
         SingleOrManyStates::Many(unsafe {
-            current_state_set.complex.get_mut(&index).unwrap_unchecked()
+            current_state_set
+                .complex
+                .get_unchecked_mut(index)
+                .as_mut()
+                .unwrap_unchecked()
         })
     } else {
         SingleOrManyStates::Single(current_simple)
@@ -1395,7 +1847,7 @@ unsafe fn update(
             }
 
             *current_simple = TextureUses::COMPLEX;
-            current_state_set.complex.insert(index, new_complex);
+            unsafe { *current_state_set.complex.get_unchecked_mut(index) = Some(new_complex) };
         }
         (SingleOrManyStates::Many(current_complex), SingleOrManyStates::Single(new_single)) => {
             for (mip_id, mip) in current_complex.mips.iter().enumerate() {
@@ -1421,7 +1873,7 @@ unsafe fn update(
             }
 
             unsafe { *current_state_set.simple.get_unchecked_mut(index) = new_single };
-            unsafe { current_state_set.complex.remove(&index).unwrap_unchecked() };
+            unsafe { current_state_set.complex.get_unchecked_mut(index).take().unwrap_unchecked() };
         }
         (SingleOrManyStates::Many(current_complex), SingleOrManyStates::Many(new_many)) => {
             for (selector, new_state) in new_many {
@@ -1475,4 +1927,6 @@ unsafe fn update(
             }
         }
     }
+
+    demote_if_uniform(current_state_set, index);
 }
@@ -0,0 +1,109 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use crate::LengthHint;
+use crate::Writeable;
+use core::fmt;
+
+/// Implements [`Writeable`] for an unsigned integer type by writing its decimal digits directly
+/// into a fixed stack buffer, back to front, short-circuiting `core::fmt`'s formatting
+/// machinery entirely. `$digits` must be large enough to hold `$ty::MAX` in decimal.
+macro_rules! impl_writeable_for_unsigned_int {
+    ($($ty:ty => $digits:literal),+ $(,)?) => {
+        $(
+            impl Writeable for $ty {
+                fn write_to<W: fmt::Write + ?Sized>(&self, sink: &mut W) -> fmt::Result {
+                    let mut n: $ty = *self;
+                    let mut buf = [0u8; $digits];
+                    let mut i = $digits;
+                    loop {
+                        i -= 1;
+                        if let Some(byte) = buf.get_mut(i) {
+                            *byte = b'0' + (n % 10) as u8;
+                        }
+                        n /= 10;
+                        if n == 0 {
+                            break;
+                        }
+                    }
+                    let digits = buf
+                        .get(i..)
+                        .and_then(|s| core::str::from_utf8(s).ok())
+                        .unwrap_or("");
+                    sink.write_str(digits)
+                }
+
+                fn writeable_length_hint(&self) -> LengthHint {
+                    LengthHint::exact(self.checked_ilog10().unwrap_or(0) as usize + 1)
+                }
+            }
+        )+
+    };
+}
+
+impl_writeable_for_unsigned_int!(
+    u8 => 3,
+    u16 => 5,
+    u32 => 10,
+    u64 => 20,
+    u128 => 39,
+    // usize is at most 64 bits on every platform this crate targets; 20 digits covers u64::MAX.
+    usize => 20,
+);
+
+/// Implements [`Writeable`] for a signed integer type on top of its unsigned counterpart's impl
+/// above: emit a leading `-` for negative values, then write the magnitude via `unsigned_abs`,
+/// which handles `$ty::MIN` correctly since its magnitude doesn't fit back in `$ty` itself.
+macro_rules! impl_writeable_for_signed_int {
+    ($($ty:ty => $uty:ty),+ $(,)?) => {
+        $(
+            impl Writeable for $ty {
+                fn write_to<W: fmt::Write + ?Sized>(&self, sink: &mut W) -> fmt::Result {
+                    if *self < 0 {
+                        sink.write_char('-')?;
+                    }
+                    Writeable::write_to(&self.unsigned_abs(), sink)
+                }
+
+                fn writeable_length_hint(&self) -> LengthHint {
+                    let sign_len: usize = if *self < 0 { 1 } else { 0 };
+                    LengthHint::exact(
+                        sign_len + self.unsigned_abs().writeable_length_hint().capacity(),
+                    )
+                }
+            }
+        )+
+    };
+}
+
+impl_writeable_for_signed_int!(
+    i8 => u8,
+    i16 => u16,
+    i32 => u32,
+    i64 => u64,
+    i128 => u128,
+    isize => usize,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_writeable_eq;
+
+    #[test]
+    fn test_unsigned_ints() {
+        assert_writeable_eq!(&0u8, "0");
+        assert_writeable_eq!(&u8::MAX, "255");
+        assert_writeable_eq!(&u64::MAX, "18446744073709551615");
+        assert_writeable_eq!(&u128::MAX, "340282366920938463463374607431768211455");
+    }
+
+    #[test]
+    fn test_signed_ints() {
+        assert_writeable_eq!(&0i8, "0");
+        assert_writeable_eq!(&(-1i32), "-1");
+        assert_writeable_eq!(&i32::MIN, "-2147483648");
+        assert_writeable_eq!(&i64::MIN, "-9223372036854775808");
+    }
+}
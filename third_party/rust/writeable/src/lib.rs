@@ -72,6 +72,7 @@ mod either;
 mod impls;
 mod ops;
 mod parts_write_adapter;
+pub mod render;
 mod testing;
 mod try_writeable;
 
@@ -224,6 +225,44 @@ pub trait Writeable {
         self.write_to(sink)
     }
 
+    /// Writes the `Writeable` to a new `String` and returns it together with a list of
+    /// `(start, end, Part)` byte ranges recording every [`PartsWrite::with_part`] call made
+    /// while writing, in the order the spans closed (so a nested part's range always appears
+    /// before the outer part's range that contains it).
+    ///
+    /// This is the stable, public way to consume a `Writeable`'s [`Part`] annotations -- for
+    /// example to feed them to the [`render`] module's [`AnsiWriteable`](render::AnsiWriteable)
+    /// or [`HtmlWriteable`](render::HtmlWriteable) adapters, or to compare against expected
+    /// parts in a test via [`assert_writeable_parts_eq`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use writeable::{Part, Writeable};
+    ///
+    /// const WORD: Part = Part {
+    ///     category: "foo",
+    ///     value: "word",
+    /// };
+    ///
+    /// struct Demo;
+    /// impl Writeable for Demo {
+    ///     fn write_to_parts<S: writeable::PartsWrite + ?Sized>(
+    ///         &self,
+    ///         sink: &mut S,
+    ///     ) -> core::fmt::Result {
+    ///         sink.with_part(WORD, |w| w.write_str("foo"))
+    ///     }
+    /// }
+    ///
+    /// let (string, parts) = Demo.write_to_parts_vec();
+    /// assert_eq!(string, "foo");
+    /// assert_eq!(parts, [(0, 3, WORD)]);
+    /// ```
+    fn write_to_parts_vec(&self) -> (String, alloc::vec::Vec<(usize, usize, Part)>) {
+        render::collect_parts(self)
+    }
+
     /// Returns a hint for the number of UTF-8 bytes that will be written to the sink.
     ///
     /// Override this method if it can be computed quickly.
@@ -0,0 +1,216 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! Consumers of the [`Part`] annotations produced by [`Writeable::write_to_parts`].
+//!
+//! This module turns the otherwise test-only parts-collection machinery into a usable output
+//! subsystem: [`collect_parts`] records `(start, end, Part)` byte ranges (backing
+//! [`Writeable::write_to_parts_vec`]), while [`AnsiWriteable`] and [`HtmlWriteable`] are
+//! [`PartsWrite`] sink adapters that render styled spans as the `Writeable` is written, without
+//! an intermediate `Vec`.
+
+use crate::Part;
+use crate::PartsWrite;
+use crate::Writeable;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A [`PartsWrite`] sink that records the string content together with the byte range of every
+/// [`with_part`](PartsWrite::with_part) call. Nested `with_part` calls are tracked with a stack
+/// of open `(start, Part)` frames; each frame is closed (producing one `(start, end, Part)`
+/// entry) when its closure returns, so an inner part's range always appears before its
+/// enclosing outer part's range in the result.
+struct PartsCollector {
+    string: String,
+    parts: Vec<(usize, usize, Part)>,
+    open: Vec<(usize, Part)>,
+}
+
+impl fmt::Write for PartsCollector {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.string.write_str(s)
+    }
+}
+
+impl PartsWrite for PartsCollector {
+    type SubPartsWrite = Self;
+
+    fn with_part(
+        &mut self,
+        part: Part,
+        mut f: impl FnMut(&mut Self::SubPartsWrite) -> fmt::Result,
+    ) -> fmt::Result {
+        self.open.push((self.string.len(), part));
+        let result = f(self);
+        if let Some((start, part)) = self.open.pop() {
+            self.parts.push((start, self.string.len(), part));
+        }
+        result
+    }
+}
+
+/// Backing implementation for [`Writeable::write_to_parts_vec`].
+pub(crate) fn collect_parts<W: Writeable + ?Sized>(w: &W) -> (String, Vec<(usize, usize, Part)>) {
+    let mut collector = PartsCollector {
+        string: String::new(),
+        parts: Vec::new(),
+        open: Vec::new(),
+    };
+    let _ = w.write_to_parts(&mut collector);
+    (collector.string, collector.parts)
+}
+
+/// A [`PartsWrite`] adapter that wraps an underlying [`fmt::Write`] sink and, for each
+/// [`with_part`](PartsWrite::with_part) span, writes an ANSI escape sequence looked up from a
+/// user-provided style table before the span's content and a reset code (`\x1b[0m`) after it.
+///
+/// The style table is a `Fn(Part) -> Option<&'static str>` consulted on every `with_part` call;
+/// parts the table maps to `None` are written with no escape codes at all. Nested parts nest
+/// their escape sequences in the same order the spans open and close.
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt::Write;
+/// use writeable::{Part, Writeable};
+/// use writeable::render::AnsiWriteable;
+///
+/// const BOLD: Part = Part { category: "demo", value: "bold" };
+///
+/// struct Demo;
+/// impl Writeable for Demo {
+///     fn write_to_parts<S: writeable::PartsWrite + ?Sized>(
+///         &self,
+///         sink: &mut S,
+///     ) -> std::fmt::Result {
+///         sink.with_part(BOLD, |w| w.write_str("hello"))
+///     }
+/// }
+///
+/// let mut buf = String::new();
+/// let mut ansi = AnsiWriteable::new(&mut buf, |part| {
+///     if part == BOLD { Some("\x1b[1m") } else { None }
+/// });
+/// Demo.write_to_parts(&mut ansi).unwrap();
+/// assert_eq!(buf, "\x1b[1mhello\x1b[0m");
+/// ```
+pub struct AnsiWriteable<'s, W: ?Sized, F> {
+    sink: &'s mut W,
+    style: F,
+}
+
+impl<'s, W, F> AnsiWriteable<'s, W, F>
+where
+    W: fmt::Write + ?Sized,
+    F: Fn(Part) -> Option<&'static str>,
+{
+    pub fn new(sink: &'s mut W, style: F) -> Self {
+        Self { sink, style }
+    }
+}
+
+impl<W, F> fmt::Write for AnsiWriteable<'_, W, F>
+where
+    W: fmt::Write + ?Sized,
+    F: Fn(Part) -> Option<&'static str>,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.sink.write_str(s)
+    }
+}
+
+impl<W, F> PartsWrite for AnsiWriteable<'_, W, F>
+where
+    W: fmt::Write + ?Sized,
+    F: Fn(Part) -> Option<&'static str>,
+{
+    type SubPartsWrite = Self;
+
+    fn with_part(
+        &mut self,
+        part: Part,
+        mut f: impl FnMut(&mut Self::SubPartsWrite) -> fmt::Result,
+    ) -> fmt::Result {
+        let escape = (self.style)(part);
+        if let Some(escape) = escape {
+            self.sink.write_str(escape)?;
+        }
+        let result = f(self);
+        if escape.is_some() {
+            self.sink.write_str("\u{1b}[0m")?;
+        }
+        result
+    }
+}
+
+/// A [`PartsWrite`] adapter that wraps an underlying [`fmt::Write`] sink and, for each
+/// [`with_part`](PartsWrite::with_part) span, wraps the span's content in
+/// `<span class="category value">...</span>`, HTML-escaping all written text (including inside
+/// spans) so that `Writeable` content containing `&`, `<`, `>`, or quote characters can't break
+/// out of the markup.
+///
+/// # Examples
+///
+/// ```
+/// use writeable::{Part, Writeable};
+/// use writeable::render::HtmlWriteable;
+///
+/// const NAME: Part = Part { category: "demo", value: "name" };
+///
+/// struct Demo;
+/// impl Writeable for Demo {
+///     fn write_to_parts<S: writeable::PartsWrite + ?Sized>(
+///         &self,
+///         sink: &mut S,
+///     ) -> std::fmt::Result {
+///         sink.with_part(NAME, |w| w.write_str("Alice & Bob"))
+///     }
+/// }
+///
+/// let mut buf = String::new();
+/// let mut html = HtmlWriteable::new(&mut buf);
+/// Demo.write_to_parts(&mut html).unwrap();
+/// assert_eq!(buf, "<span class=\"demo name\">Alice &amp; Bob</span>");
+/// ```
+pub struct HtmlWriteable<'s, W: ?Sized> {
+    sink: &'s mut W,
+}
+
+impl<'s, W: fmt::Write + ?Sized> HtmlWriteable<'s, W> {
+    pub fn new(sink: &'s mut W) -> Self {
+        Self { sink }
+    }
+}
+
+impl<W: fmt::Write + ?Sized> fmt::Write for HtmlWriteable<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '&' => self.sink.write_str("&amp;")?,
+                '<' => self.sink.write_str("&lt;")?,
+                '>' => self.sink.write_str("&gt;")?,
+                '"' => self.sink.write_str("&quot;")?,
+                '\'' => self.sink.write_str("&#39;")?,
+                _ => self.sink.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: fmt::Write + ?Sized> PartsWrite for HtmlWriteable<'_, W> {
+    type SubPartsWrite = Self;
+
+    fn with_part(
+        &mut self,
+        part: Part,
+        mut f: impl FnMut(&mut Self::SubPartsWrite) -> fmt::Result,
+    ) -> fmt::Result {
+        write!(self.sink, "<span class=\"{} {}\">", part.category, part.value)?;
+        let result = f(self);
+        self.sink.write_str("</span>")?;
+        result
+    }
+}